@@ -0,0 +1,123 @@
+// benches/wasm_analysis.rs
+//
+// Replaces the ad-hoc `Instant`-based timing checks in `tests/practical_test.rs`
+// with real, reproducible throughput numbers. `collect_corpus` recursively walks
+// `benches/corpus/`, loading every `.wasm` file as-is and assembling every
+// `.wat`/`.wast` module in-process, the way wasmparser's own benchmark harness
+// pulls test modules out of a directory of mixed binary/text fixtures. Each input
+// is benchmarked separately so a regression in one module doesn't get averaged
+// away by the rest of the corpus.
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::fs;
+use std::path::{Path, PathBuf};
+use wasm_inspector::{ModuleAnalyzer, WasmParser};
+
+struct CorpusEntry {
+    label: String,
+    bytes: Vec<u8>,
+}
+
+fn collect_corpus() -> Vec<CorpusEntry> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("benches/corpus");
+    let mut entries = Vec::new();
+    walk(&root, &root, &mut entries);
+    entries
+}
+
+fn walk(root: &Path, dir: &Path, entries: &mut Vec<CorpusEntry>) {
+    let read_dir = match fs::read_dir(dir) {
+        Ok(read_dir) => read_dir,
+        Err(_) => return,
+    };
+
+    for entry in read_dir.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            walk(root, &path, entries);
+            continue;
+        }
+
+        let label = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .display()
+            .to_string();
+
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("wasm") => {
+                if let Ok(bytes) = fs::read(&path) {
+                    entries.push(CorpusEntry { label, bytes });
+                }
+            }
+            Some("wat") => {
+                if let Ok(text) = fs::read_to_string(&path) {
+                    if let Ok(bytes) = wat::parse_str(&text) {
+                        entries.push(CorpusEntry { label, bytes });
+                    }
+                }
+            }
+            Some("wast") => collect_wast_modules(&path, &label, entries),
+            _ => {}
+        }
+    }
+}
+
+/// A `.wast` script can embed several modules; each becomes its own benchmark
+/// input, labeled `<path>#<directive index>`.
+fn collect_wast_modules(path: &PathBuf, label: &str, entries: &mut Vec<CorpusEntry>) {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(_) => return,
+    };
+    let buf = match wast::parser::ParseBuffer::new(&text) {
+        Ok(buf) => buf,
+        Err(_) => return,
+    };
+    let wast = match wast::parser::parse::<wast::Wast>(&buf) {
+        Ok(wast) => wast,
+        Err(_) => return,
+    };
+
+    for (i, directive) in wast.directives.into_iter().enumerate() {
+        if let wast::WastDirective::Module(mut quoted_module) = directive {
+            if let Ok(bytes) = quoted_module.encode() {
+                entries.push(CorpusEntry {
+                    label: format!("{}#{}", label, i),
+                    bytes,
+                });
+            }
+        }
+    }
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let corpus = collect_corpus();
+    let mut group = c.benchmark_group("WasmParser::parse");
+    for entry in &corpus {
+        group.throughput(Throughput::Bytes(entry.bytes.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(&entry.label), entry, |b, entry| {
+            b.iter(|| WasmParser::new(&entry.bytes).unwrap().parse().unwrap());
+        });
+    }
+    group.finish();
+}
+
+fn bench_analyze(c: &mut Criterion) {
+    let corpus = collect_corpus();
+    let mut group = c.benchmark_group("ModuleAnalyzer::analyze");
+    for entry in &corpus {
+        group.throughput(Throughput::Bytes(entry.bytes.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(&entry.label), entry, |b, entry| {
+            b.iter(|| {
+                let module_info = WasmParser::new(&entry.bytes).unwrap().parse().unwrap();
+                ModuleAnalyzer::new(module_info, &entry.bytes)
+                    .analyze()
+                    .unwrap()
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse, bench_analyze);
+criterion_main!(benches);
@@ -0,0 +1,37 @@
+#![no_main]
+// fuzz/fuzz_targets/analyze_wasm_module.rs
+//
+// Feeds libFuzzer's raw byte input through `wasm-smith` to get structurally-valid
+// (but otherwise arbitrary) WASM modules, then runs them through the full analysis
+// pipeline. The only thing asserted here is "doesn't panic" — see
+// `tests/proptest_fuzz.rs` for the cross-field invariant checks, which run as a
+// normal, fast `cargo test` rather than requiring a nightly cargo-fuzz toolchain.
+use libfuzzer_sys::fuzz_target;
+
+// Broadened past `Config::default()` so generated modules exercise reference
+// types, SIMD, bulk memory, and tail calls too — not just the MVP feature set —
+// since those are exactly the encodings most likely to trip up section-walking
+// code that wasn't written with them in mind. Kept in sync with the identical
+// helper in `tests/proptest_fuzz.rs` (separate crates, so it can't be shared).
+fn fuzzing_config() -> wasm_smith::Config {
+    wasm_smith::Config {
+        reference_types_enabled: true,
+        multi_value_enabled: true,
+        bulk_memory_enabled: true,
+        simd_enabled: true,
+        tail_call_enabled: true,
+        threads_enabled: true,
+        saturating_float_to_int_enabled: true,
+        sign_extension_ops_enabled: true,
+        ..Default::default()
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let mut u = arbitrary::Unstructured::new(data);
+    let module = match wasm_smith::Module::new(fuzzing_config(), &mut u) {
+        Ok(module) => module,
+        Err(_) => return,
+    };
+    let _ = wasm_inspector::analyze_wasm_module(&module.to_bytes());
+});
@@ -4,25 +4,60 @@ use crate::graph::CallGraphBuilder;
 use crate::security::SecurityAnalyzer;
 use crate::types::*;
 use crate::memory::{MemoryAnalyzer, MemoryAnalysisResult}; // Added MemoryAnalyzer and Result
+use crate::cfg::{self, Cfg};
+use crate::stackdepth;
+use crate::targets;
+use crate::complexity;
+use crate::profile::Profiler;
 use anyhow::Result;
+use std::collections::BTreeMap;
 
 
 pub struct ModuleAnalyzer<'a> { // Added lifetime 'a
     module_info: ModuleInfo,
     wasm_bytes: &'a [u8], // Added wasm_bytes
+    /// User-supplied deployment target profiles (e.g. loaded from a
+    /// `wrangler.toml`-style manifest), evaluated alongside the six built-in
+    /// runtimes. Empty unless set via `with_target_profiles`.
+    target_profiles: Vec<targets::TargetProfile>,
 }
 
 impl<'a> ModuleAnalyzer<'a> { // Added lifetime 'a
     pub fn new(module_info: ModuleInfo, wasm_bytes: &'a [u8]) -> Self { // Modified signature
-        Self { module_info, wasm_bytes }
+        Self { module_info, wasm_bytes, target_profiles: Vec::new() }
+    }
+
+    /// Add user-supplied deployment target profiles to evaluate alongside the
+    /// six built-ins, replacing any built-in of the same name.
+    pub fn with_target_profiles(mut self, target_profiles: Vec<targets::TargetProfile>) -> Self {
+        self.target_profiles = target_profiles;
+        self
     }
 
     pub fn analyze(&mut self) -> Result<ModuleAnalysis> {
-        let call_graph = self.build_call_graph()?;
-        let security_analysis = self.analyze_security()?;
-        let performance_metrics = self.analyze_performance(&call_graph)?; // Pass call_graph
-        let compatibility = self.analyze_compatibility()?;
-        let memory_analysis = self.analyze_memory_patterns()?; // Added memory analysis call
+        let mut profiler = Profiler::new(false);
+        self.analyze_profiled(&mut profiler)
+    }
+
+    /// Same analysis as `analyze`, but each pass is timed through `profiler`.
+    /// When `profiler` was built with `Profiler::new(false)` this costs one
+    /// boolean check per pass, so `analyze` just delegates here.
+    pub fn analyze_profiled(&mut self, profiler: &mut Profiler) -> Result<ModuleAnalysis> {
+        let total_functions = self.module_info.functions.len() as u32;
+
+        let call_graph = profiler.record("call_graph", total_functions, || self.build_call_graph())?;
+        let security_analysis = profiler.record("security", total_functions, || self.analyze_security(&call_graph))?;
+        let performance_metrics = profiler.record("performance", total_functions, || {
+            self.analyze_performance(&call_graph) // Pass call_graph
+        })?;
+        let compatibility = profiler.record("compatibility", total_functions, || self.analyze_compatibility())?;
+        let memory_analysis = profiler.record("memory", total_functions, || {
+            self.analyze_memory_patterns(&call_graph) // Added memory analysis call
+        })?;
+        let control_flow_graphs = cfg::build_all_cfgs(&self.module_info, self.wasm_bytes)?;
+        let capability_report = self.analyze_capabilities();
+        let conformance = self.analyze_conformance();
+        let liveness = self.module_info.compute_live_set();
 
         Ok(ModuleAnalysis {
             module_info: self.module_info.clone(),
@@ -31,9 +66,257 @@ impl<'a> ModuleAnalyzer<'a> { // Added lifetime 'a
             performance_metrics,
             compatibility,
             memory_analysis, // Added memory_analysis field
+            control_flow_graphs,
+            capability_report,
+            conformance,
+            liveness,
         })
     }
 
+    /// Export names recognized as well-known entry points across common WASM runtimes
+    /// and contract ABIs (WASI commands/reactors, CosmWasm-style contracts, etc.).
+    const WELL_KNOWN_ENTRY_POINTS: &'static [&'static str] = &[
+        "_start",
+        "_initialize",
+        "main",
+        "instantiate",
+        "execute",
+        "query",
+        "migrate",
+        "sudo",
+        "reply",
+    ];
+
+    /// Static capability/entry-point summary, using the default set of built-in
+    /// runtime/contract profiles. See `analyze_capabilities_with_profiles` to check
+    /// against a caller-supplied profile list instead.
+    pub fn analyze_capabilities(&self) -> CapabilityReport {
+        self.analyze_capabilities_with_profiles(&Self::default_entry_point_profiles())
+    }
+
+    /// Static capability/entry-point summary: groups required host imports by module
+    /// namespace and checks `profiles` (sets of required export names) against the
+    /// module's actual exports, purely from the already-parsed `imports`/`exports` —
+    /// no instantiation required.
+    pub fn analyze_capabilities_with_profiles(
+        &self,
+        profiles: &[EntryPointProfile],
+    ) -> CapabilityReport {
+        let mut required_imports_by_namespace: BTreeMap<String, u32> = BTreeMap::new();
+        for import in &self.module_info.imports {
+            if matches!(import.kind, ImportKind::Function { .. }) {
+                *required_imports_by_namespace
+                    .entry(import.module.clone())
+                    .or_insert(0) += 1;
+            }
+        }
+
+        let export_names: std::collections::HashSet<&str> = self
+            .module_info
+            .exports
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect();
+
+        let present_entry_points = Self::WELL_KNOWN_ENTRY_POINTS
+            .iter()
+            .filter(|name| export_names.contains(*name))
+            .map(|name| name.to_string())
+            .collect();
+
+        let profile_results = profiles
+            .iter()
+            .map(|profile| {
+                let missing_exports: Vec<String> = profile
+                    .required_exports
+                    .iter()
+                    .filter(|name| !export_names.contains(name.as_str()))
+                    .cloned()
+                    .collect();
+                EntryPointProfileResult {
+                    name: profile.name.clone(),
+                    satisfied: missing_exports.is_empty(),
+                    missing_exports,
+                }
+            })
+            .collect();
+
+        CapabilityReport {
+            required_imports_by_namespace,
+            present_entry_points,
+            profiles: profile_results,
+        }
+    }
+
+    /// Built-in entry-point profiles for common runtimes/contract ABIs, used by
+    /// `analyze_capabilities`. CosmWasm's own `Cache::analyze` checks a similar
+    /// fixed set of required/optional exports before allowing instantiation.
+    fn default_entry_point_profiles() -> Vec<EntryPointProfile> {
+        vec![
+            EntryPointProfile::new(
+                "WASI Command",
+                vec!["_start".to_string()],
+            ),
+            EntryPointProfile::new(
+                "WASI Reactor",
+                vec!["_initialize".to_string()],
+            ),
+            EntryPointProfile::new(
+                "CosmWasm Contract",
+                vec![
+                    "instantiate".to_string(),
+                    "execute".to_string(),
+                    "query".to_string(),
+                ],
+            ),
+        ]
+    }
+
+    /// Conformance summary against the built-in set of named interfaces (WASI
+    /// command/reactor). See `analyze_conformance_with_specs` to check against
+    /// caller-supplied `InterfaceSpec`s instead (e.g. a custom contract ABI).
+    pub fn analyze_conformance(&self) -> Vec<ConformanceReport> {
+        self.analyze_conformance_with_specs(&Self::default_interface_specs())
+    }
+
+    /// Check the module against each `InterfaceSpec`, matching not just export
+    /// *names* but their resolved `params`/`results` signatures, and cross-
+    /// checking the required/forbidden import rules (same `"module.name"` /
+    /// `"module.*"` syntax as `policy::Policy`).
+    pub fn analyze_conformance_with_specs(&self, specs: &[InterfaceSpec]) -> Vec<ConformanceReport> {
+        specs.iter().map(|spec| self.check_conformance(spec)).collect()
+    }
+
+    fn check_conformance(&self, spec: &InterfaceSpec) -> ConformanceReport {
+        let mut missing_exports = Vec::new();
+        let mut signature_mismatches = Vec::new();
+
+        for (export_name, expected_signature) in &spec.required_exports {
+            match self.exported_function_signature(export_name) {
+                None => missing_exports.push(export_name.clone()),
+                Some((params, results)) => {
+                    if !expected_signature.matches(&params, &results) {
+                        signature_mismatches.push(format!(
+                            "\"{}\": expected {:?} -> {:?}, found {:?} -> {:?}",
+                            export_name,
+                            expected_signature.params,
+                            expected_signature.results,
+                            params,
+                            results
+                        ));
+                    }
+                }
+            }
+        }
+
+        let missing_imports: Vec<String> = spec
+            .required_imports
+            .iter()
+            .filter(|rule| {
+                !self
+                    .module_info
+                    .imports
+                    .iter()
+                    .any(|i| InterfaceSpec::import_rule_matches(rule.as_str(), &i.module, &i.name))
+            })
+            .cloned()
+            .collect();
+
+        let forbidden_imports_present: Vec<String> = self
+            .module_info
+            .imports
+            .iter()
+            .filter(|i| {
+                spec.forbidden_imports
+                    .iter()
+                    .any(|rule| InterfaceSpec::import_rule_matches(rule.as_str(), &i.module, &i.name))
+            })
+            .map(|i| format!("{}.{}", i.module, i.name))
+            .collect();
+
+        let forbidden_exports_present: Vec<String> = self
+            .module_info
+            .exports
+            .iter()
+            .filter(|e| e.kind == ExportKind::Function && spec.forbidden_exports.contains(&e.name))
+            .map(|e| e.name.clone())
+            .collect();
+
+        let conforms = missing_exports.is_empty()
+            && signature_mismatches.is_empty()
+            && missing_imports.is_empty()
+            && forbidden_imports_present.is_empty()
+            && forbidden_exports_present.is_empty();
+
+        ConformanceReport {
+            profile_name: spec.name.clone(),
+            conforms,
+            missing_exports,
+            signature_mismatches,
+            missing_imports,
+            forbidden_imports_present,
+            forbidden_exports_present,
+        }
+    }
+
+    /// Resolve a function export's signature, whether it's a genuinely defined
+    /// function or a thin re-export of an imported one (see `Export::points_to_import`).
+    fn exported_function_signature(&self, export_name: &str) -> Option<(Vec<ValType>, Vec<ValType>)> {
+        let export = self
+            .module_info
+            .exports
+            .iter()
+            .find(|e| e.kind == ExportKind::Function && e.name == export_name)?;
+
+        if export.points_to_import {
+            self.module_info
+                .imports
+                .iter()
+                .filter(|i| matches!(i.kind, ImportKind::Function { .. }))
+                .nth(export.resolved_index as usize)
+                .and_then(|i| match &i.kind {
+                    ImportKind::Function { params, results, .. } => {
+                        Some((params.clone(), results.clone()))
+                    }
+                    _ => None,
+                })
+        } else {
+            self.module_info
+                .functions
+                .get(export.resolved_index as usize)
+                .map(|f| (f.params.clone(), f.results.clone()))
+        }
+    }
+
+    /// Built-in named interfaces checked by `analyze_conformance`. WASI profiles
+    /// only require the entry point to exist (`ExpectedSignature::any()`) since
+    /// the WASI Preview 1 ABI defines `_start`/`_initialize` as `() -> ()` but we
+    /// don't want a module that (harmlessly) returns a status code to be flagged
+    /// non-conforming.
+    fn default_interface_specs() -> Vec<InterfaceSpec> {
+        vec![
+            InterfaceSpec::new("WASI Command")
+                .require_export("_start", ExpectedSignature::any())
+                .require_import("wasi.*")
+                .forbid_export("_initialize"),
+            InterfaceSpec::new("WASI Reactor")
+                .require_export("_initialize", ExpectedSignature::any())
+                .require_import("wasi.*")
+                .forbid_export("_start"),
+        ]
+    }
+
+    /// Build the control-flow graph for a single defined function, by global function index.
+    pub fn build_cfg(&self, func_index: u32) -> Result<Cfg> {
+        let imported_function_count = self
+            .module_info
+            .imports
+            .iter()
+            .filter(|i| matches!(i.kind, ImportKind::Function { .. }))
+            .count() as u32;
+        cfg::build_cfg(self.wasm_bytes, imported_function_count, func_index)
+    }
+
     fn build_call_graph(&self) -> Result<CallGraph> {
         // CallGraphBuilder now uses module_info.function_call_instructions,
         // which are populated by WasmParser. No need to pass wasm_bytes to CallGraphBuilder.
@@ -41,9 +324,9 @@ impl<'a> ModuleAnalyzer<'a> { // Added lifetime 'a
         builder.build()
     }
 
-    fn analyze_security(&self) -> Result<SecurityAnalysis> {
-        let analyzer = SecurityAnalyzer::new(&self.module_info);
-        analyzer.analyze()
+    fn analyze_security(&self, call_graph: &CallGraph) -> Result<SecurityAnalysis> {
+        let analyzer = SecurityAnalyzer::new(&self.module_info, self.wasm_bytes);
+        analyzer.analyze(call_graph)
     }
 
     fn analyze_performance(&self, call_graph: &CallGraph) -> Result<PerformanceMetrics> { // Take call_graph
@@ -52,7 +335,13 @@ impl<'a> ModuleAnalyzer<'a> { // Added lifetime 'a
         let estimated_cold_start_ms = self.estimate_cold_start_time();
         let complexity_score = self.calculate_complexity_score();
         let memory_usage_estimate = self.estimate_memory_usage();
-        let optimization_suggestions = self.generate_optimization_suggestions(call_graph); // Pass call_graph
+        let function_complexity = complexity::analyze_all(&self.module_info, self.wasm_bytes, 10)
+            .unwrap_or_else(|_| complexity::ComplexityReport {
+                histogram: std::collections::BTreeMap::new(),
+                most_complex: Vec::new(),
+            });
+        let optimization_suggestions =
+            self.generate_optimization_suggestions(call_graph, &function_complexity); // Pass call_graph
 
         Ok(PerformanceMetrics {
             module_size,
@@ -61,34 +350,38 @@ impl<'a> ModuleAnalyzer<'a> { // Added lifetime 'a
             complexity_score,
             memory_usage_estimate,
             optimization_suggestions,
+            function_complexity,
         })
     }
 
+    /// Evaluate the module against the six built-in `TargetProfile`s plus any
+    /// user-supplied ones (one generic evaluator shared by every target,
+    /// rather than a bespoke `check_*_compatibility` method each) and collect
+    /// the results into `targets`, keyed by profile name.
     fn analyze_compatibility(&self) -> Result<CompatibilityMatrix> {
         let detected_language = self.detect_source_language();
+        let module_size = self.calculate_module_size();
 
-        // Analyze compatibility with different runtimes
-        let wasmtime = self.check_wasmtime_compatibility();
-        let wasmer = self.check_wasmer_compatibility();
-        let browser = self.check_browser_compatibility();
-        let node_js = self.check_nodejs_compatibility();
-        let deno = self.check_deno_compatibility();
-        let cloudflare_workers = self.check_cloudflare_workers_compatibility();
-
-        Ok(CompatibilityMatrix {
-            wasmtime,
-            wasmer,
-            browser,
-            node_js,
-            deno,
-            cloudflare_workers,
-            detected_language,
-        })
+        let targets = targets::evaluate_all(&self.module_info, module_size, &self.target_profiles)
+            .into_iter()
+            .map(|e| {
+                (
+                    e.profile_name,
+                    CompatibilityStatus {
+                        compatible: e.compatible,
+                        issues: e.issues,
+                        required_features: e.required_features,
+                    },
+                )
+            })
+            .collect();
+
+        Ok(CompatibilityMatrix { targets, detected_language })
     }
 
-    fn analyze_memory_patterns(&self) -> Result<MemoryAnalysisResult> {
+    fn analyze_memory_patterns(&self, call_graph: &CallGraph) -> Result<MemoryAnalysisResult> {
         let mut mem_analyzer = MemoryAnalyzer::new(&self.module_info, self.wasm_bytes);
-        mem_analyzer.analyze()
+        mem_analyzer.analyze(call_graph)
     }
 
 
@@ -182,27 +475,19 @@ impl<'a> ModuleAnalyzer<'a> { // Added lifetime 'a
             (0, None)
         };
 
-        // Estimate stack usage based on function complexity
-        let stack_estimate = self
-            .module_info
-            .functions
-            .iter()
-            .map(|f| {
-                // Estimate stack usage per function based on locals
-                f.locals
+        // Maximum operand-stack depth over all functions, via abstract
+        // interpretation of the bytecode rather than summing declared locals,
+        // converted to KB at 8 bytes per stack slot.
+        let stack_estimate = stackdepth::analyze_all(&self.module_info, self.wasm_bytes)
+            .map(|depths| {
+                depths
                     .iter()
-                    .map(|l| {
-                        let type_size = match l.value_type.as_str() {
-                            "i32" | "f32" => 4,
-                            "i64" | "f64" => 8,
-                            _ => 8, // Conservative estimate
-                        };
-                        l.count * type_size
-                    })
-                    .sum::<u32>()
+                    .map(|d| d.max_stack_depth)
+                    .max()
+                    .unwrap_or(0)
             })
-            .max()
             .unwrap_or(0)
+            * 8
             / 1024; // Convert to KB
 
         MemoryUsageEstimate {
@@ -212,9 +497,42 @@ impl<'a> ModuleAnalyzer<'a> { // Added lifetime 'a
         }
     }
 
-    fn generate_optimization_suggestions(&self, call_graph: &CallGraph) -> Vec<OptimizationSuggestion> {
+    fn generate_optimization_suggestions(
+        &self,
+        call_graph: &CallGraph,
+        function_complexity: &complexity::ComplexityReport,
+    ) -> Vec<OptimizationSuggestion> {
         let mut suggestions = Vec::new();
 
+        // Functions above this cyclomatic complexity are dense enough in
+        // control flow to be worth refactoring, or risky to inline verbatim.
+        const COMPLEXITY_THRESHOLD: u32 = 10;
+        let hotspots: Vec<&complexity::FunctionComplexity> = function_complexity
+            .most_complex
+            .iter()
+            .filter(|f| f.cyclomatic_complexity > COMPLEXITY_THRESHOLD)
+            .collect();
+        if !hotspots.is_empty() {
+            let worst = &hotspots[0];
+            let worst_label = worst
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("func_{}", worst.function_index));
+            suggestions.push(OptimizationSuggestion {
+                category: "Complexity".to_string(),
+                description: format!(
+                    "{} function(s) exceed a cyclomatic complexity of {} (worst: {} at {})",
+                    hotspots.len(),
+                    COMPLEXITY_THRESHOLD,
+                    worst_label,
+                    worst.cyclomatic_complexity
+                ),
+                potential_savings: Some(
+                    "Refactor into smaller functions, or avoid inlining these call sites".to_string(),
+                ),
+            });
+        }
+
         // Check for dead code using the accurate call graph
         if !call_graph.unreachable_functions.is_empty() {
             suggestions.push(OptimizationSuggestion {
@@ -276,6 +594,32 @@ impl<'a> ModuleAnalyzer<'a> { // Added lifetime 'a
         suggestions
     }
 
+    /// Majority language among functions whose raw name actually demangled as
+    /// Rust or Itanium C++, or `None` if nothing demangled (plain/unmangled
+    /// names, or too few functions to have a name at all).
+    fn majority_demangled_language(&self) -> Option<&'static str> {
+        use crate::parser::DemangledLanguage;
+
+        let (mut rust_count, mut cpp_count) = (0u32, 0u32);
+        for function in &self.module_info.functions {
+            if let Some(ref name) = function.name {
+                match crate::parser::demangle_symbol_with_language(name) {
+                    Some((_, DemangledLanguage::Rust)) => rust_count += 1,
+                    Some((_, DemangledLanguage::Cpp)) => cpp_count += 1,
+                    None => {}
+                }
+            }
+        }
+
+        if rust_count == 0 && cpp_count == 0 {
+            None
+        } else if rust_count >= cpp_count {
+            Some("Rust")
+        } else {
+            Some("C++ (Itanium)")
+        }
+    }
+
     fn detect_source_language(&self) -> Option<String> {
         // Analyze patterns to detect source language
         let custom_section_names: std::collections::HashSet<_> = self
@@ -285,6 +629,26 @@ impl<'a> ModuleAnalyzer<'a> { // Added lifetime 'a
             .map(|c| c.name.as_str())
             .collect();
 
+        // wit-bindgen generates this exact memory-management export pair on the
+        // core module it produces for a component's "guest" side, regardless of
+        // which Component Model world it implements, so it's recognized ahead of
+        // the generic Rust/C-ABI patterns below.
+        if self
+            .module_info
+            .exports
+            .iter()
+            .any(|e| e.name == "cabi_realloc" || e.name.starts_with("cabi_post_"))
+        {
+            return Some("Rust (wit-bindgen component adapter)".to_string());
+        }
+
+        // Actually demangling a symbol is stronger evidence than the naming
+        // conventions below, so a clear majority among demangled functions
+        // wins ahead of them.
+        if let Some(language) = self.majority_demangled_language() {
+            return Some(language.to_string());
+        }
+
         // Rust patterns
         if custom_section_names.contains("name")
             || self
@@ -335,171 +699,142 @@ impl<'a> ModuleAnalyzer<'a> { // Added lifetime 'a
         None
     }
 
-    fn check_wasmtime_compatibility(&self) -> CompatibilityStatus {
-        let mut issues = Vec::new();
-        let mut required_features = Vec::new();
-
-        // Check for WASI usage
-        if self
-            .module_info
-            .imports
-            .iter()
-            .any(|i| i.module.starts_with("wasi"))
-        {
-            required_features.push("WASI support".to_string());
-        }
-
-        // Check for multi-memory (not widely supported yet)
-        if self.module_info.memory.is_some()
-            && self
-                .module_info
-                .imports
-                .iter()
-                .any(|i| matches!(i.kind, ImportKind::Memory { .. }))
-        {
-            issues.push("Multiple memory instances may not be supported".to_string());
-        }
+}
 
-        CompatibilityStatus {
-            compatible: issues.is_empty(),
-            issues,
-            required_features,
-        }
+/// Groups component-level imports/exports by WIT interface name, i.e. everything
+/// up to (and not including) the last `/` in the debug-formatted name — `wasi:
+/// io/poll@0.2.0` and `wasi:io/poll@0.2.0#[method]pollable.ready` both group
+/// under `wasi:io/poll@0.2.0`. Names with no `/` (bare function imports/exports,
+/// like a plain `cabi_realloc`) group under themselves.
+fn interface_group_key(name: &str) -> String {
+    match name.rsplit_once('/') {
+        Some((interface, _)) => interface.to_string(),
+        None => name.to_string(),
     }
+}
 
-    fn check_wasmer_compatibility(&self) -> CompatibilityStatus {
-        // Similar to wasmtime but with different limitations
-        let issues = Vec::new();
-        let mut required_features = Vec::new();
-
-        if self
-            .module_info
-            .imports
-            .iter()
-            .any(|i| i.module.starts_with("wasi"))
-        {
-            required_features.push("WASI support".to_string());
-        }
-
-        CompatibilityStatus {
-            compatible: issues.is_empty(),
-            issues,
-            required_features,
-        }
+fn group_component_imports(imports: &[ComponentImport]) -> BTreeMap<String, Vec<ComponentImport>> {
+    let mut groups: BTreeMap<String, Vec<ComponentImport>> = BTreeMap::new();
+    for import in imports {
+        groups
+            .entry(interface_group_key(&import.name))
+            .or_default()
+            .push(import.clone());
     }
+    groups
+}
 
-    fn check_browser_compatibility(&self) -> CompatibilityStatus {
-        let mut issues = Vec::new();
-        let mut required_features = Vec::new();
-
-        // WASI is not natively supported in browsers
-        if self
-            .module_info
-            .imports
-            .iter()
-            .any(|i| i.module.starts_with("wasi"))
-        {
-            issues.push("WASI imports require polyfill in browser".to_string());
-            required_features.push("WASI polyfill".to_string());
-        }
-
-        // Check for file system access
-        if self
-            .module_info
-            .imports
-            .iter()
-            .any(|i| i.name.contains("fd_") || i.name.contains("path_") || i.name.contains("file"))
-        {
-            issues.push("File system access not available in browser sandbox".to_string());
-        }
-
-        // Large memory usage might be problematic
-        if let Some(ref memory) = self.module_info.memory {
-            if memory.initial > 1000 {
-                // > ~64MB
-                issues.push("Large initial memory allocation may fail in browser".to_string());
-            }
-        }
-
-        CompatibilityStatus {
-            compatible: issues.is_empty(),
-            issues,
-            required_features,
-        }
+fn group_component_exports(exports: &[ComponentExport]) -> BTreeMap<String, Vec<ComponentExport>> {
+    let mut groups: BTreeMap<String, Vec<ComponentExport>> = BTreeMap::new();
+    for export in exports {
+        groups
+            .entry(interface_group_key(&export.name))
+            .or_default()
+            .push(export.clone());
     }
+    groups
+}
 
-    fn check_nodejs_compatibility(&self) -> CompatibilityStatus {
-        let issues = Vec::new();
-        let mut required_features = Vec::new();
-
-        if self
-            .module_info
-            .imports
-            .iter()
-            .any(|i| i.module.starts_with("wasi"))
-        {
-            required_features
-                .push("Node.js WASI support (--experimental-wasi-unstable-preview1)".to_string());
-        }
-
+/// Component Model binaries are a much newer target than core modules: as of
+/// this writing only Wasmtime has stable component-model support, jco lets
+/// components run (transpiled) in a browser/Node, and Wasmer/Deno/Cloudflare
+/// Workers don't support the format at all.
+fn analyze_component_compatibility(info: &ComponentInfo) -> CompatibilityMatrix {
+    let uses_wasi = info
+        .imports
+        .iter()
+        .any(|i| i.name.starts_with("wasi:") || i.name.contains("wasi:"));
+
+    let wasmtime_features = if uses_wasi {
+        vec!["WASI Preview 2 / component-model support".to_string()]
+    } else {
+        vec!["component-model support".to_string()]
+    };
+
+    let mut targets = BTreeMap::new();
+    targets.insert(
+        "wasmtime".to_string(),
         CompatibilityStatus {
-            compatible: true, // Node.js has good WASM support
-            issues,
-            required_features,
-        }
-    }
-
-    fn check_deno_compatibility(&self) -> CompatibilityStatus {
-        let issues = Vec::new();
-        let mut required_features = Vec::new();
-
-        if self
-            .module_info
-            .imports
-            .iter()
-            .any(|i| i.module.starts_with("wasi"))
-        {
-            required_features
-                .push("Deno WASI support (--allow-read, --allow-write flags)".to_string());
-        }
-
+            compatible: true,
+            issues: Vec::new(),
+            required_features: wasmtime_features,
+        },
+    );
+    targets.insert(
+        "wasmer".to_string(),
         CompatibilityStatus {
-            compatible: true, // Deno has good WASM support
-            issues,
-            required_features,
-        }
+            compatible: false,
+            issues: vec!["Component Model support is experimental/partial".to_string()],
+            required_features: Vec::new(),
+        },
+    );
+    targets.insert(
+        "browser".to_string(),
+        CompatibilityStatus {
+            compatible: false,
+            issues: vec!["Components must be transpiled (e.g. via jco) before running in a browser".to_string()],
+            required_features: vec!["jco transpile".to_string()],
+        },
+    );
+    targets.insert(
+        "node_js".to_string(),
+        CompatibilityStatus {
+            compatible: false,
+            issues: vec!["Components must be transpiled (e.g. via jco) to run on Node.js".to_string()],
+            required_features: vec!["jco transpile".to_string()],
+        },
+    );
+    targets.insert(
+        "deno".to_string(),
+        CompatibilityStatus {
+            compatible: false,
+            issues: vec!["No native Component Model support".to_string()],
+            required_features: Vec::new(),
+        },
+    );
+    targets.insert(
+        "cloudflare_workers".to_string(),
+        CompatibilityStatus {
+            compatible: false,
+            issues: vec!["No Component Model support".to_string()],
+            required_features: Vec::new(),
+        },
+    );
+
+    CompatibilityMatrix {
+        targets,
+        detected_language: detect_component_source_language(info),
     }
+}
 
-    fn check_cloudflare_workers_compatibility(&self) -> CompatibilityStatus {
-        let mut issues = Vec::new();
-        let required_features = Vec::new();
-
-        // Cloudflare Workers has strict limitations
-        if self
-            .module_info
-            .imports
-            .iter()
-            .any(|i| i.module.starts_with("wasi"))
-        {
-            issues.push("WASI not supported in Cloudflare Workers".to_string());
-        }
-
-        if let Some(ref memory) = self.module_info.memory {
-            if memory.initial > 128 {
-                // > ~8MB
-                issues.push("Memory limit exceeded for Cloudflare Workers".to_string());
-            }
-        }
-
-        // Check module size limit (1MB compressed)
-        if self.calculate_module_size() > 1_000_000 {
-            issues.push("Module may exceed Cloudflare Workers size limit".to_string());
-        }
+/// Best-effort source-language guess from component-level export shapes. Real
+/// signal is limited without descending into nested core modules (see
+/// `ComponentInfo`'s doc comment), so this only recognizes the wit-bindgen
+/// memory-management exports that show up verbatim in the debug-formatted
+/// export name regardless of which language's bindgen emitted them.
+fn detect_component_source_language(info: &ComponentInfo) -> Option<String> {
+    let has_cabi_exports = info
+        .exports
+        .iter()
+        .any(|e| e.name.contains("cabi_realloc") || e.name.contains("cabi_post_"));
+
+    if has_cabi_exports {
+        Some("wit-bindgen adapter (language undetermined without nested-module inspection)".to_string())
+    } else {
+        None
+    }
+}
 
-        CompatibilityStatus {
-            compatible: issues.is_empty(),
-            issues,
-            required_features,
-        }
+/// Analyzes a Component Model binary: interface grouping, runtime compatibility,
+/// and a best-effort source-language guess. The lighter-weight counterpart to
+/// `ModuleAnalyzer::analyze` for when `WasmParser::parse` returns
+/// `ParsedArtifact::Component` instead of `ParsedArtifact::Module`.
+pub fn analyze_component(component_info: &ComponentInfo) -> ComponentAnalysis {
+    ComponentAnalysis {
+        interfaces_imported: group_component_imports(&component_info.imports),
+        interfaces_exported: group_component_exports(&component_info.exports),
+        compatibility: analyze_component_compatibility(component_info),
+        detected_source_language: detect_component_source_language(component_info),
+        component_info: component_info.clone(),
     }
 }
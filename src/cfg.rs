@@ -0,0 +1,302 @@
+// ===== cfg.rs =====
+// src/cfg.rs
+use crate::types::*;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use wasmparser::{Operator, Parser, Payload};
+
+/// A single basic block: a contiguous run of operators with one entry and,
+/// until a terminator/branch instruction is hit, one implicit exit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BasicBlock {
+    pub id: usize,
+    /// Index of the first operator (within the function's operator stream) in this block.
+    pub start_op: u32,
+    /// Index one-past the last operator in this block.
+    pub end_op: u32,
+    /// True if the block ends in `return`/`unreachable` (no fall-through successor).
+    pub is_terminal: bool,
+}
+
+/// Adjacency-list control-flow graph for a single function.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cfg {
+    pub function_index: u32,
+    pub blocks: Vec<BasicBlock>,
+    pub edges: Vec<(usize, usize)>,
+}
+
+enum FrameKind {
+    Block,
+    Loop,
+    If { else_block: usize, has_else: bool },
+}
+
+struct Frame {
+    kind: FrameKind,
+    /// Block a `br`/`br_if`/`br_table` targeting this depth jumps to.
+    label_target: usize,
+    /// Block execution resumes at once this construct's matching `end` is reached.
+    continuation: usize,
+}
+
+struct CfgBuilder {
+    op_index: u32,
+    blocks: Vec<BasicBlockBuilder>,
+    current: usize,
+    control_stack: Vec<Frame>,
+    edges: Vec<(usize, usize)>,
+}
+
+struct BasicBlockBuilder {
+    start_op: u32,
+    end_op: u32,
+    is_terminal: bool,
+}
+
+impl CfgBuilder {
+    fn new() -> Self {
+        let entry = BasicBlockBuilder {
+            start_op: 0,
+            end_op: 0,
+            is_terminal: false,
+        };
+        Self {
+            op_index: 0,
+            blocks: vec![entry],
+            current: 0,
+            control_stack: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    fn alloc_block(&mut self) -> usize {
+        let id = self.blocks.len();
+        self.blocks.push(BasicBlockBuilder {
+            start_op: self.op_index,
+            end_op: self.op_index,
+            is_terminal: false,
+        });
+        id
+    }
+
+    /// Close `self.current` at the current op index without an implicit fall-through edge.
+    fn terminate_current(&mut self) {
+        self.blocks[self.current].end_op = self.op_index;
+        self.blocks[self.current].is_terminal = true;
+    }
+
+    /// Close `self.current` at the current op index and record a fall-through edge to `next`.
+    fn close_current_into(&mut self, next: usize) {
+        self.blocks[self.current].end_op = self.op_index;
+        self.edges.push((self.current, next));
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize) {
+        self.edges.push((from, to));
+    }
+
+    /// Start a fresh block at the current op index and make it current.
+    fn start_new_current(&mut self) {
+        let id = self.alloc_block();
+        self.current = id;
+    }
+
+    /// Resolve a `br`/`br_if`/`br_table` relative depth to the frame it targets.
+    ///
+    /// `OperatorsReader` decodes structurally-valid-but-unvalidated operator
+    /// streams — nothing upstream of this builder runs `wasmparser::Validator`
+    /// — so a hand-crafted module can encode a branch depth that exceeds the
+    /// real control-stack nesting. Treat that as a decode error instead of
+    /// indexing (which would underflow the `usize` subtraction).
+    fn label_frame(&self, relative_depth: u32) -> Result<&Frame> {
+        let len = self.control_stack.len();
+        let depth = relative_depth as usize;
+        if depth >= len {
+            anyhow::bail!(
+                "invalid branch: relative_depth {} exceeds control-stack depth {}",
+                relative_depth,
+                len
+            );
+        }
+        Ok(&self.control_stack[len - 1 - depth])
+    }
+
+    fn visit(&mut self, op: &Operator) -> Result<()> {
+        match op {
+            Operator::Block { .. } => {
+                let continuation = self.alloc_block();
+                self.control_stack.push(Frame {
+                    kind: FrameKind::Block,
+                    label_target: continuation,
+                    continuation,
+                });
+            }
+            Operator::Loop { .. } => {
+                let header = self.alloc_block();
+                self.close_current_into(header);
+                self.current = header;
+                let continuation = self.alloc_block();
+                self.control_stack.push(Frame {
+                    kind: FrameKind::Loop,
+                    label_target: header,
+                    continuation,
+                });
+            }
+            Operator::If { .. } => {
+                let then_block = self.alloc_block();
+                let else_block = self.alloc_block();
+                let continuation = self.alloc_block();
+                self.add_edge(self.current, then_block);
+                self.add_edge(self.current, else_block);
+                self.blocks[self.current].end_op = self.op_index;
+                self.current = then_block;
+                self.control_stack.push(Frame {
+                    kind: FrameKind::If {
+                        else_block,
+                        has_else: false,
+                    },
+                    label_target: continuation,
+                    continuation,
+                });
+            }
+            Operator::Else => {
+                if let Some(frame) = self.control_stack.last_mut() {
+                    if let FrameKind::If {
+                        else_block,
+                        ref mut has_else,
+                    } = frame.kind
+                    {
+                        let continuation = frame.continuation;
+                        *has_else = true;
+                        self.close_current_into(continuation);
+                        self.current = else_block;
+                    }
+                }
+            }
+            Operator::End => {
+                match self.control_stack.pop() {
+                    Some(frame) => {
+                        let continuation = frame.continuation;
+                        if let FrameKind::If {
+                            else_block,
+                            has_else,
+                        } = frame.kind
+                        {
+                            if !has_else {
+                                // The implicit empty else path falls straight through.
+                                self.add_edge(else_block, continuation);
+                            }
+                        }
+                        self.close_current_into(continuation);
+                        self.current = continuation;
+                    }
+                    None => {
+                        // Function-level `end`: close the final block with no successor.
+                        self.terminate_current();
+                    }
+                }
+            }
+            Operator::Br { relative_depth } => {
+                let target = self.label_frame(*relative_depth)?.label_target;
+                self.add_edge(self.current, target);
+                self.terminate_current();
+                self.start_new_current();
+            }
+            Operator::BrIf { relative_depth } => {
+                let target = self.label_frame(*relative_depth)?.label_target;
+                let fallthrough = self.alloc_block();
+                self.add_edge(self.current, target);
+                self.close_current_into(fallthrough);
+                self.current = fallthrough;
+            }
+            Operator::BrTable { targets } => {
+                let mut resolved: Vec<usize> = Vec::new();
+                for target in targets.targets() {
+                    resolved.push(self.label_frame(target?)?.label_target);
+                }
+                resolved.push(self.label_frame(targets.default())?.label_target);
+                resolved.sort_unstable();
+                resolved.dedup();
+                for target in resolved {
+                    self.add_edge(self.current, target);
+                }
+                self.terminate_current();
+                self.start_new_current();
+            }
+            Operator::Return | Operator::Unreachable => {
+                self.terminate_current();
+                self.start_new_current();
+            }
+            Operator::Call { .. } | Operator::CallIndirect { .. } => {
+                let next = self.alloc_block();
+                self.close_current_into(next);
+                self.current = next;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> (Vec<BasicBlockBuilder>, Vec<(usize, usize)>) {
+        self.blocks[self.current].end_op = self.op_index;
+        (self.blocks, self.edges)
+    }
+}
+
+/// Build the control-flow graph for one defined function by re-walking its operator stream.
+pub fn build_cfg(wasm_bytes: &[u8], imported_function_count: u32, func_global_idx: u32) -> Result<Cfg> {
+    let parser = Parser::new(0);
+    let mut defined_idx_counter = 0u32;
+
+    for payload in parser.parse_all(wasm_bytes) {
+        if let Payload::CodeSectionEntry(body) = payload? {
+            let current_global_idx = imported_function_count + defined_idx_counter;
+            defined_idx_counter += 1;
+            if current_global_idx != func_global_idx {
+                continue;
+            }
+
+            let mut builder = CfgBuilder::new();
+            let mut reader = body.get_operators_reader()?;
+            while !reader.eof() {
+                let op = reader.read()?;
+                builder.visit(&op)?;
+                builder.op_index += 1;
+            }
+            let (blocks, edges) = builder.finish();
+            let blocks = blocks
+                .into_iter()
+                .enumerate()
+                .map(|(id, b)| BasicBlock {
+                    id,
+                    start_op: b.start_op,
+                    end_op: b.end_op,
+                    is_terminal: b.is_terminal,
+                })
+                .collect();
+            return Ok(Cfg {
+                function_index: func_global_idx,
+                blocks,
+                edges,
+            });
+        }
+    }
+
+    anyhow::bail!("Function with global index {} not found in code section", func_global_idx)
+}
+
+/// Build CFGs for every defined function in the module.
+pub fn build_all_cfgs(module_info: &ModuleInfo, wasm_bytes: &[u8]) -> Result<Vec<Cfg>> {
+    let imported_function_count = module_info
+        .imports
+        .iter()
+        .filter(|i| matches!(i.kind, ImportKind::Function { .. }))
+        .count() as u32;
+
+    module_info
+        .functions
+        .iter()
+        .map(|f| build_cfg(wasm_bytes, imported_function_count, f.index))
+        .collect()
+}
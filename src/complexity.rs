@@ -0,0 +1,102 @@
+// ===== complexity.rs =====
+// src/complexity.rs
+//! Per-function cyclomatic complexity, computed from decision points in each
+//! function body rather than the flat, module-wide count-based heuristic in
+//! `calculate_complexity_score`. Complexity = decision_points + 1, where a
+//! decision point is an `if`, `br_if`, each `br_table` target (including its
+//! default), a `select`/`select_t`, or a `loop` (its implicit back-edge).
+//! This tells apart a large module made of many simple functions from one
+//! with a few genuinely complex hotspots, which a flat module-wide score can't.
+use crate::types::*;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use wasmparser::{FunctionBody, Operator, Parser, Payload};
+
+/// One function's cyclomatic complexity, with its name resolved the same way
+/// the call graph resolves one (debug name first, falling back to none for
+/// anonymous/unexported functions).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionComplexity {
+    pub function_index: u32,
+    pub name: Option<String>,
+    pub cyclomatic_complexity: u32,
+}
+
+/// Module-wide summary of per-function complexity: a histogram (complexity
+/// value -> how many functions have it) plus the most complex functions,
+/// for flagging refactor/inlining candidates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplexityReport {
+    pub histogram: BTreeMap<u32, u32>,
+    pub most_complex: Vec<FunctionComplexity>,
+}
+
+fn count_decision_points(body: &FunctionBody) -> Result<u32> {
+    let mut decision_points = 0u32;
+    let mut reader = body.get_operators_reader()?;
+    while !reader.eof() {
+        match reader.read()? {
+            Operator::If { .. }
+            | Operator::BrIf { .. }
+            | Operator::Select
+            | Operator::TypedSelect { .. }
+            | Operator::Loop { .. } => {
+                decision_points += 1;
+            }
+            Operator::BrTable { targets } => {
+                // Every target is its own branch, including the default one.
+                decision_points += targets.targets().count() as u32 + 1;
+            }
+            _ => {}
+        }
+    }
+    Ok(decision_points)
+}
+
+/// Compute per-function cyclomatic complexity for every defined function and
+/// summarize it into a histogram plus the `top_n` most complex functions.
+pub fn analyze_all(module_info: &ModuleInfo, wasm_bytes: &[u8], top_n: usize) -> Result<ComplexityReport> {
+    let imported_function_count = module_info
+        .imports
+        .iter()
+        .filter(|i| matches!(i.kind, ImportKind::Function { .. }))
+        .count() as u32;
+
+    let names: std::collections::HashMap<u32, Option<String>> = module_info
+        .functions
+        .iter()
+        .map(|f| (f.index, f.demangled_name.clone().or_else(|| f.name.clone())))
+        .collect();
+
+    let mut functions = Vec::new();
+    let mut defined_idx_counter = 0u32;
+    for payload in Parser::new(0).parse_all(wasm_bytes) {
+        if let Payload::CodeSectionEntry(body) = payload? {
+            let function_index = imported_function_count + defined_idx_counter;
+            defined_idx_counter += 1;
+
+            let decision_points = count_decision_points(&body)?;
+            functions.push(FunctionComplexity {
+                function_index,
+                name: names.get(&function_index).cloned().flatten(),
+                cyclomatic_complexity: decision_points + 1,
+            });
+        }
+    }
+
+    let mut histogram = BTreeMap::new();
+    for f in &functions {
+        *histogram.entry(f.cyclomatic_complexity).or_insert(0u32) += 1;
+    }
+
+    let mut most_complex = functions;
+    most_complex.sort_by(|a, b| {
+        b.cyclomatic_complexity
+            .cmp(&a.cyclomatic_complexity)
+            .then(a.function_index.cmp(&b.function_index))
+    });
+    most_complex.truncate(top_n);
+
+    Ok(ComplexityReport { histogram, most_complex })
+}
@@ -0,0 +1,393 @@
+// ===== diff.rs =====
+// src/diff.rs
+use crate::memory::{HotspotType, MemoryHotspot};
+use crate::{Capability, Export, Function, Import, ModuleAnalysis, RiskLevel, Vulnerability};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// A function present in both analyses at the same global index, but under a
+/// different resolved name (debug-name-section churn, or a rename that left
+/// behavior untouched).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionRename {
+    pub index: u32,
+    pub old_name: Option<String>,
+    pub new_name: Option<String>,
+}
+
+/// How the module's single memory's declared limits changed between analyses.
+/// `None` when neither analysis declares a memory, or both declare the same
+/// limits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryLimitChange {
+    pub old_initial_pages: Option<u32>,
+    pub new_initial_pages: Option<u32>,
+    pub old_maximum_pages: Option<u32>,
+    pub new_maximum_pages: Option<u32>,
+}
+
+/// A table present in both analyses (matched by index) whose declared limits
+/// changed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableLimitChange {
+    pub index: u32,
+    pub old_initial: u32,
+    pub new_initial: u32,
+    pub old_maximum: Option<u32>,
+    pub new_maximum: Option<u32>,
+}
+
+/// A `Capability` present in both analyses (matched by name) whose
+/// `risk_level` increased.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapabilityRiskEscalation {
+    pub name: String,
+    pub old_risk_level: RiskLevel,
+    pub new_risk_level: RiskLevel,
+}
+
+/// Structured delta between two analyses of the "same" module at different
+/// points in time (e.g. before/after a build), meant to be consumed by a CI
+/// regression gate rather than read by a human. Capabilities/imports/exports/
+/// vulnerabilities are matched by name/id so a renumbering that changes
+/// nothing observable doesn't register as a spurious add+remove pair.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleDiff {
+    pub new_capabilities: Vec<Capability>,
+    pub removed_capabilities: Vec<Capability>,
+    /// Capabilities present in both analyses whose `risk_level` went up, e.g.
+    /// an import that was `Medium` risk now carries evidence that makes it
+    /// `High`. Not a new/removed capability, so not reflected in
+    /// `new_capabilities`/`removed_capabilities`.
+    pub capability_risk_escalations: Vec<CapabilityRiskEscalation>,
+    pub new_vulnerabilities: Vec<Vulnerability>,
+    /// Vulnerabilities present in `old` with no counterpart (same `id`) in
+    /// `new` — i.e. findings that got fixed.
+    pub resolved_vulnerabilities: Vec<Vulnerability>,
+    pub complexity_score_delta: f64,
+    pub cold_start_ms_delta: f64,
+    pub module_size_delta: i64,
+    pub code_size_delta: i64,
+    pub new_imports: Vec<Import>,
+    pub removed_imports: Vec<Import>,
+    pub new_exports: Vec<Export>,
+    pub removed_exports: Vec<Export>,
+    /// Functions present in `new` with no counterpart (same global index) in
+    /// `old`.
+    pub new_functions: Vec<Function>,
+    /// Functions present in `old` with no counterpart (same global index) in
+    /// `new`.
+    pub removed_functions: Vec<Function>,
+    pub renamed_functions: Vec<FunctionRename>,
+    pub memory_limit_change: Option<MemoryLimitChange>,
+    pub table_limit_changes: Vec<TableLimitChange>,
+    /// Hotspots present in `new` with no counterpart (same function index and
+    /// hotspot type) in `old`.
+    pub new_memory_hotspots: Vec<MemoryHotspot>,
+}
+
+impl ModuleDiff {
+    /// True when nothing tracked by this diff changed between the two modules.
+    pub fn is_empty(&self) -> bool {
+        self.new_capabilities.is_empty()
+            && self.removed_capabilities.is_empty()
+            && self.capability_risk_escalations.is_empty()
+            && self.new_vulnerabilities.is_empty()
+            && self.resolved_vulnerabilities.is_empty()
+            && self.complexity_score_delta == 0.0
+            && self.cold_start_ms_delta == 0.0
+            && self.module_size_delta == 0
+            && self.code_size_delta == 0
+            && self.new_imports.is_empty()
+            && self.removed_imports.is_empty()
+            && self.new_exports.is_empty()
+            && self.removed_exports.is_empty()
+            && self.new_functions.is_empty()
+            && self.removed_functions.is_empty()
+            && self.renamed_functions.is_empty()
+            && self.memory_limit_change.is_none()
+            && self.table_limit_changes.is_empty()
+            && self.new_memory_hotspots.is_empty()
+    }
+
+    /// True when this diff contains changes a CI gate would typically want to
+    /// fail a build over: the module got strictly more dangerous, via newly
+    /// introduced capabilities, a capability's risk escalating, or a new
+    /// vulnerability finding. Dropped capabilities, resolved vulnerabilities,
+    /// and timing/size drift are informational, not regressions.
+    pub fn has_regressions(&self) -> bool {
+        !self.new_capabilities.is_empty()
+            || !self.capability_risk_escalations.is_empty()
+            || !self.new_vulnerabilities.is_empty()
+    }
+}
+
+/// Compare two [`ModuleAnalysis`] results — typically the "before" and "after"
+/// of a build — and surface what changed.
+pub fn diff_modules(old: &ModuleAnalysis, new: &ModuleAnalysis) -> ModuleDiff {
+    let old_capability_names: HashSet<&str> = old
+        .security_analysis
+        .capabilities
+        .iter()
+        .map(|c| c.name.as_str())
+        .collect();
+    let new_capability_names: HashSet<&str> = new
+        .security_analysis
+        .capabilities
+        .iter()
+        .map(|c| c.name.as_str())
+        .collect();
+
+    let new_capabilities = new
+        .security_analysis
+        .capabilities
+        .iter()
+        .filter(|c| !old_capability_names.contains(c.name.as_str()))
+        .cloned()
+        .collect();
+    let removed_capabilities = old
+        .security_analysis
+        .capabilities
+        .iter()
+        .filter(|c| !new_capability_names.contains(c.name.as_str()))
+        .cloned()
+        .collect();
+
+    let old_capabilities_by_name: std::collections::HashMap<&str, &Capability> = old
+        .security_analysis
+        .capabilities
+        .iter()
+        .map(|c| (c.name.as_str(), c))
+        .collect();
+    let capability_risk_escalations = new
+        .security_analysis
+        .capabilities
+        .iter()
+        .filter_map(|new_cap| {
+            let old_cap = old_capabilities_by_name.get(new_cap.name.as_str())?;
+            if new_cap.risk_level > old_cap.risk_level {
+                Some(CapabilityRiskEscalation {
+                    name: new_cap.name.clone(),
+                    old_risk_level: old_cap.risk_level.clone(),
+                    new_risk_level: new_cap.risk_level.clone(),
+                })
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let old_vulnerability_ids: HashSet<&str> = old
+        .security_analysis
+        .vulnerabilities
+        .iter()
+        .map(|v| v.id.as_str())
+        .collect();
+    let new_vulnerability_ids: HashSet<&str> = new
+        .security_analysis
+        .vulnerabilities
+        .iter()
+        .map(|v| v.id.as_str())
+        .collect();
+    let new_vulnerabilities = new
+        .security_analysis
+        .vulnerabilities
+        .iter()
+        .filter(|v| !old_vulnerability_ids.contains(v.id.as_str()))
+        .cloned()
+        .collect();
+    let resolved_vulnerabilities = old
+        .security_analysis
+        .vulnerabilities
+        .iter()
+        .filter(|v| !new_vulnerability_ids.contains(v.id.as_str()))
+        .cloned()
+        .collect();
+
+    let old_import_keys: HashSet<(&str, &str)> = old
+        .module_info
+        .imports
+        .iter()
+        .map(|i| (i.module.as_str(), i.name.as_str()))
+        .collect();
+    let new_import_keys: HashSet<(&str, &str)> = new
+        .module_info
+        .imports
+        .iter()
+        .map(|i| (i.module.as_str(), i.name.as_str()))
+        .collect();
+
+    let new_imports = new
+        .module_info
+        .imports
+        .iter()
+        .filter(|i| !old_import_keys.contains(&(i.module.as_str(), i.name.as_str())))
+        .cloned()
+        .collect();
+    let removed_imports = old
+        .module_info
+        .imports
+        .iter()
+        .filter(|i| !new_import_keys.contains(&(i.module.as_str(), i.name.as_str())))
+        .cloned()
+        .collect();
+
+    let old_export_names: HashSet<&str> = old
+        .module_info
+        .exports
+        .iter()
+        .map(|e| e.name.as_str())
+        .collect();
+    let new_export_names: HashSet<&str> = new
+        .module_info
+        .exports
+        .iter()
+        .map(|e| e.name.as_str())
+        .collect();
+
+    let new_exports = new
+        .module_info
+        .exports
+        .iter()
+        .filter(|e| !old_export_names.contains(e.name.as_str()))
+        .cloned()
+        .collect();
+    let removed_exports = old
+        .module_info
+        .exports
+        .iter()
+        .filter(|e| !new_export_names.contains(e.name.as_str()))
+        .cloned()
+        .collect();
+
+    let old_functions_by_index: std::collections::HashMap<u32, &Function> = old
+        .module_info
+        .functions
+        .iter()
+        .map(|f| (f.index, f))
+        .collect();
+    let new_functions_by_index: std::collections::HashMap<u32, &Function> = new
+        .module_info
+        .functions
+        .iter()
+        .map(|f| (f.index, f))
+        .collect();
+
+    let new_functions = new
+        .module_info
+        .functions
+        .iter()
+        .filter(|f| !old_functions_by_index.contains_key(&f.index))
+        .cloned()
+        .collect();
+    let removed_functions = old
+        .module_info
+        .functions
+        .iter()
+        .filter(|f| !new_functions_by_index.contains_key(&f.index))
+        .cloned()
+        .collect();
+    let renamed_functions = new
+        .module_info
+        .functions
+        .iter()
+        .filter_map(|new_func| {
+            let old_func = old_functions_by_index.get(&new_func.index)?;
+            if old_func.name != new_func.name {
+                Some(FunctionRename {
+                    index: new_func.index,
+                    old_name: old_func.name.clone(),
+                    new_name: new_func.name.clone(),
+                })
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let memory_limit_change = {
+        let old_memory = old.module_info.memory.as_ref();
+        let new_memory = new.module_info.memory.as_ref();
+        let old_initial_pages = old_memory.map(|m| m.initial);
+        let new_initial_pages = new_memory.map(|m| m.initial);
+        let old_maximum_pages = old_memory.and_then(|m| m.maximum);
+        let new_maximum_pages = new_memory.and_then(|m| m.maximum);
+        if old_initial_pages == new_initial_pages && old_maximum_pages == new_maximum_pages {
+            None
+        } else {
+            Some(MemoryLimitChange {
+                old_initial_pages,
+                new_initial_pages,
+                old_maximum_pages,
+                new_maximum_pages,
+            })
+        }
+    };
+
+    let old_tables_by_index: std::collections::HashMap<u32, &crate::types::Table> = old
+        .module_info
+        .tables
+        .iter()
+        .map(|t| (t.index, t))
+        .collect();
+    let table_limit_changes = new
+        .module_info
+        .tables
+        .iter()
+        .filter_map(|new_table| {
+            let old_table = old_tables_by_index.get(&new_table.index)?;
+            if old_table.table_type.initial == new_table.table_type.initial
+                && old_table.table_type.maximum == new_table.table_type.maximum
+            {
+                return None;
+            }
+            Some(TableLimitChange {
+                index: new_table.index,
+                old_initial: old_table.table_type.initial,
+                new_initial: new_table.table_type.initial,
+                old_maximum: old_table.table_type.maximum,
+                new_maximum: new_table.table_type.maximum,
+            })
+        })
+        .collect();
+
+    let old_hotspots: HashSet<(u32, HotspotType)> = old
+        .memory_analysis
+        .memory_hotspots
+        .iter()
+        .map(|h| (h.function_index, h.hotspot_type.clone()))
+        .collect();
+    let new_memory_hotspots = new
+        .memory_analysis
+        .memory_hotspots
+        .iter()
+        .filter(|h| !old_hotspots.contains(&(h.function_index, h.hotspot_type.clone())))
+        .cloned()
+        .collect();
+
+    ModuleDiff {
+        new_capabilities,
+        removed_capabilities,
+        capability_risk_escalations,
+        new_vulnerabilities,
+        resolved_vulnerabilities,
+        complexity_score_delta: new.performance_metrics.complexity_score
+            - old.performance_metrics.complexity_score,
+        cold_start_ms_delta: new.performance_metrics.estimated_cold_start_ms
+            - old.performance_metrics.estimated_cold_start_ms,
+        module_size_delta: i64::from(new.performance_metrics.module_size)
+            - i64::from(old.performance_metrics.module_size),
+        code_size_delta: i64::from(new.performance_metrics.code_size)
+            - i64::from(old.performance_metrics.code_size),
+        new_imports,
+        removed_imports,
+        new_exports,
+        removed_exports,
+        new_functions,
+        removed_functions,
+        renamed_functions,
+        memory_limit_change,
+        table_limit_changes,
+        new_memory_hotspots,
+    }
+}
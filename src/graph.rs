@@ -29,8 +29,10 @@ impl<'a> CallGraphBuilder<'a> {
         // 2. Build Nodes (this needs to be robust to include all mentioned functions)
         let nodes = self.build_call_nodes_robust();
 
-        // 3. Build Edges from collected function_call_instructions
-        let edges = self.build_call_edges_from_parsed_instructions();
+        // 3. Build Edges from collected function_call_instructions, plus conservative
+        // edges for call_indirect sites resolved by type-signature matching.
+        let mut edges = self.build_call_edges_from_parsed_instructions();
+        edges.extend(self.build_indirect_call_edges());
 
         // 4. Find Entry Points
         let entry_points = self.find_entry_points();
@@ -38,6 +40,16 @@ impl<'a> CallGraphBuilder<'a> {
         // 5. Find Unreachable Functions using the new accurate graph data
         let unreachable_functions = self.find_unreachable_functions(&nodes, &edges, &entry_points);
 
+        // 6. Find self- and mutually-recursive cycles via Tarjan's SCC algorithm
+        let recursive_components = self.find_recursive_components(&nodes, &edges);
+
+        // 7. Score defined functions as inlining candidates using a simple cost model
+        let inline_candidates =
+            self.compute_inline_candidates(&nodes, &edges, &recursive_components);
+
+        // 8. Classify functions by usage: dead code, unused imports, leaf-only exports
+        let usage_report = self.compute_usage_report(&nodes, &unreachable_functions);
+
         // Debugging output
         println!(
             "[DEBUG CallGraphBuilder] Final Nodes Count: {}",
@@ -58,9 +70,134 @@ impl<'a> CallGraphBuilder<'a> {
             edges,
             entry_points,
             unreachable_functions,
+            recursive_components,
+            inline_candidates,
+            usage_report,
         })
     }
 
+    /// Splits the unreachable/import/export picture already computed for this
+    /// graph into actionable usage categories. See [`UsageReport`].
+    fn compute_usage_report(&self, nodes: &[CallNode], unreachable_functions: &[u32]) -> UsageReport {
+        let unused_imports = nodes
+            .iter()
+            .filter(|node| node.is_imported && node.call_count == 0)
+            .map(|node| node.function_index)
+            .collect();
+
+        let leaf_only_exports = nodes
+            .iter()
+            .filter(|node| node.is_exported && !node.is_imported && node.call_count == 0)
+            .map(|node| node.function_index)
+            .collect();
+
+        UsageReport {
+            dead_functions: unreachable_functions.to_vec(),
+            unused_imports,
+            leaf_only_exports,
+        }
+    }
+
+    /// Per-call-instruction cost added on top of a function's raw instruction
+    /// count, modeling the overhead a call site would otherwise avoid if inlined.
+    const CALL_PENALTY: u32 = 2;
+    /// Extra cost added per indirect call, since the callee's size can't be
+    /// accounted for statically and the call retains its indirection overhead.
+    const INDIRECT_CALL_PENALTY: u32 = 4;
+
+    /// Ranks defined functions as inlining candidates using a MIR-style cost model:
+    /// estimated cost comes from instruction count plus call-site penalties, and the
+    /// benefit score favors small, singly-called, non-recursive functions.
+    fn compute_inline_candidates(
+        &self,
+        nodes: &[CallNode],
+        edges: &[CallEdge],
+        recursive_components: &[Vec<u32>],
+    ) -> Vec<InlineCandidate> {
+        let mut recursive: HashSet<u32> = HashSet::new();
+        for component in recursive_components {
+            if component.len() > 1 {
+                recursive.extend(component.iter().copied());
+            }
+        }
+
+        // Number of distinct call sites (edges) targeting each callee, i.e. how many
+        // different places would need a copy of the body if it were inlined there —
+        // as opposed to `CallNode::call_count`, which is the total dynamic call count.
+        let mut call_sites_by_callee: HashMap<u32, u32> = HashMap::new();
+        for edge in edges {
+            *call_sites_by_callee.entry(edge.to).or_insert(0) += 1;
+        }
+
+        let mut indirect_calls_by_caller: HashMap<u32, u32> = HashMap::new();
+        for &(caller_idx, _type_index, _const_slot) in &self.module_info.indirect_call_instructions {
+            *indirect_calls_by_caller.entry(caller_idx).or_insert(0) += 1;
+        }
+
+        let mut candidates = Vec::new();
+        for node in nodes {
+            if node.is_imported {
+                continue;
+            }
+            let Some(func) = self
+                .module_info
+                .functions
+                .iter()
+                .find(|f| f.index == node.function_index)
+            else {
+                continue;
+            };
+
+            let direct_calls = self
+                .module_info
+                .function_call_instructions
+                .iter()
+                .filter(|&&(caller_idx, _)| caller_idx == node.function_index)
+                .count() as u32;
+            let indirect_calls = indirect_calls_by_caller
+                .get(&node.function_index)
+                .copied()
+                .unwrap_or(0);
+
+            let estimated_cost = func.instruction_count
+                + direct_calls * Self::CALL_PENALTY
+                + indirect_calls * Self::INDIRECT_CALL_PENALTY;
+
+            let call_sites = call_sites_by_callee
+                .get(&node.function_index)
+                .copied()
+                .unwrap_or(0);
+
+            // Inlining at every call site grows code by roughly `cost * call_sites`,
+            // and each duplicate copy still has to pay for however many times it's
+            // actually invoked (`call_count`), so both penalize the score; a
+            // single-call-site function gets the best possible score for its size,
+            // since inlining it there costs nothing (the original can be dropped)
+            // rather than duplicated.
+            let score = if recursive.contains(&node.function_index) || call_sites == 0 {
+                0.0
+            } else {
+                let hotness = node.call_count.max(call_sites) as f64;
+                1.0 / (estimated_cost.max(1) as f64 * call_sites as f64 * hotness)
+            };
+
+            candidates.push(InlineCandidate {
+                function_index: node.function_index,
+                estimated_cost,
+                call_sites,
+                score,
+            });
+        }
+
+        candidates.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.function_index.cmp(&b.function_index))
+        });
+        candidates
+    }
+
     // More robust node building
     fn build_call_nodes_robust(&self) -> Vec<CallNode> {
         let mut node_map: HashMap<u32, CallNode> = HashMap::new();
@@ -74,6 +211,7 @@ impl<'a> CallGraphBuilder<'a> {
                 CallNode {
                     function_index: func.index,
                     name: func.name.clone(),
+                    demangled_name: func.demangled_name.clone(),
                     is_imported: func.is_imported, // Should be false
                     is_exported: func.is_exported,
                     call_count: self.call_counts.get(&func.index).copied().unwrap_or(0),
@@ -92,6 +230,7 @@ impl<'a> CallGraphBuilder<'a> {
                     .or_insert_with(|| CallNode {
                         function_index: current_imported_func_global_idx,
                         name: Some(format!("{}::{} (import)", import.module, import.name)),
+                        demangled_name: crate::parser::demangle_symbol(&import.name),
                         is_imported: true,
                         is_exported: false, // Imports are not directly exported from the module itself in this context
                         call_count: self
@@ -112,6 +251,7 @@ impl<'a> CallGraphBuilder<'a> {
             node_map.entry(caller_idx).or_insert_with(|| CallNode {
                 function_index: caller_idx,
                 name: Some(format!("func_{} (implicit_caller)", caller_idx)),
+                demangled_name: None, // Synthetic placeholder, never a real symbol
                 is_imported: caller_idx < current_imported_func_global_idx, // Heuristic: lower indices are often imports
                 is_exported: false, // Cannot know without iterating exports
                 call_count: self.call_counts.get(&caller_idx).copied().unwrap_or(0),
@@ -119,6 +259,7 @@ impl<'a> CallGraphBuilder<'a> {
             node_map.entry(callee_idx).or_insert_with(|| CallNode {
                 function_index: callee_idx,
                 name: Some(format!("func_{} (implicit_callee)", callee_idx)),
+                demangled_name: None, // Synthetic placeholder, never a real symbol
                 is_imported: callee_idx < current_imported_func_global_idx, // Heuristic
                 is_exported: false,
                 call_count: self.call_counts.get(&callee_idx).copied().unwrap_or(0),
@@ -131,6 +272,7 @@ impl<'a> CallGraphBuilder<'a> {
                 let node_entry = node_map.entry(export.index).or_insert_with(|| CallNode {
                     function_index: export.index,
                     name: Some(export.name.clone()), // Use export name if no other name yet
+                    demangled_name: crate::parser::demangle_symbol(&export.name),
                     is_imported: export.index < current_imported_func_global_idx, // Heuristic
                     is_exported: true,
                     call_count: self.call_counts.get(&export.index).copied().unwrap_or(0),
@@ -139,12 +281,16 @@ impl<'a> CallGraphBuilder<'a> {
                 if node_entry.name.is_none() {
                     node_entry.name = Some(export.name.clone());
                 }
+                if node_entry.demangled_name.is_none() {
+                    node_entry.demangled_name = crate::parser::demangle_symbol(&export.name);
+                }
             }
         }
         if let Some(start_func_idx) = self.module_info.start_function {
             node_map.entry(start_func_idx).or_insert_with(|| CallNode {
                 function_index: start_func_idx,
                 name: Some(format!("_start (func_{})", start_func_idx)),
+                demangled_name: None, // Synthetic placeholder, never a real symbol
                 is_imported: start_func_idx < current_imported_func_global_idx, // Heuristic
                 is_exported: false, // Start function usually not an export by name
                 call_count: self.call_counts.get(&start_func_idx).copied().unwrap_or(0),
@@ -176,10 +322,101 @@ impl<'a> CallGraphBuilder<'a> {
                 from,
                 to,
                 call_sites: count, // count is the number of call instructions from 'from' to 'to'
+                is_indirect: false,
             })
             .collect()
     }
 
+    /// Global index -> type index for every function (imported and defined), so
+    /// indirect call sites can be resolved by matching signatures.
+    fn type_index_by_function(&self) -> HashMap<u32, u32> {
+        let mut map = HashMap::new();
+        let mut imported_func_idx = 0u32;
+        for import in &self.module_info.imports {
+            if let ImportKind::Function { type_index, .. } = import.kind {
+                map.insert(imported_func_idx, type_index);
+                imported_func_idx += 1;
+            }
+        }
+        for func in &self.module_info.functions {
+            map.insert(func.index, func.type_index);
+        }
+        map
+    }
+
+    /// Table slot -> function index, from active element segments whose items are a
+    /// concrete function-index list (the common `wasm-bindgen`-style vtable/closure
+    /// table codegen). Segments without a statically known offset, or using
+    /// `ref.func`/`ref.null` expression items, contribute nothing here; their slots
+    /// are only covered by the type-signature fan-out fallback below. Doesn't
+    /// distinguish between multiple tables, matching this codegen's single-table
+    /// assumption elsewhere.
+    fn table_slot_to_function(&self) -> HashMap<u32, u32> {
+        let mut map = HashMap::new();
+        for segment in &self.module_info.element_segments {
+            if segment.is_passive {
+                continue;
+            }
+            if let Some(offset) = segment.offset.as_ref().and_then(|v| v.as_u32()) {
+                for (slot_in_segment, &func_idx) in segment.function_indices.iter().enumerate() {
+                    map.insert(offset + slot_in_segment as u32, func_idx);
+                }
+            }
+        }
+        map
+    }
+
+    /// `call_indirect` sites preceded by `i32.const N` resolve exactly to whatever
+    /// function sits in table slot `N` (via `table_slot_to_function`). Sites whose
+    /// index isn't statically known, or whose slot isn't covered by a resolved
+    /// element segment, conservatively fan out to every function sharing the call
+    /// site's type signature instead. This over-approximates reachability (a live
+    /// `call_indirect` never falsely marks a same-signature function as dead) at
+    /// the cost of some precision; callers can tell these apart via
+    /// `CallEdge::is_indirect`.
+    fn build_indirect_call_edges(&self) -> Vec<CallEdge> {
+        let type_index_by_function = self.type_index_by_function();
+        let mut candidates_by_type: HashMap<u32, Vec<u32>> = HashMap::new();
+        for (&func_idx, &type_index) in &type_index_by_function {
+            candidates_by_type.entry(type_index).or_default().push(func_idx);
+        }
+        let table_slot_to_function = self.table_slot_to_function();
+
+        let mut edge_map: HashMap<(u32, u32), u32> = HashMap::new();
+        for &(caller_idx, type_index, const_table_slot) in &self.module_info.indirect_call_instructions {
+            let resolved_callee = const_table_slot.and_then(|slot| table_slot_to_function.get(&slot).copied());
+
+            if let Some(callee_idx) = resolved_callee {
+                *edge_map.entry((caller_idx, callee_idx)).or_insert(0) += 1;
+            } else if let Some(candidates) = candidates_by_type.get(&type_index) {
+                for &callee_idx in candidates {
+                    *edge_map.entry((caller_idx, callee_idx)).or_insert(0) += 1;
+                }
+            }
+        }
+
+        edge_map
+            .into_iter()
+            .map(|((from, to), count)| CallEdge {
+                from,
+                to,
+                call_sites: count,
+                is_indirect: true,
+            })
+            .collect()
+    }
+
+    /// Entry points the call graph treats as externally reachable: `start`,
+    /// every function export, every function placed in a table via an
+    /// element segment (reachable through a host/another module's
+    /// `call_indirect`, and through direct host table access wasm-bindgen-style
+    /// code relies on), and every function a global's `ref.func` init
+    /// expression hands out. The table/global seeding mirrors
+    /// `ModuleInfo::compute_live_set` in `liveness.rs` — without it, a
+    /// function only ever invoked through a table slot or a `ref.func`
+    /// global, never called by another function in the module, would be
+    /// misclassified as dead code and `transform::strip_unreachable_functions`
+    /// would delete it outright.
     fn find_entry_points(&self) -> Vec<u32> {
         let mut entry_points = HashSet::new(); // Use HashSet to avoid duplicates initially
 
@@ -194,6 +431,18 @@ impl<'a> CallGraphBuilder<'a> {
             }
         }
 
+        for segment in &self.module_info.element_segments {
+            for &func_idx in &segment.function_indices {
+                entry_points.insert(func_idx);
+            }
+        }
+
+        for global in &self.module_info.globals {
+            if let ConstValue::FuncRef(func_idx) = global.init_value {
+                entry_points.insert(func_idx);
+            }
+        }
+
         let mut sorted_entry_points: Vec<u32> = entry_points.into_iter().collect();
         sorted_entry_points.sort_unstable();
 
@@ -263,4 +512,327 @@ impl<'a> CallGraphBuilder<'a> {
             })
             .collect()
     }
+
+    /// Strongly connected components of the call graph (via Tarjan's algorithm),
+    /// filtered to actual cycles: components with more than one function, or a
+    /// single function with a direct edge to itself.
+    fn find_recursive_components(&self, nodes: &[CallNode], edges: &[CallEdge]) -> Vec<Vec<u32>> {
+        let mut adj: HashMap<u32, Vec<u32>> = HashMap::new();
+        let mut self_loops: HashSet<u32> = HashSet::new();
+        for edge in edges {
+            adj.entry(edge.from).or_default().push(edge.to);
+            if edge.from == edge.to {
+                self_loops.insert(edge.from);
+            }
+        }
+
+        let mut state = TarjanState::default();
+        for node in nodes {
+            if !state.index.contains_key(&node.function_index) {
+                Self::tarjan_strongconnect(node.function_index, &adj, &mut state);
+            }
+        }
+
+        state
+            .components
+            .into_iter()
+            .filter(|component| component.len() > 1 || self_loops.contains(&component[0]))
+            .collect()
+    }
+
+    /// Tarjan's SCC DFS from `start`, assigning each visited node an
+    /// `index`/`lowlink`, pushing it on the stack, visiting its unvisited
+    /// callees (tightening `lowlink` for tree edges) and tightening `lowlink`
+    /// against the `index` of callees that are still on the stack (back
+    /// edges). When `lowlink == index` for a node, it's an SCC root, so pop
+    /// the stack down to and including it to form one component.
+    ///
+    /// Iterative rather than recursive: a module can encode an arbitrarily
+    /// long linear call chain in well under a megabyte, and a call-depth-deep
+    /// native recursion would blow the stack (an unrecoverable abort, not a
+    /// catchable panic) on exactly the kind of attacker-supplied input this
+    /// analysis is meant to run safely on. An explicit work-stack of
+    /// `(node, next unvisited successor index)` frames replaces the call
+    /// stack so depth is bounded only by heap, not by thread stack size.
+    fn tarjan_strongconnect(start: u32, adj: &HashMap<u32, Vec<u32>>, state: &mut TarjanState) {
+        struct Frame {
+            v: u32,
+            succ_idx: usize,
+        }
+
+        fn visit_new(v: u32, state: &mut TarjanState, work: &mut Vec<Frame>) {
+            state.index.insert(v, state.next_index);
+            state.lowlink.insert(v, state.next_index);
+            state.next_index += 1;
+            state.stack.push(v);
+            state.on_stack.insert(v);
+            work.push(Frame { v, succ_idx: 0 });
+        }
+
+        let mut work: Vec<Frame> = Vec::new();
+        visit_new(start, state, &mut work);
+
+        while let Some(frame) = work.last_mut() {
+            let v = frame.v;
+            let successors = adj.get(&v).map(Vec::as_slice).unwrap_or(&[]);
+
+            if let Some(&w) = successors.get(frame.succ_idx) {
+                frame.succ_idx += 1;
+                if !state.index.contains_key(&w) {
+                    visit_new(w, state, &mut work);
+                } else if state.on_stack.contains(&w) {
+                    let candidate = state.index[&w];
+                    let current = state.lowlink[&v];
+                    state.lowlink.insert(v, current.min(candidate));
+                }
+                continue;
+            }
+
+            // All of `v`'s successors are processed: fold its lowlink into its
+            // parent frame (the tree-edge tightening the recursive version did
+            // right after its recursive call returned), then close the SCC if
+            // `v` turned out to be a root.
+            work.pop();
+            if let Some(parent) = work.last() {
+                let candidate = state.lowlink[&v];
+                let current = state.lowlink[&parent.v];
+                state.lowlink.insert(parent.v, current.min(candidate));
+            }
+
+            if state.lowlink[&v] == state.index[&v] {
+                let mut component = Vec::new();
+                loop {
+                    let w = state
+                        .stack
+                        .pop()
+                        .expect("SCC root must still have itself on the stack");
+                    state.on_stack.remove(&w);
+                    component.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                component.sort_unstable();
+                state.components.push(component);
+            }
+        }
+    }
+}
+
+/// Running state threaded through Tarjan's DFS: per-node `index`/`lowlink`,
+/// the DFS stack (and a set mirroring its membership for O(1) lookup), and
+/// the components found so far.
+#[derive(Default)]
+struct TarjanState {
+    next_index: u32,
+    index: HashMap<u32, u32>,
+    lowlink: HashMap<u32, u32>,
+    on_stack: HashSet<u32>,
+    stack: Vec<u32>,
+    components: Vec<Vec<u32>>,
+}
+
+/// Hooks for observing a [`CallGraph::reachable_from`] traversal as it happens,
+/// so callers can accumulate their own state (a report, a filtered subgraph, a
+/// running cost total, ...) without re-walking the graph themselves. All
+/// methods are no-ops by default — implement only the ones you need.
+pub trait CallGraphVisitor {
+    /// Called once for each seed function the traversal starts from.
+    fn visit_entry_point(&mut self, _function_index: u32) {}
+    /// Called once for each edge the traversal follows, in the direction it
+    /// was walked (i.e. `from`/`to` are already oriented for `direction`).
+    fn visit_edge(&mut self, _from: u32, _to: u32, _direction: Direction) {}
+    /// Called once for each function the traversal visits (seeds included).
+    fn visit_function(&mut self, _function_index: u32) {}
+}
+
+/// A [`CallGraphVisitor`] that observes nothing, used when the caller only
+/// wants the visited set back from [`CallGraph::reachable_from`].
+struct NoopVisitor;
+impl CallGraphVisitor for NoopVisitor {}
+
+impl CallGraph {
+    /// Returns every function reachable from `seeds` by following edges in
+    /// `direction` (`Forward` for "what do these functions call", `Reverse`
+    /// for "what calls these functions"), including the seeds themselves.
+    pub fn reachable_from(&self, seeds: &[u32], direction: Direction) -> HashSet<u32> {
+        self.reachable_from_with_visitor(seeds, direction, &mut NoopVisitor)
+    }
+
+    /// Same traversal as [`CallGraph::reachable_from`], but drives `visitor`
+    /// with each seed, edge, and visited function as the walk discovers them.
+    pub fn reachable_from_with_visitor(
+        &self,
+        seeds: &[u32],
+        direction: Direction,
+        visitor: &mut impl CallGraphVisitor,
+    ) -> HashSet<u32> {
+        let adjacency = self.adjacency(direction);
+
+        let mut visited: HashSet<u32> = HashSet::new();
+        let mut to_visit: Vec<u32> = Vec::new();
+
+        for &seed in seeds {
+            visitor.visit_entry_point(seed);
+            if visited.insert(seed) {
+                visitor.visit_function(seed);
+                to_visit.push(seed);
+            }
+        }
+
+        while let Some(current) = to_visit.pop() {
+            let Some(neighbors) = adjacency.get(&current) else {
+                continue;
+            };
+            for &next in neighbors {
+                visitor.visit_edge(current, next, direction);
+                if visited.insert(next) {
+                    visitor.visit_function(next);
+                    to_visit.push(next);
+                }
+            }
+        }
+
+        visited
+    }
+
+    /// Functions directly called by `function_index` (one hop forward).
+    pub fn callees_of(&self, function_index: u32) -> Vec<u32> {
+        self.edges
+            .iter()
+            .filter(|edge| edge.from == function_index)
+            .map(|edge| edge.to)
+            .collect()
+    }
+
+    /// Functions that directly call `function_index` (one hop backward).
+    pub fn callers_of(&self, function_index: u32) -> Vec<u32> {
+        self.edges
+            .iter()
+            .filter(|edge| edge.to == function_index)
+            .map(|edge| edge.from)
+            .collect()
+    }
+
+    /// Builds the forward or reverse adjacency map over `self.edges` once, so
+    /// a traversal doesn't re-scan the full edge list at every visited node.
+    fn adjacency(&self, direction: Direction) -> HashMap<u32, Vec<u32>> {
+        let mut adjacency: HashMap<u32, Vec<u32>> = HashMap::new();
+        for edge in &self.edges {
+            let (from, to) = match direction {
+                Direction::Forward => (edge.from, edge.to),
+                Direction::Reverse => (edge.to, edge.from),
+            };
+            adjacency.entry(from).or_default().push(to);
+        }
+        adjacency
+    }
+
+    /// Renders this call graph as a Graphviz DOT digraph.
+    ///
+    /// Nodes are styled by role (unreachable, entry point, exported, imported),
+    /// entry points get a double border, and edges derived from `call_indirect`
+    /// (i.e. `is_indirect`) are drawn dashed since their target may be one of
+    /// several type-compatible candidates rather than a single resolved callee.
+    /// Strongly connected components of size 2 or more are grouped into dashed
+    /// red `cluster_scc_N` subgraphs to call out recursive cycles at a glance.
+    pub fn to_dot(&self) -> String {
+        let unreachable: HashSet<u32> = self.unreachable_functions.iter().copied().collect();
+        let entry_points: HashSet<u32> = self.entry_points.iter().copied().collect();
+
+        let mut clustered: HashSet<u32> = HashSet::new();
+        for component in &self.recursive_components {
+            if component.len() > 1 {
+                clustered.extend(component.iter().copied());
+            }
+        }
+
+        let mut dot = String::new();
+        dot.push_str("digraph call_graph {\n");
+        dot.push_str("    rankdir=LR;\n");
+        dot.push_str("    node [shape=box, style=filled];\n\n");
+
+        for (i, component) in self.recursive_components.iter().enumerate() {
+            if component.len() < 2 {
+                continue;
+            }
+            dot.push_str(&format!("    subgraph cluster_scc_{} {{\n", i));
+            dot.push_str("        style=dashed;\n");
+            dot.push_str("        color=red;\n");
+            dot.push_str(&format!(
+                "        label=\"{}\";\n",
+                escape_dot_label(&format!("recursive cycle #{}", i))
+            ));
+            for node in &self.nodes {
+                if component.contains(&node.function_index) {
+                    dot.push_str(&format!(
+                        "        {};\n",
+                        node_dot_line(node, &unreachable, &entry_points)
+                    ));
+                }
+            }
+            dot.push_str("    }\n\n");
+        }
+
+        for node in &self.nodes {
+            if clustered.contains(&node.function_index) {
+                continue;
+            }
+            dot.push_str(&format!(
+                "    {};\n",
+                node_dot_line(node, &unreachable, &entry_points)
+            ));
+        }
+
+        dot.push('\n');
+        for edge in &self.edges {
+            let style = if edge.is_indirect { "dashed" } else { "solid" };
+            dot.push_str(&format!(
+                "    f{} -> f{} [label=\"{}\", style={}];\n",
+                edge.from, edge.to, edge.call_sites, style
+            ));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Builds the `fN [label=..., fillcolor=..., peripheries=...]` line for a single node.
+fn node_dot_line(node: &CallNode, unreachable: &HashSet<u32>, entry_points: &HashSet<u32>) -> String {
+    let label = match node.demangled_name.as_ref().or(node.name.as_ref()) {
+        Some(name) => format!("{} (#{})", name, node.function_index),
+        None => format!("#{}", node.function_index),
+    };
+
+    let fillcolor = if unreachable.contains(&node.function_index) {
+        "lightgray"
+    } else if entry_points.contains(&node.function_index) {
+        "gold"
+    } else if node.is_exported {
+        "lightgreen"
+    } else if node.is_imported {
+        "lightblue"
+    } else {
+        "white"
+    };
+
+    let peripheries = if entry_points.contains(&node.function_index) {
+        2
+    } else {
+        1
+    };
+
+    format!(
+        "f{} [label=\"{}\", fillcolor={}, peripheries={}]",
+        node.function_index,
+        escape_dot_label(&label),
+        fillcolor,
+        peripheries
+    )
+}
+
+/// Escapes `\` and `"` so arbitrary function names can't break a DOT label.
+fn escape_dot_label(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
 }
@@ -7,6 +7,18 @@ pub mod parser;
 pub mod graph;
 pub mod security;
 pub mod memory; // Added memory module
+pub mod cfg; // Per-function control-flow graph reconstruction
+pub mod transform; // Section-stripping / dead-code-removal re-encoding passes
+pub mod policy; // Import allowlist/denylist enforcement
+pub mod passes; // Single-pass operator-visitor framework shared across analyses
+pub mod diff; // Structured before/after comparison of two analyses, for CI regression gates
+pub mod sarif; // SARIF 2.1.0 rendering of vulnerabilities/overflow findings for code-scanning dashboards
+pub mod profile; // Opt-in per-pass wall-clock profiling for ModuleAnalyzer::analyze
+pub mod liveness; // Reachability-based tree-shaking analysis over ModuleInfo
+pub mod stackdepth; // Abstract-interpretation operand-stack depth analysis per function
+pub mod optimize; // Opt-in measured binaryen optimization savings (requires the `wasm-opt` feature)
+pub mod targets; // Data-driven deployment target profiles (built-in + user-supplied TOML/JSON)
+pub mod complexity; // Per-function cyclomatic complexity from decision-point counting
 
 // src/lib.rs
 
@@ -23,18 +35,101 @@ use serde_json; // For serializing the result to JSON string
 /// Main entry point for WASM module analysis
 pub fn analyze_wasm_module(bytes: &[u8]) -> Result<ModuleAnalysis>{
     let parser = WasmParser::new(bytes)?;
-    let module_info = parser.parse()?;
+    let module_info = parser.parse()?.into_module()?;
     
     let mut analyzer = ModuleAnalyzer::new(module_info, bytes); // Pass bytes
     analyzer.analyze()
 }
 
+/// Entry point that handles both core modules and Component Model binaries:
+/// parses `bytes`, then dispatches to the full `ModuleAnalyzer` pipeline for a
+/// core module or the lighter-weight `analyzer::analyze_component` for a
+/// component, rather than failing on the latter like `analyze_wasm_module` does.
+pub fn analyze_wasm_artifact(bytes: &[u8]) -> Result<AnalysisResult> {
+    match WasmParser::new(bytes)?.parse()? {
+        ParsedArtifact::Module(module_info) => {
+            let mut analyzer = ModuleAnalyzer::new(module_info, bytes);
+            Ok(AnalysisResult::Module(analyzer.analyze()?))
+        }
+        ParsedArtifact::Component(component_info) => {
+            Ok(AnalysisResult::Component(analyzer::analyze_component(&component_info)))
+        }
+    }
+}
+
+/// Analyze a module and check its imports against an allow/denylist in one call,
+/// returning the usual analysis alongside the policy verdict.
+pub fn analyze_with_policy(
+    bytes: &[u8],
+    policy: &policy::Policy,
+) -> Result<(ModuleAnalysis, policy::PolicyReport)> {
+    let analysis = analyze_wasm_module(bytes)?;
+    let report = policy::check_policy(&analysis.module_info, policy);
+    Ok((analysis, report))
+}
+
 /// Quick summary analysis for CLI/API responses
 pub fn quick_analyze(bytes: &[u8]) -> Result<ModuleSummary> {
     let analysis = analyze_wasm_module(bytes)?;
     Ok(ModuleSummary::from(analysis))
 }
 
+/// Like `analyze_wasm_module`, but times each analysis pass (security,
+/// performance, memory, compatibility, call-graph) and returns the populated
+/// [`profile::Profiler`] alongside the result.
+pub fn analyze_wasm_module_profiled(bytes: &[u8]) -> Result<(ModuleAnalysis, profile::Profiler)> {
+    let parser = WasmParser::new(bytes)?;
+    let module_info = parser.parse()?.into_module()?;
+
+    let mut analyzer = ModuleAnalyzer::new(module_info, bytes);
+    let mut profiler = profile::Profiler::new(true);
+    let analysis = analyzer.analyze_profiled(&mut profiler)?;
+    Ok((analysis, profiler))
+}
+
+/// Like `analyze_wasm_module`, but additionally runs the module through binaryen's
+/// `wasm-opt` pipeline at `level` and reports the actual measured size delta,
+/// rather than the heuristic guesses in `optimization_suggestions`. Requires this
+/// crate to be built with the `wasm-opt` feature; returns an error otherwise (the
+/// heuristic suggestions in `analysis.performance_metrics` are still populated).
+pub fn analyze_wasm_module_with_measured_optimization(
+    bytes: &[u8],
+    level: optimize::OptLevel,
+) -> Result<(ModuleAnalysis, optimize::MeasuredOptimization)> {
+    let analysis = analyze_wasm_module(bytes)?;
+    let measured = optimize::measure(bytes, level)?;
+    Ok((analysis, measured))
+}
+
+/// Analyze a module given as WebAssembly text format (`.wat`) source, assembling it
+/// to binary in-process rather than shelling out to `wat2wasm`.
+pub fn analyze_wat_str(text: &str) -> Result<ModuleAnalysis> {
+    let bytes = wat::parse_str(text).map_err(|e| anyhow::anyhow!("Failed to parse WAT: {}", e))?;
+    analyze_wasm_module(&bytes)
+}
+
+/// Analyze every module embedded in a `.wast` script, skipping assertion/invocation
+/// directives that aren't themselves a module definition.
+pub fn analyze_wast_str(text: &str) -> Result<Vec<ModuleAnalysis>> {
+    let buf = wast::parser::ParseBuffer::new(text)
+        .map_err(|e| anyhow::anyhow!("Failed to tokenize WAST: {}", e))?;
+    let wast = wast::parser::parse::<wast::Wast>(&buf)
+        .map_err(|e| anyhow::anyhow!("Failed to parse WAST: {}", e))?;
+
+    let mut results = Vec::new();
+    for directive in wast.directives {
+        if let wast::WastDirective::Module(mut quoted_module) = directive {
+            let bytes = quoted_module
+                .encode()
+                .map_err(|e| anyhow::anyhow!("Failed to encode module from WAST: {}", e))?;
+            results.push(analyze_wasm_module(&bytes)?);
+        }
+        // Assertion/invoke/register directives describe expected runtime behavior,
+        // not module structure, so they carry nothing for us to analyze.
+    }
+    Ok(results)
+}
+
 
 #[wasm_bindgen]
 pub fn analyze_wasm_bytes_for_web(wasm_bytes: &[u8]) -> Result<String, JsValue> {
@@ -49,4 +144,23 @@ pub fn analyze_wasm_bytes_for_web(wasm_bytes: &[u8]) -> Result<String, JsValue>
         }
         Err(e) => Err(JsValue::from_str(&format!("WASM Analysis Error: {}", e))),
     }
+}
+
+/// Recommend a capability-lockdown manifest for `wasm_bytes`: the minimal
+/// WASI-Virt-style restriction a host/virtualization layer can apply without
+/// breaking the module, serialized as JSON so a web UI can render "here is
+/// the minimal sandbox this module needs."
+#[wasm_bindgen]
+pub fn recommend_lockdown_for_web(wasm_bytes: &[u8]) -> Result<String, JsValue> {
+    #[cfg(feature = "console_error_panic_hook")]
+    console_error_panic_hook::set_once();
+
+    let module_info = WasmParser::new(wasm_bytes)
+        .and_then(|parser| parser.parse())
+        .and_then(|artifact| artifact.into_module())
+        .map_err(|e| JsValue::from_str(&format!("WASM Analysis Error: {}", e)))?;
+
+    let policy = security::SecurityAnalyzer::new(&module_info, wasm_bytes).recommend_lockdown();
+    serde_json::to_string(&policy)
+        .map_err(|e| JsValue::from_str(&format!("JSON serialization error: {}", e)))
 }
\ No newline at end of file
@@ -0,0 +1,112 @@
+// ===== liveness.rs =====
+// src/liveness.rs
+use crate::types::{ConstValue, ExportKind, ModuleInfo};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Reachability-based liveness analysis over a parsed module, modeled on
+/// classic wasm-gc tree-shaking: which defined functions are actually
+/// reachable from the module's external surface (exports, start function,
+/// and anything a table or global can hand a host/another function), and how
+/// much of the module's code that accounts for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LivenessReport {
+    pub reachable_functions: HashSet<u32>,
+    /// Defined, non-imported functions this pass never marks live. Usually
+    /// identical to `CallGraph::unreachable_functions`/`usage_report.dead_functions`,
+    /// since both seed from exports/start/table slots/`ref.func` globals —
+    /// but this pass only follows *direct* `call` edges, while `CallGraph`
+    /// also treats an unresolved `call_indirect` as reaching every
+    /// same-signature function, so `CallGraph`'s set can be a (safe,
+    /// conservative) superset of this one. Prefer `CallGraph` before deleting
+    /// code; this field is better suited to reporting dead-code byte size.
+    pub dead_functions: Vec<u32>,
+    pub reachable_bytes: u32,
+    pub dead_bytes: u32,
+}
+
+/// Adds `idx` to the live set and, if it wasn't already present, queues it
+/// for the worklist to expand its callees from.
+fn mark_live(idx: u32, live: &mut HashSet<u32>, worklist: &mut Vec<u32>) {
+    if live.insert(idx) {
+        worklist.push(idx);
+    }
+}
+
+impl ModuleInfo {
+    /// Computes which defined functions are live, seeding the worklist with
+    /// every exported function, the start function (if any), every function
+    /// placed in a table via an element segment (reachable through
+    /// `call_indirect`), and every function referenced by `ref.func` in a
+    /// global's init expression — then following direct `call` edges to their
+    /// transitive callees.
+    ///
+    /// Known limitation: functions referenced only by a per-slot `ref.func`
+    /// expression inside a `ElementItems::Expressions`-kind element segment
+    /// aren't seeded, since those per-item expressions aren't resolved during
+    /// parsing (see `ElementSegment::function_indices`'s doc comment).
+    pub fn compute_live_set(&self) -> LivenessReport {
+        let mut callees_by_caller: HashMap<u32, Vec<u32>> = HashMap::new();
+        for &(caller_idx, callee_idx) in &self.function_call_instructions {
+            callees_by_caller.entry(caller_idx).or_default().push(callee_idx);
+        }
+
+        let mut live: HashSet<u32> = HashSet::new();
+        let mut worklist: Vec<u32> = Vec::new();
+
+        for export in &self.exports {
+            if export.kind == ExportKind::Function && !export.points_to_import {
+                mark_live(export.resolved_index, &mut live, &mut worklist);
+            }
+        }
+
+        if let Some(start_idx) = self.start_function {
+            mark_live(start_idx, &mut live, &mut worklist);
+        }
+
+        for segment in &self.element_segments {
+            for &func_idx in &segment.function_indices {
+                mark_live(func_idx, &mut live, &mut worklist);
+            }
+        }
+
+        for global in &self.globals {
+            if let ConstValue::FuncRef(func_idx) = global.init_value {
+                mark_live(func_idx, &mut live, &mut worklist);
+            }
+        }
+
+        while let Some(caller_idx) = worklist.pop() {
+            let Some(callees) = callees_by_caller.get(&caller_idx) else {
+                continue;
+            };
+            for &callee_idx in callees {
+                mark_live(callee_idx, &mut live, &mut worklist);
+            }
+        }
+
+        let mut reachable_bytes: u32 = 0;
+        let mut dead_bytes: u32 = 0;
+        let mut dead_functions: Vec<u32> = Vec::new();
+
+        for func in &self.functions {
+            if func.is_imported {
+                continue;
+            }
+            if live.contains(&func.index) {
+                reachable_bytes += func.body_size;
+            } else {
+                dead_bytes += func.body_size;
+                dead_functions.push(func.index);
+            }
+        }
+        dead_functions.sort_unstable();
+
+        LivenessReport {
+            reachable_functions: live,
+            dead_functions,
+            reachable_bytes,
+            dead_bytes,
+        }
+    }
+}
@@ -1,10 +1,18 @@
 // ===== main.rs =====
 // src/main.rs
-use clap::{Arg, Command};
+use clap::{Arg, ArgMatches, Command};
 use std::fs;
+use std::path::{Path, PathBuf};
 use wasm_inspector::{
-    CallGraph, CompatibilityMatrix, ModuleAnalysis, ModuleInfo, PerformanceMetrics, RiskLevel,
-    SecurityAnalysis, analyze_wasm_module, memory::MemoryAnalysisResult, quick_analyze,
+    CallGraph, CapabilityReport, CompatibilityMatrix, ConformanceReport, ModuleAnalysis, ModuleInfo,
+    PerformanceMetrics, RiskLevel, SecurityAnalysis, analyze_wasm_module,
+    diff::{ModuleDiff, diff_modules},
+    memory::MemoryAnalysisResult, quick_analyze,
+    sarif::build_sarif_report,
+    analyze_wasm_module_profiled,
+    optimize::{self, OptLevel},
+    profile::Profiler,
+    targets,
 };
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -12,6 +20,24 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .version("0.1.0")
         .author("Your Name")
         .about("Analyze and inspect WebAssembly modules")
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .subcommand(inspect_subcommand())
+        .subcommand(diff_subcommand())
+        .subcommand(batch_subcommand())
+        .get_matches();
+
+    match matches.subcommand() {
+        Some(("inspect", sub_matches)) => run_inspect(sub_matches),
+        Some(("diff", sub_matches)) => run_diff(sub_matches),
+        Some(("batch", sub_matches)) => run_batch(sub_matches),
+        _ => unreachable!("clap enforces subcommand_required"),
+    }
+}
+
+fn inspect_subcommand() -> Command {
+    Command::new("inspect")
+        .about("Analyze a single WASM module")
         .arg(
             Arg::new("file")
                 .help("WASM file to analyze")
@@ -30,7 +56,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .short('f')
                 .long("format")
                 .value_name("FORMAT")
-                .help("Output format: json, summary, detailed")
+                .help("Output format: json, summary, detailed, sarif, dot")
                 .default_value("summary"),
         )
         .arg(
@@ -51,28 +77,162 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .action(clap::ArgAction::SetTrue)
                 .help("Show only compatibility analysis"),
         )
-        .get_matches();
+        .arg(
+            Arg::new("fail_on")
+                .long("fail-on")
+                .value_name("LEVEL")
+                .help("Exit non-zero if the overall risk level meets/exceeds LEVEL (low|medium|high|critical)"),
+        )
+        .arg(
+            Arg::new("fail_on_vuln")
+                .long("fail-on-vuln")
+                .action(clap::ArgAction::SetTrue)
+                .requires("fail_on")
+                .help("Also gate on any vulnerability severity meeting/exceeding --fail-on"),
+        )
+        .arg(
+            Arg::new("fail_on_overflow")
+                .long("fail-on-overflow")
+                .action(clap::ArgAction::SetTrue)
+                .requires("fail_on")
+                .help("Also gate on any potential memory overflow risk meeting/exceeding --fail-on"),
+        )
+        .arg(
+            Arg::new("deny_wasi")
+                .long("deny-wasi")
+                .action(clap::ArgAction::SetTrue)
+                .help("Exit non-zero if the module uses WASI"),
+        )
+        .arg(
+            Arg::new("require_browser_safe")
+                .long("require-browser-safe")
+                .action(clap::ArgAction::SetTrue)
+                .help("Exit non-zero if the module is not browser-safe"),
+        )
+        .arg(
+            Arg::new("profile")
+                .long("profile")
+                .action(clap::ArgAction::SetTrue)
+                .help("Time each analysis pass and print a breakdown (attached to JSON output too)"),
+        )
+        .arg(
+            Arg::new("measure_optimizations")
+                .long("measure-optimizations")
+                .value_name("LEVEL")
+                .help("Run the module through wasm-opt and report the measured size delta (O0|O1|O2|O3|Os|Oz). Requires the `wasm-opt` feature."),
+        )
+        .arg(
+            Arg::new("target_profiles")
+                .long("target-profiles")
+                .value_name("FILE")
+                .help("Evaluate the module against deployment target profiles from a .toml or .json file, in addition to the six built-in runtimes"),
+        )
+}
 
-    let file_path = matches.get_one::<String>("file").unwrap();
-    let format = matches.get_one::<String>("format").unwrap();
+fn diff_subcommand() -> Command {
+    Command::new("diff")
+        .about("Compare two WASM modules and report the delta (for CI regression gates)")
+        .arg(
+            Arg::new("old")
+                .help("Baseline WASM file ('before')")
+                .required(true)
+                .index(1),
+        )
+        .arg(
+            Arg::new("new")
+                .help("Candidate WASM file ('after')")
+                .required(true)
+                .index(2),
+        )
+        .arg(
+            Arg::new("fail_on_regression")
+                .long("fail-on-regression")
+                .action(clap::ArgAction::SetTrue)
+                .help("Exit with a non-zero status if the module got strictly more dangerous (new capabilities, a risk escalation, or a new vulnerability)"),
+        )
+}
 
-    // Read the WASM file
+fn batch_subcommand() -> Command {
+    Command::new("batch")
+        .about("Analyze every module matching a directory or glob and print an aggregate table")
+        .arg(
+            Arg::new("target")
+                .help("Directory of .wasm files, or a glob such as 'out/*.wasm'")
+                .required(true)
+                .index(1),
+        )
+}
+
+fn read_wasm_file(file_path: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
     let bytes =
         fs::read(file_path).map_err(|e| format!("Failed to read file '{}': {}", file_path, e))?;
 
-    // Validate it's a WASM file
     if !bytes.starts_with(&[0x00, 0x61, 0x73, 0x6d]) {
-        return Err("File does not appear to be a valid WASM module.".into());
+        return Err(format!("'{}' does not appear to be a valid WASM module.", file_path).into());
     }
 
+    Ok(bytes)
+}
+
+fn run_inspect(matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let file_path = matches.get_one::<String>("file").unwrap();
+    let format = matches.get_one::<String>("format").unwrap();
+
+    let bytes = read_wasm_file(file_path)?;
+
     println!("[INFO] Analyzing WASM module: {}", file_path);
     println!("[INFO] File size: {} bytes", bytes.len());
     println!();
 
+    let profile_enabled = matches.get_flag("profile");
+    let (analysis, profiler) = if profile_enabled {
+        analyze_wasm_module_profiled(&bytes)?
+    } else {
+        (analyze_wasm_module(&bytes)?, Profiler::new(false))
+    };
+
+    let measured_optimization = match matches.get_one::<String>("measure_optimizations") {
+        Some(level_str) => {
+            let level = parse_opt_level(level_str)?;
+            Some(optimize::measure(&bytes, level)?)
+        }
+        None => None,
+    };
+
+    let target_evaluations = match matches.get_one::<String>("target_profiles") {
+        Some(path) => Some(load_and_evaluate_target_profiles(path, &analysis)?),
+        None => None,
+    };
+
     match format.as_str() {
         "json" => {
-            let analysis = analyze_wasm_module(&bytes)?;
-            let json = serde_json::to_string_pretty(&analysis)?;
+            let json = if profile_enabled || measured_optimization.is_some() || target_evaluations.is_some()
+            {
+                let mut value = serde_json::to_value(&analysis)?;
+                if let serde_json::Value::Object(ref mut map) = value {
+                    if profile_enabled {
+                        map.insert(
+                            "profile".to_string(),
+                            serde_json::to_value(profiler.timings())?,
+                        );
+                    }
+                    if let Some(ref measured) = measured_optimization {
+                        map.insert(
+                            "measured_optimization".to_string(),
+                            serde_json::to_value(measured)?,
+                        );
+                    }
+                    if let Some(ref evaluations) = target_evaluations {
+                        map.insert(
+                            "target_evaluations".to_string(),
+                            serde_json::to_value(evaluations)?,
+                        );
+                    }
+                }
+                serde_json::to_string_pretty(&value)?
+            } else {
+                serde_json::to_string_pretty(&analysis)?
+            };
 
             if let Some(output_file) = matches.get_one::<String>("output") {
                 fs::write(output_file, &json)?;
@@ -82,21 +242,377 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
         }
         "summary" => {
-            let summary = quick_analyze(&bytes)?;
+            let summary = wasm_inspector::ModuleSummary::from(analysis.clone());
             print_summary(&summary);
         }
         "detailed" => {
-            let analysis = analyze_wasm_module(&bytes)?;
-            print_detailed_analysis(&analysis, &matches);
+            print_detailed_analysis(&analysis, matches);
+        }
+        "sarif" => {
+            let report = build_sarif_report(&analysis, file_path);
+            let json = serde_json::to_string_pretty(&report)?;
+
+            if let Some(output_file) = matches.get_one::<String>("output") {
+                fs::write(output_file, &json)?;
+                println!("[OK] SARIF report saved to: {}", output_file);
+            } else {
+                println!("{}", json);
+            }
+        }
+        "dot" => {
+            let dot = analysis.call_graph.to_dot();
+
+            if let Some(output_file) = matches.get_one::<String>("output") {
+                fs::write(output_file, &dot)?;
+                println!("[OK] Call graph DOT saved to: {}", output_file);
+            } else {
+                println!("{}", dot);
+            }
         }
         _ => {
-            return Err("Invalid format. Use: json, summary, or detailed.".into());
+            return Err("Invalid format. Use: json, summary, detailed, sarif, or dot.".into());
         }
     }
 
+    if profile_enabled {
+        print_profile_report(&profiler);
+    }
+
+    if let Some(ref measured) = measured_optimization {
+        print_measured_optimization(measured);
+    }
+
+    if let Some(ref evaluations) = target_evaluations {
+        print_target_evaluations(evaluations);
+    }
+
+    check_risk_gates(&analysis, matches)?;
+
+    Ok(())
+}
+
+fn print_profile_report(profiler: &Profiler) {
+    println!("\nPROFILE BREAKDOWN");
+    println!("━━━━━━━━━━━━━━━━━━━━");
+    println!(
+        "{:<16} {:>10} {:>8} {:>14}",
+        "Pass", "Elapsed(ms)", "% Total", "Funcs/ms"
+    );
+    for entry in profiler.report() {
+        println!(
+            "{:<16} {:>10.3} {:>7.1}% {:>14.2}",
+            entry.name, entry.elapsed_ms, entry.percent_of_total, entry.functions_per_ms
+        );
+    }
+    println!();
+}
+
+fn parse_opt_level(value: &str) -> Result<OptLevel, Box<dyn std::error::Error>> {
+    match value.to_ascii_lowercase().as_str() {
+        "o0" => Ok(OptLevel::O0),
+        "o1" => Ok(OptLevel::O1),
+        "o2" => Ok(OptLevel::O2),
+        "o3" => Ok(OptLevel::O3),
+        "os" => Ok(OptLevel::Os),
+        "oz" => Ok(OptLevel::Oz),
+        other => Err(format!(
+            "Invalid --measure-optimizations level '{}'. Use: O0, O1, O2, O3, Os, or Oz.",
+            other
+        )
+        .into()),
+    }
+}
+
+/// Load user-supplied target profiles from `path` (`.toml` or `.json`, by
+/// extension) and evaluate `analysis`'s module against them plus the six
+/// built-in runtimes.
+fn load_and_evaluate_target_profiles(
+    path: &str,
+    analysis: &ModuleAnalysis,
+) -> Result<Vec<targets::TargetEvaluation>, Box<dyn std::error::Error>> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read target profiles file '{}': {}", path, e))?;
+
+    let user_profiles = if path.ends_with(".json") {
+        targets::load_profiles_from_json(&contents)
+    } else if path.ends_with(".toml") {
+        targets::load_profiles_from_toml(&contents)
+    } else {
+        return Err(format!(
+            "Unrecognized target profiles file extension for '{}'. Use .toml or .json.",
+            path
+        )
+        .into());
+    }
+    .map_err(|e| format!("Failed to parse target profiles file '{}': {}", path, e))?;
+
+    Ok(targets::evaluate_all(
+        &analysis.module_info,
+        analysis.performance_metrics.module_size,
+        &user_profiles,
+    ))
+}
+
+fn print_target_evaluations(evaluations: &[targets::TargetEvaluation]) {
+    println!("\nTARGET PROFILE EVALUATIONS");
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    for evaluation in evaluations {
+        let status = if evaluation.compatible { "OK" } else { "FAIL" };
+        println!("[{}] {}", status, evaluation.profile_name);
+        for issue in &evaluation.issues {
+            println!("  - {}", issue);
+        }
+        for feature in &evaluation.required_features {
+            println!("  requires: {}", feature);
+        }
+    }
+    println!();
+}
+
+fn print_measured_optimization(measured: &optimize::MeasuredOptimization) {
+    println!("\nMEASURED OPTIMIZATION ({:?})", measured.level);
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("Original:  {} bytes", measured.original_size);
+    println!("Optimized: {} bytes", measured.optimized_size);
+    println!("Saved:     {} bytes", measured.bytes_saved);
+    if !measured.per_section_delta.is_empty() {
+        println!("Per-section delta:");
+        for (section, delta) in &measured.per_section_delta {
+            if *delta != 0 {
+                println!("  {:<14} {:+}", section, delta);
+            }
+        }
+    }
+    println!("Passes requested: {}", measured.passes_requested.join(", "));
+    println!();
+}
+
+/// CI gate: given the flags from [`inspect_subcommand`], decide whether this
+/// module is too risky to pass, printing one `[FAIL] ...` line per violated
+/// gate before returning an error (which propagates to a non-zero exit code).
+fn check_risk_gates(
+    analysis: &ModuleAnalysis,
+    matches: &ArgMatches,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut failures: Vec<String> = Vec::new();
+
+    if matches.get_flag("deny_wasi") && analysis.security_analysis.wasi_usage.uses_wasi {
+        failures.push("module uses WASI, but --deny-wasi was set".to_string());
+    }
+
+    if matches.get_flag("require_browser_safe")
+        && !analysis.security_analysis.sandbox_compatibility.browser_safe
+    {
+        failures.push("module is not browser-safe, but --require-browser-safe was set".to_string());
+    }
+
+    if let Some(threshold_str) = matches.get_one::<String>("fail_on") {
+        let threshold = parse_risk_level(threshold_str)?;
+
+        let overall_risk = analysis
+            .security_analysis
+            .capabilities
+            .iter()
+            .map(|c| &c.risk_level)
+            .max()
+            .cloned()
+            .unwrap_or(RiskLevel::Low);
+        if overall_risk >= threshold {
+            failures.push(format!(
+                "overall risk level {:?} meets/exceeds --fail-on {:?}",
+                overall_risk, threshold
+            ));
+        }
+
+        if matches.get_flag("fail_on_vuln") {
+            if let Some(worst) = analysis
+                .security_analysis
+                .vulnerabilities
+                .iter()
+                .map(|v| &v.severity)
+                .max()
+            {
+                if *worst >= threshold {
+                    failures.push(format!(
+                        "a vulnerability has severity {:?}, meeting/exceeding --fail-on {:?}",
+                        worst, threshold
+                    ));
+                }
+            }
+        }
+
+        if matches.get_flag("fail_on_overflow") {
+            if let Some(worst) = analysis
+                .memory_analysis
+                .safety_analysis
+                .potential_overflows
+                .iter()
+                .map(|o| &o.risk_level)
+                .max()
+            {
+                if *worst >= threshold {
+                    failures.push(format!(
+                        "a potential memory overflow has risk {:?}, meeting/exceeding --fail-on {:?}",
+                        worst, threshold
+                    ));
+                }
+            }
+        }
+    }
+
+    if failures.is_empty() {
+        return Ok(());
+    }
+
+    for reason in &failures {
+        println!("[FAIL] {}", reason);
+    }
+    Err(format!("{} CI gate(s) failed.", failures.len()).into())
+}
+
+fn parse_risk_level(value: &str) -> Result<RiskLevel, Box<dyn std::error::Error>> {
+    match value.to_ascii_lowercase().as_str() {
+        "low" => Ok(RiskLevel::Low),
+        "medium" => Ok(RiskLevel::Medium),
+        "high" => Ok(RiskLevel::High),
+        "critical" => Ok(RiskLevel::Critical),
+        other => Err(format!(
+            "Invalid --fail-on level '{}'. Use: low, medium, high, or critical.",
+            other
+        )
+        .into()),
+    }
+}
+
+fn run_diff(matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let old_path = matches.get_one::<String>("old").unwrap();
+    let new_path = matches.get_one::<String>("new").unwrap();
+
+    let old_bytes = read_wasm_file(old_path)?;
+    let new_bytes = read_wasm_file(new_path)?;
+
+    let old_analysis = analyze_wasm_module(&old_bytes)?;
+    let new_analysis = analyze_wasm_module(&new_bytes)?;
+
+    let delta = diff_modules(&old_analysis, &new_analysis);
+    print_diff_report(old_path, new_path, &delta);
+
+    if matches.get_flag("fail_on_regression") && delta.has_regressions() {
+        return Err(
+            "The module got strictly more dangerous relative to the baseline \
+             (new capabilities, a capability risk escalation, or a new vulnerability)."
+                .into(),
+        );
+    }
+
+    Ok(())
+}
+
+fn run_batch(matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let target = matches.get_one::<String>("target").unwrap();
+    let files = expand_batch_targets(target)?;
+
+    if files.is_empty() {
+        return Err(format!("No .wasm files matched '{}'.", target).into());
+    }
+
+    let mut rows: Vec<(String, wasm_inspector::ModuleSummary)> = Vec::new();
+
+    for path in &files {
+        let display_path = path.display().to_string();
+        let bytes = match read_wasm_file(&display_path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                println!("[SKIP] {}: {}", display_path, e);
+                continue;
+            }
+        };
+
+        println!("[INFO] Analyzing WASM module: {}", display_path);
+        println!("[INFO] File size: {} bytes", bytes.len());
+        println!();
+
+        let summary = quick_analyze(&bytes)?;
+        print_summary(&summary);
+        rows.push((display_path, summary));
+    }
+
+    print_batch_table(&rows);
+
     Ok(())
 }
 
+/// Resolve a `batch` target into a sorted list of `.wasm` files: either every
+/// `.wasm` file directly inside a directory, or every file in a directory that
+/// matches a single-`*`-wildcard glob such as `out/*.wasm`.
+fn expand_batch_targets(target: &str) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
+    let path = Path::new(target);
+
+    if path.is_dir() {
+        let mut matches: Vec<PathBuf> = fs::read_dir(path)
+            .map_err(|e| format!("Failed to read directory '{}': {}", target, e))?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("wasm"))
+            .collect();
+        matches.sort();
+        return Ok(matches);
+    }
+
+    let (dir, file_pattern) = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => (
+            parent.to_path_buf(),
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or(target)
+                .to_string(),
+        ),
+        _ => (PathBuf::from("."), target.to_string()),
+    };
+
+    let mut matches: Vec<PathBuf> = fs::read_dir(&dir)
+        .map_err(|e| format!("Failed to read directory '{}': {}", dir.display(), e))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|name| glob_match(&file_pattern, name))
+        })
+        .collect();
+    matches.sort();
+    Ok(matches)
+}
+
+/// Minimal single-`*`-wildcard glob matcher (sufficient for patterns like
+/// `*.wasm` or `bundle-*.wasm`); not a full glob implementation.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == text;
+    }
+
+    let mut pos = 0;
+    for (i, part) in parts.iter().enumerate() {
+        if part.is_empty() {
+            continue;
+        }
+        if i == 0 {
+            if !text[pos..].starts_with(part) {
+                return false;
+            }
+            pos += part.len();
+        } else if i == parts.len() - 1 {
+            return text.len() >= pos + part.len() && text[pos..].ends_with(part);
+        } else if let Some(found) = text[pos..].find(part) {
+            pos += found + part.len();
+        } else {
+            return false;
+        }
+    }
+    true
+}
+
 fn print_summary(summary: &wasm_inspector::ModuleSummary) {
     println!("MODULE SUMMARY");
     println!("━━━━━━━━━━━━━━━━");
@@ -135,6 +651,204 @@ fn print_summary(summary: &wasm_inspector::ModuleSummary) {
     println!();
 }
 
+fn print_diff_report(old_path: &str, new_path: &str, delta: &ModuleDiff) {
+    println!("MODULE DIFF");
+    println!("━━━━━━━━━━━━━━━");
+    println!("Baseline: {}", old_path);
+    println!("Candidate: {}", new_path);
+    println!();
+
+    if delta.is_empty() {
+        println!("[INFO] No tracked differences between baseline and candidate.");
+        println!();
+        return;
+    }
+
+    if !delta.new_capabilities.is_empty() {
+        println!("New Capabilities:");
+        for cap in &delta.new_capabilities {
+            let risk_text = match cap.risk_level {
+                RiskLevel::Low => "[LOW]",
+                RiskLevel::Medium => "[MEDIUM]",
+                RiskLevel::High => "[HIGH]",
+                RiskLevel::Critical => "[CRITICAL]",
+            };
+            println!("  + {} {} - {}", risk_text, cap.name, cap.description);
+        }
+    }
+
+    if !delta.removed_capabilities.is_empty() {
+        println!("Removed Capabilities:");
+        for cap in &delta.removed_capabilities {
+            println!("  - {}", cap.name);
+        }
+    }
+
+    if !delta.capability_risk_escalations.is_empty() {
+        println!("Capability Risk Escalations:");
+        for escalation in &delta.capability_risk_escalations {
+            println!(
+                "  ! {}: {:?} -> {:?}",
+                escalation.name, escalation.old_risk_level, escalation.new_risk_level
+            );
+        }
+    }
+
+    if !delta.new_vulnerabilities.is_empty() {
+        println!("New Vulnerabilities:");
+        for vuln in &delta.new_vulnerabilities {
+            println!("  + [{:?}] {} - {}", vuln.severity, vuln.id, vuln.description);
+        }
+    }
+    if !delta.resolved_vulnerabilities.is_empty() {
+        println!("Resolved Vulnerabilities:");
+        for vuln in &delta.resolved_vulnerabilities {
+            println!("  - {}", vuln.id);
+        }
+    }
+
+    if delta.complexity_score_delta != 0.0 {
+        println!(
+            "Complexity Score: {:+.1}",
+            delta.complexity_score_delta
+        );
+    }
+    if delta.cold_start_ms_delta != 0.0 {
+        println!(
+            "Estimated Cold Start: {:+.2}ms",
+            delta.cold_start_ms_delta
+        );
+    }
+    if delta.module_size_delta != 0 {
+        println!("Module Size: {:+} bytes", delta.module_size_delta);
+    }
+    if delta.code_size_delta != 0 {
+        println!("Code Size: {:+} bytes", delta.code_size_delta);
+    }
+
+    if !delta.new_imports.is_empty() {
+        println!("New Imports:");
+        for import in &delta.new_imports {
+            println!("  + {}::{}", import.module, import.name);
+        }
+    }
+    if !delta.removed_imports.is_empty() {
+        println!("Removed Imports:");
+        for import in &delta.removed_imports {
+            println!("  - {}::{}", import.module, import.name);
+        }
+    }
+
+    if !delta.new_exports.is_empty() {
+        println!("New Exports:");
+        for export in &delta.new_exports {
+            println!("  + {}", export.name);
+        }
+    }
+    if !delta.removed_exports.is_empty() {
+        println!("Removed Exports:");
+        for export in &delta.removed_exports {
+            println!("  - {}", export.name);
+        }
+    }
+
+    if !delta.new_functions.is_empty() {
+        println!("New Functions:");
+        for function in &delta.new_functions {
+            let name = function.demangled_name.as_deref().or(function.name.as_deref()).unwrap_or("N/A");
+            println!("  + {} (Index {})", name, function.index);
+        }
+    }
+    if !delta.removed_functions.is_empty() {
+        println!("Removed Functions:");
+        for function in &delta.removed_functions {
+            let name = function.demangled_name.as_deref().or(function.name.as_deref()).unwrap_or("N/A");
+            println!("  - {} (Index {})", name, function.index);
+        }
+    }
+    if !delta.renamed_functions.is_empty() {
+        println!("Renamed Functions:");
+        for rename in &delta.renamed_functions {
+            println!(
+                "  ~ Index {}: {:?} -> {:?}",
+                rename.index, rename.old_name, rename.new_name
+            );
+        }
+    }
+
+    if let Some(ref mem_change) = delta.memory_limit_change {
+        println!(
+            "Memory Limits: initial {:?} -> {:?} pages, maximum {:?} -> {:?} pages",
+            mem_change.old_initial_pages,
+            mem_change.new_initial_pages,
+            mem_change.old_maximum_pages,
+            mem_change.new_maximum_pages
+        );
+    }
+    if !delta.table_limit_changes.is_empty() {
+        println!("Table Limit Changes:");
+        for change in &delta.table_limit_changes {
+            println!(
+                "  ~ Table {}: initial {} -> {}, maximum {:?} -> {:?}",
+                change.index, change.old_initial, change.new_initial, change.old_maximum, change.new_maximum
+            );
+        }
+    }
+
+    if !delta.new_memory_hotspots.is_empty() {
+        println!("New Memory Hotspots:");
+        for hotspot in &delta.new_memory_hotspots {
+            let func_name = hotspot.function_name.as_deref().unwrap_or("N/A");
+            println!(
+                "  + Function: {} (Index {}), Type: {:?}",
+                func_name, hotspot.function_index, hotspot.hotspot_type
+            );
+        }
+    }
+
+    println!();
+    if delta.has_regressions() {
+        println!("[WARN] Regressions detected: the module got strictly more dangerous.");
+    } else {
+        println!("[OK] No new capabilities, risk escalations, or vulnerabilities introduced.");
+    }
+    println!();
+}
+
+fn print_batch_table(rows: &[(String, wasm_inspector::ModuleSummary)]) {
+    println!("BATCH SUMMARY");
+    println!("━━━━━━━━━━━━━━━━");
+    println!(
+        "{:<40} {:<10} {:>12} {:<5}",
+        "File", "Risk", "Size (B)", "WASI"
+    );
+
+    let mut worst_risk = RiskLevel::Low;
+    for (path, summary) in rows {
+        let risk_text = match summary.risk_level {
+            RiskLevel::Low => "[LOW]",
+            RiskLevel::Medium => "[MEDIUM]",
+            RiskLevel::High => "[HIGH]",
+            RiskLevel::Critical => "[CRITICAL]",
+        };
+        println!(
+            "{:<40} {:<10} {:>12} {:<5}",
+            path,
+            risk_text,
+            summary.size_bytes,
+            if summary.uses_wasi { "Yes" } else { "No" }
+        );
+        if summary.risk_level > worst_risk {
+            worst_risk = summary.risk_level.clone();
+        }
+    }
+
+    println!();
+    println!("Modules analyzed: {}", rows.len());
+    println!("Worst-case risk level: {:?}", worst_risk);
+    println!();
+}
+
 fn print_detailed_analysis(analysis: &ModuleAnalysis, matches: &clap::ArgMatches) {
     let show_all = !matches.get_flag("security")
         && !matches.get_flag("performance")
@@ -162,6 +876,8 @@ fn print_detailed_analysis(analysis: &ModuleAnalysis, matches: &clap::ArgMatches
     if show_all {
         print_module_structure(&analysis.module_info);
         print_call_graph_summary(&analysis.call_graph);
+        print_capability_report(&analysis.capability_report);
+        print_conformance_report(&analysis.conformance);
     }
 }
 
@@ -424,16 +1140,7 @@ fn print_compatibility_analysis(compat: &CompatibilityMatrix) {
     println!("\nCOMPATIBILITY ANALYSIS");
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
-    let runtimes = [
-        ("Wasmtime", &compat.wasmtime),
-        ("Wasmer", &compat.wasmer),
-        ("Browser", &compat.browser),
-        ("Node.js", &compat.node_js),
-        ("Deno", &compat.deno),
-        ("Cloudflare Workers", &compat.cloudflare_workers),
-    ];
-
-    for (name, status) in &runtimes {
+    for (name, status) in &compat.targets {
         let status_indicator = if status.compatible {
             "[COMPATIBLE]"
         } else {
@@ -607,6 +1314,18 @@ fn print_call_graph_summary(call_graph: &CallGraph) {
         println!("Unreachable Defined Functions: None found.");
     }
 
+    if call_graph.recursive_components.is_empty() {
+        println!("Recursive Cycles: None found.");
+    } else {
+        println!(
+            "Recursive Cycles (self- or mutually-recursive): {}",
+            call_graph.recursive_components.len()
+        );
+        for component in &call_graph.recursive_components {
+            println!("  - {:?}", component);
+        }
+    }
+
     let mut most_called: Vec<_> = call_graph
         .nodes
         .iter()
@@ -624,5 +1343,113 @@ fn print_call_graph_summary(call_graph: &CallGraph) {
             );
         }
     }
+
+    let usage = &call_graph.usage_report;
+    if !usage.unused_imports.is_empty() {
+        println!(
+            "\nUnused Imports (declared but never called): {}",
+            usage.unused_imports.len()
+        );
+        println!("  Indices: {:?}", usage.unused_imports);
+    }
+    if !usage.leaf_only_exports.is_empty() {
+        println!(
+            "\nLeaf-Only Exports (never called internally, only via export): {}",
+            usage.leaf_only_exports.len()
+        );
+        println!("  Indices: {:?}", usage.leaf_only_exports);
+    }
+
+    let top_inline_candidates: Vec<_> = call_graph
+        .inline_candidates
+        .iter()
+        .filter(|c| c.score > 0.0)
+        .take(5)
+        .collect();
+    if !top_inline_candidates.is_empty() {
+        println!("\nTop Inlining Candidates (by benefit score):");
+        for candidate in top_inline_candidates {
+            let func_name = call_graph
+                .nodes
+                .iter()
+                .find(|n| n.function_index == candidate.function_index)
+                .and_then(|n| n.name.as_deref())
+                .unwrap_or("N/A");
+            println!(
+                "  - \"{}\" (Index {}): cost {}, {} call site(s), score {:.4}",
+                func_name, candidate.function_index, candidate.estimated_cost, candidate.call_sites, candidate.score
+            );
+        }
+    }
+    println!();
+}
+
+fn print_capability_report(report: &CapabilityReport) {
+    println!("\nCAPABILITY REPORT");
+    println!("━━━━━━━━━━━━━━━━━━━━");
+
+    if report.required_imports_by_namespace.is_empty() {
+        println!("Required Host Imports: None");
+    } else {
+        println!("Required Host Imports (by namespace):");
+        for (namespace, count) in &report.required_imports_by_namespace {
+            println!("  - {}: {} function(s)", namespace, count);
+        }
+    }
+
+    if report.present_entry_points.is_empty() {
+        println!("Known Entry Points: None found");
+    } else {
+        println!("Known Entry Points: {}", report.present_entry_points.join(", "));
+    }
+
+    println!("Runtime/Contract Profiles:");
+    for profile in &report.profiles {
+        if profile.satisfied {
+            println!("  [OK] {}", profile.name);
+        } else {
+            println!(
+                "  [--] {} (missing: {})",
+                profile.name,
+                profile.missing_exports.join(", ")
+            );
+        }
+    }
+    println!();
+}
+
+fn print_conformance_report(reports: &[ConformanceReport]) {
+    println!("\nINTERFACE CONFORMANCE");
+    println!("━━━━━━━━━━━━━━━━━━━━");
+
+    for report in reports {
+        if report.conforms {
+            println!("  [OK] {}", report.profile_name);
+            continue;
+        }
+
+        println!("  [--] {}", report.profile_name);
+        if !report.missing_exports.is_empty() {
+            println!("        missing exports: {}", report.missing_exports.join(", "));
+        }
+        if !report.signature_mismatches.is_empty() {
+            println!("        signature mismatches: {}", report.signature_mismatches.join("; "));
+        }
+        if !report.missing_imports.is_empty() {
+            println!("        missing imports: {}", report.missing_imports.join(", "));
+        }
+        if !report.forbidden_imports_present.is_empty() {
+            println!(
+                "        forbidden imports present: {}",
+                report.forbidden_imports_present.join(", ")
+            );
+        }
+        if !report.forbidden_exports_present.is_empty() {
+            println!(
+                "        forbidden exports present: {}",
+                report.forbidden_exports_present.join(", ")
+            );
+        }
+    }
     println!();
 }
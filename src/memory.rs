@@ -1,27 +1,768 @@
 // ===== memory.rs =====
 // src/memory.rs
+use crate::passes::{drive_function_body, FunctionBodyPass};
 use crate::types::*;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
-use wasmparser::{FunctionBody, Operator}; 
+use wasmparser::{FunctionBody, Operator};
 
 pub struct MemoryAnalyzer<'a> {
     module_info: &'a ModuleInfo,
     wasm_bytes: &'a [u8],
     memory_operations: HashMap<u32, Vec<MemoryOperation>>, // Key: global function index
     allocation_patterns: Vec<AllocationPattern>,
+    unchecked_allocations: Vec<UncheckedAllocationFinding>,
+    bounds_findings: Vec<BoundsFinding>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)] // OK
 pub struct MemoryOperation {
     pub operation_type: MemoryOpType,
+    /// The static offset encoded in the instruction (`memarg.offset` for a
+    /// load/store), independent of whatever the dynamic base operand turns out
+    /// to be.
     pub offset: Option<u32>,
+    /// The operand this operation's size is resolved from, when the abstract
+    /// interpreter in `analyze_function_body_for_memory_ops` could determine it
+    /// statically: access size in bytes for `Load`/`Store`, page delta for
+    /// `MemoryGrow`, byte length for `MemoryCopy`/`MemoryFill`. `None` when the
+    /// relevant operand depends on something not tracked by the interpreter.
     pub size: Option<u32>,
+    /// For `Load`/`Store`, `base + offset` when the base operand resolved to a
+    /// known constant.
+    pub effective_address: Option<u32>,
+    /// Whether a `memory.grow` occurred earlier in this same function, which
+    /// invalidates any overflow check based on the module's declared memory size.
+    pub preceded_by_grow: bool,
     pub function_index: u32,
     pub instruction_offset: u32,
 }
 
+/// A value tracked by the tiny abstract interpreter in
+/// `analyze_function_body_for_memory_ops`: either a statically-known constant or
+/// `Unknown`. Kept deliberately flat (no ranges, no symbolic expressions) so the
+/// pass stays a single linear walk over the instruction stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AbstractVal {
+    Const(i64),
+    Unknown,
+}
+
+impl AbstractVal {
+    fn as_const(self) -> Option<i64> {
+        match self {
+            AbstractVal::Const(v) => Some(v),
+            AbstractVal::Unknown => None,
+        }
+    }
+}
+
+/// Pop the top of the abstract stack, treating an empty stack as `Unknown`
+/// rather than panicking — the interpreter doesn't model every instruction's
+/// stack effect, so it can legitimately run dry.
+fn pop_val(stack: &mut Vec<AbstractVal>) -> AbstractVal {
+    stack.pop().unwrap_or(AbstractVal::Unknown)
+}
+
+/// Pop two operands and fold them through `f` if both are constants, otherwise
+/// push `Unknown`.
+fn fold_binop(stack: &mut Vec<AbstractVal>, f: impl Fn(i64, i64) -> i64) {
+    let b = pop_val(stack);
+    let a = pop_val(stack);
+    let result = match (a, b) {
+        (AbstractVal::Const(a), AbstractVal::Const(b)) => AbstractVal::Const(f(a, b)),
+        _ => AbstractVal::Unknown,
+    };
+    stack.push(result);
+}
+
+/// `base + offset` as a `u32` address, if `base` is a known constant.
+fn resolve_effective_address(base: AbstractVal, offset: u64) -> Option<u32> {
+    let base = base.as_const()? as u32;
+    Some(base.wrapping_add(offset as u32))
+}
+
+/// Which shape of threads-proposal atomic instruction a `MemoryOperation` is
+/// being built for — determines which `MemoryOpType` variant `atomic_op`
+/// produces.
+enum AtomicKind {
+    Load,
+    Store,
+    Rmw(AtomicRmwOp),
+    Cmpxchg,
+}
+
+/// The read-modify-write operation performed by an `*.atomic.rmw.*`
+/// instruction, read off the `Operator` itself by `atomic_rmw_op_kind` since
+/// the match arms in `analyze_function_body_for_memory_ops` group every op
+/// of a given operand size into one arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)] // OK
+pub enum AtomicRmwOp {
+    Add,
+    Sub,
+    And,
+    Or,
+    Xor,
+    Xchg,
+}
+
+/// Maps an `I32AtomicRmw*`/`I64AtomicRmw*` operator to the RMW operation it
+/// performs, independent of operand size/signedness.
+fn atomic_rmw_op_kind(op: &Operator) -> AtomicRmwOp {
+    match op {
+        Operator::I32AtomicRmwAdd { .. }
+        | Operator::I32AtomicRmw8AddU { .. }
+        | Operator::I32AtomicRmw16AddU { .. }
+        | Operator::I64AtomicRmwAdd { .. }
+        | Operator::I64AtomicRmw8AddU { .. }
+        | Operator::I64AtomicRmw16AddU { .. }
+        | Operator::I64AtomicRmw32AddU { .. } => AtomicRmwOp::Add,
+        Operator::I32AtomicRmwSub { .. }
+        | Operator::I32AtomicRmw8SubU { .. }
+        | Operator::I32AtomicRmw16SubU { .. }
+        | Operator::I64AtomicRmwSub { .. }
+        | Operator::I64AtomicRmw8SubU { .. }
+        | Operator::I64AtomicRmw16SubU { .. }
+        | Operator::I64AtomicRmw32SubU { .. } => AtomicRmwOp::Sub,
+        Operator::I32AtomicRmwAnd { .. }
+        | Operator::I32AtomicRmw8AndU { .. }
+        | Operator::I32AtomicRmw16AndU { .. }
+        | Operator::I64AtomicRmwAnd { .. }
+        | Operator::I64AtomicRmw8AndU { .. }
+        | Operator::I64AtomicRmw16AndU { .. }
+        | Operator::I64AtomicRmw32AndU { .. } => AtomicRmwOp::And,
+        Operator::I32AtomicRmwOr { .. }
+        | Operator::I32AtomicRmw8OrU { .. }
+        | Operator::I32AtomicRmw16OrU { .. }
+        | Operator::I64AtomicRmwOr { .. }
+        | Operator::I64AtomicRmw8OrU { .. }
+        | Operator::I64AtomicRmw16OrU { .. }
+        | Operator::I64AtomicRmw32OrU { .. } => AtomicRmwOp::Or,
+        Operator::I32AtomicRmwXor { .. }
+        | Operator::I32AtomicRmw8XorU { .. }
+        | Operator::I32AtomicRmw16XorU { .. }
+        | Operator::I64AtomicRmwXor { .. }
+        | Operator::I64AtomicRmw8XorU { .. }
+        | Operator::I64AtomicRmw16XorU { .. }
+        | Operator::I64AtomicRmw32XorU { .. } => AtomicRmwOp::Xor,
+        Operator::I32AtomicRmwXchg { .. }
+        | Operator::I32AtomicRmw8XchgU { .. }
+        | Operator::I32AtomicRmw16XchgU { .. }
+        | Operator::I64AtomicRmwXchg { .. }
+        | Operator::I64AtomicRmw8XchgU { .. }
+        | Operator::I64AtomicRmw16XchgU { .. }
+        | Operator::I64AtomicRmw32XchgU { .. } => AtomicRmwOp::Xchg,
+        other => unreachable!("atomic_rmw_op_kind called on non-RMW operator: {:?}", other),
+    }
+}
+
+/// Builds a `MemoryOperation` for an atomic load/store/read-modify-write/
+/// compare-exchange, shared across all the size/op-kind variants matched in
+/// `analyze_function_body_for_memory_ops`.
+fn atomic_op(
+    func_global_idx: u32,
+    instruction_offset: u32,
+    preceded_by_grow: bool,
+    memarg_offset: u64,
+    base: AbstractVal,
+    size_bytes: u32,
+    kind: AtomicKind,
+) -> MemoryOperation {
+    let operation_type = match kind {
+        AtomicKind::Load => MemoryOpType::AtomicLoad { size_bytes },
+        AtomicKind::Store => MemoryOpType::AtomicStore { size_bytes },
+        AtomicKind::Rmw(op) => MemoryOpType::AtomicRmw { size_bytes, op },
+        AtomicKind::Cmpxchg => MemoryOpType::AtomicCmpxchg { size_bytes },
+    };
+    MemoryOperation {
+        operation_type,
+        offset: Some(memarg_offset as u32),
+        size: Some(size_bytes),
+        effective_address: resolve_effective_address(base, memarg_offset),
+        preceded_by_grow,
+        function_index: func_global_idx,
+        instruction_offset,
+    }
+}
+
+/// A lightweight abstract interpreter run as a `FunctionBodyPass`: tracks an
+/// operand stack and a local/global map of `Const(i64)`/`Unknown` values so
+/// dynamic memory sizes and effective addresses can be resolved when they're
+/// statically known, without building a full control-flow graph or fixpoint
+/// solver. Params start `Unknown` (caller-supplied); declared locals are
+/// always zero-initialized by the WASM spec, so they start as `Const(0)`.
+struct MemoryOpsPass<'a> {
+    const_globals: &'a HashMap<u32, i64>,
+    locals: HashMap<u32, AbstractVal>,
+    stack: Vec<AbstractVal>,
+    seen_grow: bool,
+    operations: Vec<MemoryOperation>,
+}
+
+impl<'a> MemoryOpsPass<'a> {
+    fn new(locals: HashMap<u32, AbstractVal>, const_globals: &'a HashMap<u32, i64>) -> Self {
+        Self {
+            const_globals,
+            locals,
+            stack: Vec::new(),
+            seen_grow: false,
+            operations: Vec::new(),
+        }
+    }
+
+    fn finish(self) -> Vec<MemoryOperation> {
+        self.operations
+    }
+}
+
+/// Where an unchecked, possibly-failed allocation result came from: a
+/// `memory.grow` (fails by returning `-1`) or a call to a detected allocation
+/// function (fails by returning a null `0` pointer).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AllocOrigin {
+    Grow,
+    HeapCall,
+}
+
+/// A `memory.grow`/allocation-call result that reached a load/store address
+/// with no `i32.eqz`/`-1`-compare/`br_if` check in between.
+struct UncheckedAllocationFinding {
+    function_index: u32,
+    instruction_offset: u32,
+    origin: AllocOrigin,
+}
+
+/// Whether `op` dereferences a dynamic base operand as a memory address —
+/// the common load/store shapes; narrower lane/lane-replace and RMW variants
+/// aren't enumerated since they're rare as the very first use of a freshly
+/// returned pointer.
+fn is_memory_address_consuming_op(op: &Operator) -> bool {
+    matches!(
+        op,
+        Operator::I32Load { .. }
+            | Operator::I64Load { .. }
+            | Operator::F32Load { .. }
+            | Operator::F64Load { .. }
+            | Operator::I32Load8S { .. }
+            | Operator::I32Load8U { .. }
+            | Operator::I32Load16S { .. }
+            | Operator::I32Load16U { .. }
+            | Operator::I64Load8S { .. }
+            | Operator::I64Load8U { .. }
+            | Operator::I64Load16S { .. }
+            | Operator::I64Load16U { .. }
+            | Operator::I64Load32S { .. }
+            | Operator::I64Load32U { .. }
+            | Operator::I32Store { .. }
+            | Operator::I64Store { .. }
+            | Operator::F32Store { .. }
+            | Operator::F64Store { .. }
+            | Operator::I32Store8 { .. }
+            | Operator::I32Store16 { .. }
+            | Operator::I64Store8 { .. }
+            | Operator::I64Store16 { .. }
+            | Operator::I64Store32 { .. }
+            | Operator::V128Load { .. }
+            | Operator::V128Store { .. }
+            | Operator::I32AtomicLoad { .. }
+            | Operator::I64AtomicLoad { .. }
+            | Operator::I32AtomicStore { .. }
+            | Operator::I64AtomicStore { .. }
+    )
+}
+
+/// Lightweight peephole pass, independent of `MemoryOpsPass`'s address-resolving
+/// stack: it just watches for a `memory.grow`/allocation-call result reaching a
+/// load/store address before an `i32.eqz`/`i32.const -1` compare or `br_if`
+/// tests it first — the single most common WASM memory bug, treating a
+/// fallible allocation as if it always succeeds.
+struct OomResiliencePass<'a> {
+    alloc_function_indices: &'a HashSet<u32>,
+    pending: Option<(u32, AllocOrigin)>,
+    saw_const_neg_one: bool,
+    findings: Vec<UncheckedAllocationFinding>,
+}
+
+impl<'a> OomResiliencePass<'a> {
+    fn new(alloc_function_indices: &'a HashSet<u32>) -> Self {
+        Self {
+            alloc_function_indices,
+            pending: None,
+            saw_const_neg_one: false,
+            findings: Vec::new(),
+        }
+    }
+
+    fn finish(self) -> Vec<UncheckedAllocationFinding> {
+        self.findings
+    }
+}
+
+impl FunctionBodyPass for OomResiliencePass<'_> {
+    fn visit(&mut self, func_idx: u32, offset: u32, op: &Operator) {
+        if let Some((origin_offset, origin)) = self.pending {
+            match op {
+                Operator::I32Eqz => {
+                    self.pending = None;
+                    self.saw_const_neg_one = false;
+                }
+                Operator::I32Const { value: -1 } => {
+                    self.saw_const_neg_one = true;
+                }
+                (Operator::I32Eq | Operator::I32Ne) if self.saw_const_neg_one => {
+                    self.pending = None;
+                    self.saw_const_neg_one = false;
+                }
+                Operator::BrIf { .. }
+                | Operator::Loop { .. }
+                | Operator::Else
+                | Operator::End
+                | Operator::Br { .. }
+                | Operator::BrTable { .. }
+                | Operator::Drop => {
+                    // `BrIf` is treated as a check (the value, or something derived
+                    // from it, gates a branch); the rest are control-flow merges or
+                    // an explicit discard, after which the linear scan can no
+                    // longer trust this is still the value in play.
+                    self.pending = None;
+                    self.saw_const_neg_one = false;
+                }
+                op if is_memory_address_consuming_op(op) => {
+                    self.findings.push(UncheckedAllocationFinding {
+                        function_index: func_idx,
+                        instruction_offset: origin_offset,
+                        origin,
+                    });
+                    self.pending = None;
+                    self.saw_const_neg_one = false;
+                }
+                _ => {}
+            }
+        }
+
+        match op {
+            Operator::MemoryGrow { .. } => {
+                self.pending = Some((offset, AllocOrigin::Grow));
+                self.saw_const_neg_one = false;
+            }
+            Operator::Call { function_index } if self.alloc_function_indices.contains(function_index) => {
+                self.pending = Some((offset, AllocOrigin::HeapCall));
+                self.saw_const_neg_one = false;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Whether `name` looks like an allocation (not deallocation) function by
+/// naming convention — used to build the set of call targets `OomResiliencePass`
+/// treats as fallible-allocation sources.
+fn is_allocation_function_name(name: &str) -> bool {
+    (name.contains("alloc") || name.contains("malloc"))
+        && !name.contains("free")
+        && !name.contains("dealloc")
+}
+
+/// The global function indices of every import/defined function whose name
+/// looks like an allocator (see `is_allocation_function_name`), for
+/// `OomResiliencePass` to recognize calls into them.
+fn allocation_function_indices(module_info: &ModuleInfo) -> HashSet<u32> {
+    let mut indices = HashSet::new();
+
+    for (i, imp) in module_info
+        .imports
+        .iter()
+        .filter(|i| matches!(i.kind, ImportKind::Function { .. }))
+        .enumerate()
+    {
+        if is_allocation_function_name(&imp.name) {
+            indices.insert(i as u32);
+        }
+    }
+
+    for func in &module_info.functions {
+        if func
+            .name
+            .as_deref()
+            .map_or(false, is_allocation_function_name)
+        {
+            indices.insert(func.index);
+        }
+    }
+
+    indices
+}
+
+/// A value tracked by `BoundsAnalysisPass`: like `AbstractVal`, but an
+/// interval rather than a single constant, so that masking (`i32.and` with a
+/// constant) or arithmetic on a guard-refined local can still produce a
+/// provable upper bound even when the coarser `Const`/`Unknown` lattice would
+/// have given up and called it `Unknown`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Interval {
+    Exact(i64),
+    Bounded { lo: i64, hi: i64 },
+    Unknown,
+}
+
+impl Interval {
+    fn bounds(self) -> Option<(i64, i64)> {
+        match self {
+            Interval::Exact(v) => Some((v, v)),
+            Interval::Bounded { lo, hi } => Some((lo, hi)),
+            Interval::Unknown => None,
+        }
+    }
+
+    fn add(self, other: Interval) -> Interval {
+        match (self.bounds(), other.bounds()) {
+            (Some((l1, h1)), Some((l2, h2))) => {
+                // Nothing upstream validates that operand types match the
+                // operator (e.g. an `i64.const` feeding an `i32.load`), so a
+                // hand-crafted module can push bounds near `i64::MAX`/`MIN` —
+                // saturate instead of panicking/wrapping like plain `+` would.
+                let (lo, hi) = (l1.saturating_add(l2), h1.saturating_add(h2));
+                if lo == hi {
+                    Interval::Exact(lo)
+                } else {
+                    Interval::Bounded { lo, hi }
+                }
+            }
+            _ => Interval::Unknown,
+        }
+    }
+
+    fn sub(self, other: Interval) -> Interval {
+        match (self.bounds(), other.bounds()) {
+            (Some((l1, h1)), Some((l2, h2))) => {
+                let (lo, hi) = (l1.saturating_sub(h2), h1.saturating_sub(l2));
+                if lo == hi {
+                    Interval::Exact(lo)
+                } else {
+                    Interval::Bounded { lo, hi }
+                }
+            }
+            _ => Interval::Unknown,
+        }
+    }
+
+    /// Folds only the constant case; a `Bounded * Bounded` product isn't
+    /// linear in the endpoints, so it's not worth widening precisely here.
+    fn mul(self, other: Interval) -> Interval {
+        match (self, other) {
+            (Interval::Exact(a), Interval::Exact(b)) => Interval::Exact(a * b),
+            _ => Interval::Unknown,
+        }
+    }
+
+    /// `self & mask`: ANDing with a non-negative constant mask always clamps
+    /// the result into `[0, mask]` regardless of what `self` is — the one
+    /// binary op this lattice can reason about without knowing one operand.
+    fn and_mask(self, mask: Interval) -> Interval {
+        match (self, mask) {
+            (Interval::Exact(s), Interval::Exact(m)) if m >= 0 => Interval::Exact(s & m),
+            (_, Interval::Exact(m)) if m >= 0 => Interval::Bounded { lo: 0, hi: m },
+            _ => Interval::Unknown,
+        }
+    }
+}
+
+/// A memory access `BoundsAnalysisPass` proved exceeds the module's declared
+/// memory limit: the computed base address interval's upper bound, combined
+/// with the instruction's static offset and access size, is past `limit`.
+/// Unlike `analyze_memory_safety`'s existing offset-only heuristic, this
+/// holds even when the base address is built from arithmetic or a bitmask
+/// rather than being a bare constant.
+struct BoundsFinding {
+    function_index: u32,
+    instruction_offset: u32,
+    proven_range: (i64, i64),
+}
+
+/// The `(memarg.offset, access size in bytes, is_store)` of a load/store
+/// operator, for `BoundsAnalysisPass` to combine with the popped base
+/// interval — mirrors the load/store shapes `MemoryOpsPass` already handles,
+/// just collapsed into one table instead of a per-size `MemoryOperation`
+/// literal, since this pass only needs the numbers, not a full record.
+fn load_store_info(op: &Operator) -> Option<(u64, u32, bool)> {
+    use Operator::*;
+    Some(match op {
+        I32Load { memarg } | F32Load { memarg } => (memarg.offset, 4, false),
+        I64Load { memarg } | F64Load { memarg } => (memarg.offset, 8, false),
+        I32Load8S { memarg } | I32Load8U { memarg } => (memarg.offset, 1, false),
+        I32Load16S { memarg } | I32Load16U { memarg } => (memarg.offset, 2, false),
+        I64Load8S { memarg } | I64Load8U { memarg } => (memarg.offset, 1, false),
+        I64Load16S { memarg } | I64Load16U { memarg } => (memarg.offset, 2, false),
+        I64Load32S { memarg } | I64Load32U { memarg } => (memarg.offset, 4, false),
+        V128Load { memarg } => (memarg.offset, 16, false),
+        I32Store { memarg } | F32Store { memarg } => (memarg.offset, 4, true),
+        I64Store { memarg } | F64Store { memarg } => (memarg.offset, 8, true),
+        I32Store8 { memarg } | I64Store8 { memarg } => (memarg.offset, 1, true),
+        I32Store16 { memarg } | I64Store16 { memarg } => (memarg.offset, 2, true),
+        I64Store32 { memarg } => (memarg.offset, 4, true),
+        V128Store { memarg } => (memarg.offset, 16, true),
+        _ => return None,
+    })
+}
+
+/// An intra-function abstract interpreter that tracks an `Interval` (rather
+/// than `MemoryOpsPass`'s single `Const`/`Unknown`) per local/stack slot, so
+/// a base address built from masking or from arithmetic on a guard-refined
+/// local can be proven bounded even when the coarser lattice would give up.
+///
+/// Scoped to stay a single linear walk, like its sibling passes:
+/// - Control-flow merges (a loop back-edge, `else`, any block/loop/if `end`,
+///   or any branch) reset every tracked value to `Unknown` — the same
+///   widening `MemoryOpsPass` already applies at the same points, so a
+///   loop-carried local's interval is soundly forgotten rather than carried
+///   past a join point this single pass can't fix-point.
+/// - Only the `local.get`/`i32.const`/`{lt,le}_u` → `if` idiom refines a
+///   local's interval, to `[0, bound)` on the taken "then" branch; other
+///   guard shapes (signed compares, `br_if`-based loop-exit checks) aren't
+///   recognized and just fall back to the unrefined interval.
+/// - Only accesses *proven* to exceed `limit` are reported. An `Unknown`
+///   base is deliberately not itself a finding: almost every dynamic-base
+///   load in a real module would match it, which would drown out the two
+///   precise heuristics `analyze_memory_safety` already has.
+struct BoundsAnalysisPass<'a> {
+    const_globals: &'a HashMap<u32, i64>,
+    locals: HashMap<u32, Interval>,
+    stack: Vec<Interval>,
+    limit: u32,
+    /// The `(local_index, bound, inclusive)` of the most recent
+    /// `local.get`/`i32.const`/`{lt,le}_u` sequence, consumed by the next
+    /// `If` to refine that local's interval on the taken branch.
+    pending_guard: Option<(u32, i64, bool)>,
+    last_local_get: Option<u32>,
+    findings: Vec<BoundsFinding>,
+}
+
+impl<'a> BoundsAnalysisPass<'a> {
+    fn new(locals: HashMap<u32, Interval>, const_globals: &'a HashMap<u32, i64>, limit: u32) -> Self {
+        Self {
+            const_globals,
+            locals,
+            stack: Vec::new(),
+            limit,
+            pending_guard: None,
+            last_local_get: None,
+            findings: Vec::new(),
+        }
+    }
+
+    fn pop(&mut self) -> Interval {
+        self.stack.pop().unwrap_or(Interval::Unknown)
+    }
+
+    fn reset(&mut self) {
+        self.stack.clear();
+        for v in self.locals.values_mut() {
+            *v = Interval::Unknown;
+        }
+        self.pending_guard = None;
+        self.last_local_get = None;
+    }
+
+    fn finish(self) -> Vec<BoundsFinding> {
+        self.findings
+    }
+}
+
+impl FunctionBodyPass for BoundsAnalysisPass<'_> {
+    fn visit(&mut self, func_idx: u32, offset: u32, op: &Operator) {
+        // Track the `local.get`/`i32.const`/`{lt,le}_u` guard idiom as a side
+        // channel alongside the normal stack simulation below; anything that
+        // isn't part of the expected sequence drops the in-progress match.
+        match op {
+            Operator::LocalGet { local_index } => self.last_local_get = Some(*local_index),
+            Operator::I32Const { value } => {
+                if let Some(local_index) = self.last_local_get {
+                    self.pending_guard = Some((local_index, *value as i64, false));
+                }
+            }
+            Operator::I32LtU => {
+                if let Some((local_index, bound, _)) = self.pending_guard {
+                    self.pending_guard = Some((local_index, bound, false));
+                } else {
+                    self.pending_guard = None;
+                }
+            }
+            Operator::I32LeU => {
+                if let Some((local_index, bound, _)) = self.pending_guard {
+                    self.pending_guard = Some((local_index, bound, true));
+                } else {
+                    self.pending_guard = None;
+                }
+            }
+            Operator::If { .. } => {
+                if let Some((local_index, bound, inclusive)) = self.pending_guard.take() {
+                    let upper = if inclusive { bound } else { bound - 1 };
+                    let refined = match self.locals.get(&local_index).copied() {
+                        Some(Interval::Bounded { hi, .. }) | Some(Interval::Exact(hi))
+                            if hi < upper =>
+                        {
+                            Interval::Bounded { lo: 0, hi }
+                        }
+                        _ => Interval::Bounded { lo: 0, hi: upper },
+                    };
+                    self.locals.insert(local_index, refined);
+                }
+            }
+            _ => {
+                self.last_local_get = None;
+                self.pending_guard = None;
+            }
+        }
+
+        // The actual interval stack/locals simulation, mirroring
+        // `MemoryOpsPass`'s shape so the two passes stay easy to compare.
+        match op {
+            Operator::Loop { .. }
+            | Operator::Else
+            | Operator::End
+            | Operator::Br { .. }
+            | Operator::BrIf { .. }
+            | Operator::BrTable { .. } => self.reset(),
+
+            Operator::I32Const { value } => self.stack.push(Interval::Exact(*value as i64)),
+            Operator::I64Const { value } => self.stack.push(Interval::Exact(*value)),
+            Operator::LocalGet { local_index } => {
+                let v = self.locals.get(local_index).copied().unwrap_or(Interval::Unknown);
+                self.stack.push(v);
+            }
+            Operator::LocalSet { local_index } => {
+                let v = self.pop();
+                self.locals.insert(*local_index, v);
+            }
+            Operator::LocalTee { local_index } => {
+                let v = self.pop();
+                self.locals.insert(*local_index, v);
+                self.stack.push(v);
+            }
+            Operator::GlobalGet { global_index } => {
+                let v = self
+                    .const_globals
+                    .get(global_index)
+                    .map(|v| Interval::Exact(*v))
+                    .unwrap_or(Interval::Unknown);
+                self.stack.push(v);
+            }
+            Operator::GlobalSet { .. } => {
+                self.pop();
+            }
+            Operator::I32Add => {
+                let b = self.pop();
+                let a = self.pop();
+                self.stack.push(a.add(b));
+            }
+            Operator::I32Sub => {
+                let b = self.pop();
+                let a = self.pop();
+                self.stack.push(a.sub(b));
+            }
+            Operator::I32Mul => {
+                let b = self.pop();
+                let a = self.pop();
+                self.stack.push(a.mul(b));
+            }
+            Operator::I32And => {
+                let b = self.pop();
+                let a = self.pop();
+                self.stack.push(a.and_mask(b));
+            }
+            Operator::Drop => {
+                self.pop();
+            }
+
+            _ => {
+                if let Some((memarg_offset, size, is_store)) = load_store_info(op) {
+                    if is_store {
+                        self.pop(); // value
+                    }
+                    let base = self.pop();
+                    if !is_store {
+                        self.stack.push(Interval::Unknown); // loaded value
+                    }
+
+                    if let Some((lo, hi)) = base.bounds() {
+                        // No value-type validation runs before this pass either, so `base`
+                        // may be a 64-bit interval left on the stack by a type-mismatched
+                        // operator sequence (e.g. `i64.const i64::MAX` before `i32.load`) —
+                        // saturate rather than let the combine overflow `i64`.
+                        let proven_lo = lo.saturating_add(memarg_offset as i64);
+                        let proven_hi = hi.saturating_add(memarg_offset as i64).saturating_add(size as i64);
+                        if self.limit > 0 && proven_hi > self.limit as i64 {
+                            self.findings.push(BoundsFinding {
+                                function_index: func_idx,
+                                instruction_offset: offset,
+                                proven_range: (proven_lo, proven_hi),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The arithmetic mean of `values`, or `None` if empty.
+fn average(values: &[u32]) -> Option<u32> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().copied().sum::<u32>() / values.len() as u32)
+    }
+}
+
+/// Longest weighted path from each entry point through the call graph,
+/// skipping any function involved in a cycle (`recursive_functions`) so the
+/// walk never needs to detect a cycle itself. Returns one `(cumulative frame
+/// size, chain)` pair per entry point that isn't itself recursive.
+fn longest_call_chains(
+    entry_points: &[u32],
+    adjacency: &HashMap<u32, Vec<u32>>,
+    recursive_functions: &HashSet<u32>,
+    frame_size: &HashMap<u32, u32>,
+) -> Vec<(u32, Vec<u32>)> {
+    fn longest_from(
+        node: u32,
+        adjacency: &HashMap<u32, Vec<u32>>,
+        recursive_functions: &HashSet<u32>,
+        frame_size: &HashMap<u32, u32>,
+        memo: &mut HashMap<u32, (u32, Vec<u32>)>,
+    ) -> (u32, Vec<u32>) {
+        if let Some(cached) = memo.get(&node) {
+            return cached.clone();
+        }
+        let own_size = frame_size.get(&node).copied().unwrap_or(0);
+        let mut best = (own_size, vec![node]);
+        if let Some(successors) = adjacency.get(&node) {
+            for &succ in successors {
+                if recursive_functions.contains(&succ) {
+                    continue;
+                }
+                let (succ_depth, succ_chain) =
+                    longest_from(succ, adjacency, recursive_functions, frame_size, memo);
+                if own_size + succ_depth > best.0 {
+                    let mut chain = vec![node];
+                    chain.extend(succ_chain);
+                    best = (own_size + succ_depth, chain);
+                }
+            }
+        }
+        memo.insert(node, best.clone());
+        best
+    }
+
+    let mut memo = HashMap::new();
+    entry_points
+        .iter()
+        .filter(|ep| !recursive_functions.contains(ep))
+        .map(|&ep| longest_from(ep, adjacency, recursive_functions, frame_size, &mut memo))
+        .collect()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)] // OK
 pub enum MemoryOpType {
     Load { size_bytes: u32 },
@@ -30,6 +771,56 @@ pub enum MemoryOpType {
     MemoryGrow,
     MemoryCopy,
     MemoryFill,
+    /// `memory.init`, copying from the passive data segment `data_index` into
+    /// linear memory — tracked so dead-segment analysis can tell which
+    /// passive segments are ever actually initialized.
+    MemoryInit { data_index: u32 },
+    /// A SIMD vector load/store (plain, widening, splat, zero, or lane
+    /// access) — all treated as a flat 16-byte access, since the narrower
+    /// lane/splat variants only ever move a sub-lane of a 16-byte register.
+    Vector { size_bytes: u32 },
+    /// `*.atomic.load*` from the threads (atomics) proposal.
+    AtomicLoad { size_bytes: u32 },
+    /// `*.atomic.store*` from the threads (atomics) proposal.
+    AtomicStore { size_bytes: u32 },
+    /// `*.atomic.rmw*.{add,sub,and,or,xor,xchg}` — a read-modify-write that
+    /// serializes concurrent access more than a plain load/store, since the
+    /// read and the write can't be interleaved with another agent's access.
+    AtomicRmw { size_bytes: u32, op: AtomicRmwOp },
+    /// `*.atomic.rmw*.cmpxchg` — compares and conditionally replaces, the
+    /// heaviest-weight atomic access since it carries two operands plus the
+    /// address.
+    AtomicCmpxchg { size_bytes: u32 },
+    /// `memory.atomic.wait32`/`memory.atomic.wait64`.
+    AtomicWait,
+    /// `memory.atomic.notify`.
+    AtomicNotify,
+}
+
+/// Whether `op_type` is one of the threads-proposal atomic instructions.
+fn is_atomic_op(op_type: &MemoryOpType) -> bool {
+    matches!(
+        op_type,
+        MemoryOpType::AtomicLoad { .. }
+            | MemoryOpType::AtomicStore { .. }
+            | MemoryOpType::AtomicRmw { .. }
+            | MemoryOpType::AtomicCmpxchg { .. }
+            | MemoryOpType::AtomicWait
+            | MemoryOpType::AtomicNotify
+    )
+}
+
+/// Whether `op_type` is an RMW or wait/notify atomic — the shapes that
+/// actually serialize across threads (a plain atomic load/store doesn't
+/// contend the way a read-modify-write or a blocking wait does).
+fn is_atomic_contention_op(op_type: &MemoryOpType) -> bool {
+    matches!(
+        op_type,
+        MemoryOpType::AtomicRmw { .. }
+            | MemoryOpType::AtomicCmpxchg { .. }
+            | MemoryOpType::AtomicWait
+            | MemoryOpType::AtomicNotify
+    )
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)] // OK
@@ -65,6 +856,8 @@ pub struct MemoryAnalysisResult {
     pub memory_hotspots: Vec<MemoryHotspot>,
     pub optimization_opportunities: Vec<MemoryOptimization>,
     pub safety_analysis: MemorySafetyAnalysis,
+    pub thread_safety_analysis: ThreadSafetyAnalysis,
+    pub pooling_recommendation: PoolingRecommendation,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)] // OK
@@ -74,6 +867,7 @@ pub struct MemoryLayout {
     pub data_segments: Vec<DataSegmentAnalysis>,
     pub stack_estimation: StackAnalysis,
     pub heap_estimation: HeapAnalysis,
+    pub data_segment_report: DataSegmentReport,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)] // OK
@@ -93,6 +887,49 @@ pub enum DataUsagePattern {
     Unknown,
 }
 
+/// One data segment's resolved placement: `[start, end)` is its byte range in
+/// `memory_index`'s linear memory. Passive segments have no static placement
+/// (`start`/`end` are `None`) since their destination is only known at the
+/// `memory.init` call site.
+#[derive(Debug, Clone, Serialize, Deserialize)] // OK
+pub struct DataSegmentInventoryEntry {
+    pub index: u32,
+    pub memory_index: u32,
+    pub is_active: bool,
+    pub start: Option<u32>,
+    pub end: Option<u32>,
+    pub size: u32,
+}
+
+/// A run of explicit zero bytes at the end of a segment's encoded data —
+/// redundant since a linear memory already starts zero-filled, so trimming it
+/// shrinks the module with no change in behavior.
+#[derive(Debug, Clone, Serialize, Deserialize)] // OK
+pub struct ZeroFillGap {
+    pub segment_index: u32,
+    pub trailing_zero_bytes: u32,
+}
+
+/// A per-segment inventory of the data section, like a live-files listing:
+/// every segment's resolved placement, plus the structural problems that flat
+/// size totals can't see. `overlapping_segments` and `out_of_bounds_segments`
+/// are mirrored into `MemorySafetyAnalysis.potential_overflows` as `High`
+/// risk, since both cause instantiation failures rather than mere
+/// inefficiency.
+#[derive(Debug, Clone, Serialize, Deserialize)] // OK
+pub struct DataSegmentReport {
+    pub segments: Vec<DataSegmentInventoryEntry>,
+    /// Pairs of segment indices (on the same memory) whose `[start, end)`
+    /// ranges overlap.
+    pub overlapping_segments: Vec<(u32, u32)>,
+    /// Active segments whose `[start, end)` range extends past the memory's
+    /// declared *initial* size — data initialization runs at instantiation,
+    /// before any `memory.grow`, so this is checked against `initial`, not
+    /// `maximum`.
+    pub out_of_bounds_segments: Vec<u32>,
+    pub zero_fill_gaps: Vec<ZeroFillGap>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)] // OK
 pub struct StackAnalysis {
     pub estimated_max_depth: u32,
@@ -105,6 +942,57 @@ pub struct HeapAnalysis {
     pub uses_dynamic_allocation: bool,
     pub allocation_functions: Vec<String>,
     pub estimated_heap_usage: u32,
+    pub allocator_kind: AllocatorKind,
+}
+
+/// Best-effort fingerprint of the module's embedded allocator, from
+/// import/export naming conventions. Drives which advice
+/// `identify_optimizations` gives for `FrequentSmallAllocations` patterns
+/// instead of a single generic "use pooling" message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)] // OK
+pub enum AllocatorKind {
+    Dlmalloc,
+    WeeAlloc,
+    EmscriptenMalloc,
+    RustSystemAlloc,
+    CustomPool,
+    Unknown,
+}
+
+/// Inspects exported/imported function names for the naming conventions each
+/// allocator leaves behind. `has_frequent_small_allocations` lets a
+/// malloc/free pair that doesn't match any known vendor symbol still be
+/// classified as `CustomPool` rather than `Unknown` when it's clearly under
+/// small-allocation pressure.
+fn fingerprint_allocator(module_info: &ModuleInfo, has_frequent_small_allocations: bool) -> AllocatorKind {
+    let exported_names: Vec<&str> = module_info
+        .functions
+        .iter()
+        .filter(|f| f.is_exported)
+        .filter_map(|f| f.name.as_deref())
+        .collect();
+
+    let has_export = |needle: &str| exported_names.iter().any(|n| n.contains(needle));
+    let has_import_module = |needle: &str| module_info.imports.iter().any(|i| i.module.contains(needle));
+    let has_import_name = |needle: &str| module_info.imports.iter().any(|i| i.name.contains(needle));
+
+    if has_export("wee_alloc") || has_import_name("wee_alloc") {
+        AllocatorKind::WeeAlloc
+    } else if has_export("__rust_alloc") || has_export("__rust_dealloc") || has_export("__rust_realloc") {
+        AllocatorKind::RustSystemAlloc
+    } else if has_export("dlmalloc") || has_export("dlfree") {
+        AllocatorKind::Dlmalloc
+    } else if has_import_module("emscripten") || has_export("emscripten") || has_export("_malloc") || has_export("_emmalloc") {
+        AllocatorKind::EmscriptenMalloc
+    } else if has_export("malloc") && has_export("free") {
+        if has_frequent_small_allocations {
+            AllocatorKind::CustomPool
+        } else {
+            AllocatorKind::Unknown
+        }
+    } else {
+        AllocatorKind::Unknown
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)] // OK
@@ -114,6 +1002,8 @@ pub struct MemoryOperationAnalysis {
     pub store_operations: u32,
     pub bulk_operations: u32,
     pub memory_growth_operations: u32,
+    pub vector_operations: u32,
+    pub atomic_operations: u32,
     pub operation_density: f64,
 }
 
@@ -126,12 +1016,15 @@ pub struct MemoryHotspot {
     pub hotspot_type: HotspotType,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)] // OK
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)] // OK
 pub enum HotspotType {
     HighFrequencyAccess,
     LargeDataMovement,
     MemoryGrowth,
     PotentialLeaks,
+    /// Dominated by `AtomicRmw`/`AtomicCmpxchg`/`AtomicWait`/`AtomicNotify`
+    /// ops, which serialize across threads rather than just this function.
+    AtomicContention,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)] // OK
@@ -149,6 +1042,38 @@ pub enum OptimizationType {
     MinimizeAllocations,
     ImproveLocality,
     SetMemoryLimits,
+    /// A host running many short-lived instances can pre-reserve pooling-allocator
+    /// slots from `pooling_recommendation` instead of mapping memory per instance.
+    PoolingAllocatorConfig,
+}
+
+/// How a pooling allocator should reclaim an instance's memory slot between
+/// uses.
+#[derive(Debug, Clone, Serialize, Deserialize)] // OK
+pub enum MemoryResetStrategy {
+    /// Remap the slot back to its zeroed initial snapshot via copy-on-write;
+    /// cheap, and the default for a non-shared memory.
+    CopyOnWrite,
+    /// Explicitly zero-fill the slot on reset: required for `shared` memory,
+    /// since its pages may still be mapped into another live thread and can't
+    /// be swapped out from under it.
+    ZeroFill,
+}
+
+/// The static per-instance upper bounds a wasmtime-style pooling instance
+/// allocator needs up front, synthesized from the module's declared memory/
+/// table limits and, when memory has no declared maximum, from observed
+/// `MemoryGrow` hotspot traffic.
+#[derive(Debug, Clone, Serialize, Deserialize)] // OK
+pub struct PoolingRecommendation {
+    pub max_memory_pages: u32,
+    pub requires_growth: bool,
+    pub reset_strategy: MemoryResetStrategy,
+    pub recommended_slot_bytes: u32,
+    /// Sum of each table's maximum element count (or its initial count, when
+    /// no maximum is declared), for sizing a pooling allocator's table slots
+    /// alongside its memory slots.
+    pub table_element_capacity: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)] // OK
@@ -164,6 +1089,39 @@ pub struct MemorySafetyAnalysis {
     pub uninitialized_access_risk: RiskLevel, // From types.rs
     pub memory_leak_risk: RiskLevel,          // From types.rs
     pub buffer_safety_score: f64,
+    pub data_race_risk: DataRaceRisk,
+}
+
+/// Concurrent-access hazard on a *shared* linear memory (the threads
+/// proposal's `shared` flag): functions that mix non-atomic loads/stores with
+/// atomic accesses are a sign that access to the shared memory isn't
+/// consistently synchronized.
+#[derive(Debug, Clone, Serialize, Deserialize)] // OK
+pub struct DataRaceRisk {
+    pub risk_level: RiskLevel, // From types.rs
+    pub functions_involved: Vec<u32>,
+    pub description: String,
+}
+
+/// Threads-proposal-specific checks, reported alongside `MemorySafetyAnalysis`
+/// rather than folded into it: these are about whether a module's use of
+/// `shared`/atomics makes sense at all, not about overflow/leak safety.
+#[derive(Debug, Clone, Serialize, Deserialize)] // OK
+pub struct ThreadSafetyAnalysis {
+    pub is_shared_memory: bool,
+    /// Functions that issue atomic instructions even though the module's
+    /// memory isn't declared `shared` — harmless at runtime (Wasm 2.0 allows
+    /// atomics on unshared memory) but usually a sign the `shared` flag was
+    /// meant to be set and wasn't, or that the atomics are dead weight.
+    pub atomics_on_non_shared_memory: Vec<u32>,
+    /// Memory is declared `shared` but no function ever issues an atomic
+    /// instruction — every access is a potential data race with no
+    /// synchronization in sight, and/or `shared` may simply be unneeded.
+    pub shared_memory_with_no_atomics: bool,
+    /// Functions from `find_memory_hotspots` whose `hotspot_type` is
+    /// `HotspotType::AtomicContention` — hotspots dominated by RMW/cmpxchg/
+    /// wait/notify ops, which serialize across threads.
+    pub atomic_contention_hotspots: Vec<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)] // OK
@@ -172,6 +1130,10 @@ pub struct PotentialOverflow {
     pub operation_type: String,
     pub risk_level: RiskLevel, // From types.rs
     pub description: String,
+    /// The `(lo, hi)` address range `BoundsAnalysisPass` proved the access
+    /// falls in, when this finding came from that analysis rather than one
+    /// of `analyze_memory_safety`'s other, coarser heuristics.
+    pub inferred_address_range: Option<(i64, i64)>,
 }
 
 const WASM_PAGE_SIZE_BYTES: u32 = 64 * 1024;
@@ -183,18 +1145,41 @@ impl<'a> MemoryAnalyzer<'a> {
             wasm_bytes,
             memory_operations: HashMap::new(),
             allocation_patterns: Vec::new(),
+            unchecked_allocations: Vec::new(),
+            bounds_findings: Vec::new(),
         }
     }
 
-    pub fn analyze(&mut self) -> Result<MemoryAnalysisResult> {
+    /// The memory limit `analyze_memory_safety`/`BoundsAnalysisPass` check
+    /// accesses against: the declared maximum if one exists, else the
+    /// initial size — both expressed in bytes, not pages. A module with no
+    /// memory section at all falls back to a single page, matching the
+    /// pre-existing heuristic in `analyze_memory_safety`.
+    fn memory_limit_bytes(&self) -> u32 {
+        self.module_info
+            .memory
+            .as_ref()
+            .map_or(WASM_PAGE_SIZE_BYTES, |m| {
+                m.maximum.unwrap_or(m.initial) * WASM_PAGE_SIZE_BYTES
+            })
+    }
+
+    pub fn analyze(&mut self, call_graph: &CallGraph) -> Result<MemoryAnalysisResult> {
         self.extract_memory_operations()?;
         self.analyze_allocation_patterns(); // Uses self.memory_operations
 
-        let memory_layout = self.analyze_memory_layout();
+        let memory_layout = self.analyze_memory_layout(call_graph);
         let operation_analysis = self.analyze_operations_summary(); // Renamed from analyze_operations
         let memory_hotspots = self.find_memory_hotspots();
-        let optimization_opportunities = self.identify_optimizations();
-        let safety_analysis = self.analyze_memory_safety();
+        let pooling_recommendation = self.build_pooling_recommendation();
+        let optimization_opportunities = self.identify_optimizations(
+            call_graph,
+            &pooling_recommendation,
+            &memory_layout.heap_estimation,
+            &memory_layout.data_segment_report,
+        );
+        let safety_analysis = self.analyze_memory_safety(&memory_layout.data_segment_report);
+        let thread_safety_analysis = self.analyze_thread_safety(&memory_hotspots);
 
         Ok(MemoryAnalysisResult {
             memory_layout,
@@ -203,6 +1188,8 @@ impl<'a> MemoryAnalyzer<'a> {
             memory_hotspots,
             optimization_opportunities,
             safety_analysis,
+            thread_safety_analysis,
+            pooling_recommendation,
         })
     }
 
@@ -220,206 +1207,829 @@ impl<'a> MemoryAnalyzer<'a> {
             .filter(|i| matches!(i.kind, ImportKind::Function { .. }))
             .count() as u32;
 
+        let const_globals = self.immutable_const_globals();
+        let alloc_function_indices = allocation_function_indices(self.module_info);
+        let limit = self.memory_limit_bytes();
+
+        // First collect every function body (and its global index); the per-body
+        // analysis below only reads `self.module_info`/`const_globals`, so the
+        // bodies can then be analyzed independently of one another.
+        let mut bodies = Vec::new();
         for payload_result in parser.parse_all(self.wasm_bytes) {
             let payload = payload_result?;
             if let Payload::CodeSectionEntry(body) = payload {
                 let current_func_global_idx =
                     imported_function_count + defined_function_idx_counter;
-                self.analyze_function_body_for_memory_ops(current_func_global_idx, &body)?;
+                bodies.push((current_func_global_idx, body));
                 defined_function_idx_counter += 1;
             }
         }
+
+        let per_function_results = Self::analyze_bodies(
+            self.module_info,
+            &bodies,
+            &const_globals,
+            &alloc_function_indices,
+            limit,
+        )?;
+        for (func_global_idx, operations, findings, bounds_findings) in per_function_results {
+            if !operations.is_empty() {
+                self.memory_operations.insert(func_global_idx, operations);
+            }
+            self.unchecked_allocations.extend(findings);
+            self.bounds_findings.extend(bounds_findings);
+        }
         Ok(())
     }
 
+    /// Opt-in parallel variant, built with `--features parallel`: each function
+    /// body is analyzed independently on rayon's global thread pool and the
+    /// results are collected afterward, so there's no shared mutable state
+    /// during the parallel region. Without the feature, falls back to a plain
+    /// sequential loop so the crate keeps building in single-threaded/`no_std`-ish
+    /// configurations that can't pull in rayon.
+    #[cfg(feature = "parallel")]
+    fn analyze_bodies(
+        module_info: &ModuleInfo,
+        bodies: &[(u32, FunctionBody)],
+        const_globals: &HashMap<u32, i64>,
+        alloc_function_indices: &HashSet<u32>,
+        limit: u32,
+    ) -> Result<
+        Vec<(
+            u32,
+            Vec<MemoryOperation>,
+            Vec<UncheckedAllocationFinding>,
+            Vec<BoundsFinding>,
+        )>,
+    > {
+        use rayon::prelude::*;
+
+        bodies
+            .par_iter()
+            .map(|(func_global_idx, body)| {
+                Self::analyze_function_body_for_memory_ops(
+                    module_info,
+                    *func_global_idx,
+                    body,
+                    const_globals,
+                    alloc_function_indices,
+                    limit,
+                )
+                .map(|(ops, findings, bounds)| (*func_global_idx, ops, findings, bounds))
+            })
+            .collect()
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    fn analyze_bodies(
+        module_info: &ModuleInfo,
+        bodies: &[(u32, FunctionBody)],
+        const_globals: &HashMap<u32, i64>,
+        alloc_function_indices: &HashSet<u32>,
+        limit: u32,
+    ) -> Result<
+        Vec<(
+            u32,
+            Vec<MemoryOperation>,
+            Vec<UncheckedAllocationFinding>,
+            Vec<BoundsFinding>,
+        )>,
+    > {
+        bodies
+            .iter()
+            .map(|(func_global_idx, body)| {
+                Self::analyze_function_body_for_memory_ops(
+                    module_info,
+                    *func_global_idx,
+                    body,
+                    const_globals,
+                    alloc_function_indices,
+                    limit,
+                )
+                .map(|(ops, findings, bounds)| (*func_global_idx, ops, findings, bounds))
+            })
+            .collect()
+    }
+
+    /// Every immutable global whose init expression resolved to an integer
+    /// constant (see `parser::parse_global_section`), keyed by global index — the
+    /// set `global.get` can fold to a `Const` rather than `Unknown`.
+    fn immutable_const_globals(&self) -> HashMap<u32, i64> {
+        self.module_info
+            .globals
+            .iter()
+            .filter(|g| !g.global_type.mutable)
+            .filter_map(|g| g.init_value.as_i64().map(|v| (g.index, v)))
+            .collect()
+    }
+
+    /// Builds the initial locals map (params start `Unknown`, declared locals
+    /// start `Const(0)` per the WASM spec's zero-initialization rule), then
+    /// drives a fresh `MemoryOpsPass` over `body` via `drive_function_body` so
+    /// the operator stream is parsed exactly once.
+    ///
+    /// Associated function (rather than a `&mut self` method) so it has no
+    /// shared mutable state: `analyze_bodies` can run one of these per
+    /// function body in parallel and merge the resulting vecs afterwards.
+    /// Drives `MemoryOpsPass`, `OomResiliencePass`, and `BoundsAnalysisPass`
+    /// together so `body`'s operator stream is still parsed exactly once.
     fn analyze_function_body_for_memory_ops(
-        &mut self,
+        module_info: &ModuleInfo,
         func_global_idx: u32,
         body: &FunctionBody,
-    ) -> Result<()> {
-        let mut reader = body.get_operators_reader()?;
-        let mut instruction_offset_counter: u32;
-        let mut operations_for_func = Vec::new();
-
-        while !reader.eof() {
-            let op_pos = reader.original_position();
-            let op = reader.read()?;
-            instruction_offset_counter = op_pos as u32; // Using original_position as offset
-
-            let mem_op = match op {
-                Operator::I32Load { memarg } => Some(MemoryOperation {
-                    operation_type: MemoryOpType::Load { size_bytes: 4 },
-                    offset: Some(memarg.offset as u32),
-                    size: Some(4),
-                    function_index: func_global_idx,
-                    instruction_offset: instruction_offset_counter,
-                }),
-                Operator::I64Load { memarg } => Some(MemoryOperation {
-                    operation_type: MemoryOpType::Load { size_bytes: 8 },
-                    offset: Some(memarg.offset as u32),
-                    size: Some(8),
-                    function_index: func_global_idx,
-                    instruction_offset: instruction_offset_counter,
-                }),
-                Operator::F32Load { memarg } => Some(MemoryOperation {
-                    operation_type: MemoryOpType::Load { size_bytes: 4 },
-                    offset: Some(memarg.offset as u32),
-                    size: Some(4),
-                    function_index: func_global_idx,
-                    instruction_offset: instruction_offset_counter,
-                }),
-                Operator::F64Load { memarg } => Some(MemoryOperation {
-                    operation_type: MemoryOpType::Load { size_bytes: 8 },
-                    offset: Some(memarg.offset as u32),
-                    size: Some(8),
-                    function_index: func_global_idx,
-                    instruction_offset: instruction_offset_counter,
-                }),
+        const_globals: &HashMap<u32, i64>,
+        alloc_function_indices: &HashSet<u32>,
+        limit: u32,
+    ) -> Result<(
+        Vec<MemoryOperation>,
+        Vec<UncheckedAllocationFinding>,
+        Vec<BoundsFinding>,
+    )> {
+        let param_count = module_info
+            .functions
+            .iter()
+            .find(|f| f.index == func_global_idx)
+            .map(|f| f.params.len() as u32)
+            .unwrap_or(0);
+
+        let mut locals: HashMap<u32, AbstractVal> = HashMap::new();
+        let mut interval_locals: HashMap<u32, Interval> = HashMap::new();
+        let mut next_local_idx = param_count;
+        for local_result in body.get_locals_reader()? {
+            let (count, _value_type) = local_result?;
+            for _ in 0..count {
+                locals.insert(next_local_idx, AbstractVal::Const(0));
+                interval_locals.insert(next_local_idx, Interval::Exact(0));
+                next_local_idx += 1;
+            }
+        }
+
+        let mut mem_pass = MemoryOpsPass::new(locals, const_globals);
+        let mut oom_pass = OomResiliencePass::new(alloc_function_indices);
+        let mut bounds_pass = BoundsAnalysisPass::new(interval_locals, const_globals, limit);
+        drive_function_body(
+            func_global_idx,
+            body,
+            &mut [&mut mem_pass, &mut oom_pass, &mut bounds_pass],
+        )?;
+        Ok((mem_pass.finish(), oom_pass.finish(), bounds_pass.finish()))
+    }
+}
+
+impl FunctionBodyPass for MemoryOpsPass<'_> {
+    fn visit(&mut self, func_idx: u32, offset: u32, op: &Operator) {
+        let func_global_idx = func_idx;
+        let instruction_offset_counter = offset;
+        let seen_grow = self.seen_grow;
+        let const_globals = self.const_globals;
+        let stack = &mut self.stack;
+        let locals = &mut self.locals;
+
+        let mem_op = match op {
+                // Control-flow merges: this interpreter walks one linear path
+                // rather than joining multiple incoming edges, so the sound
+                // thing to do wherever another edge could join in (a loop's
+                // back-edge, an `else`, a block/loop/if's `end`, or any branch)
+                // is to forget what we thought we knew rather than keep
+                // carrying values that may not hold on every path.
+                Operator::Loop { .. }
+                | Operator::Else
+                | Operator::End
+                | Operator::Br { .. }
+                | Operator::BrIf { .. }
+                | Operator::BrTable { .. } => {
+                    stack.clear();
+                    for v in locals.values_mut() {
+                        *v = AbstractVal::Unknown;
+                    }
+                    None
+                }
+
+                Operator::I32Const { value } => {
+                    stack.push(AbstractVal::Const(*value as i64));
+                    None
+                }
+                Operator::I64Const { value } => {
+                    stack.push(AbstractVal::Const(*value));
+                    None
+                }
+                Operator::LocalGet { local_index } => {
+                    stack.push(
+                        locals
+                            .get(local_index)
+                            .copied()
+                            .unwrap_or(AbstractVal::Unknown),
+                    );
+                    None
+                }
+                Operator::LocalSet { local_index } => {
+                    let v = pop_val(stack);
+                    locals.insert(*local_index, v);
+                    None
+                }
+                Operator::LocalTee { local_index } => {
+                    let v = pop_val(stack);
+                    locals.insert(*local_index, v);
+                    stack.push(v);
+                    None
+                }
+                Operator::GlobalGet { global_index } => {
+                    stack.push(
+                        const_globals
+                            .get(global_index)
+                            .map(|v| AbstractVal::Const(*v))
+                            .unwrap_or(AbstractVal::Unknown),
+                    );
+                    None
+                }
+                Operator::GlobalSet { .. } => {
+                    pop_val(stack);
+                    None
+                }
+                Operator::I32Add => {
+                    fold_binop(stack, i64::wrapping_add);
+                    None
+                }
+                Operator::I32Sub => {
+                    fold_binop(stack, i64::wrapping_sub);
+                    None
+                }
+                Operator::I32Mul => {
+                    fold_binop(stack, i64::wrapping_mul);
+                    None
+                }
+                Operator::I32And => {
+                    fold_binop(stack, |a, b| a & b);
+                    None
+                }
+                Operator::I32Or => {
+                    fold_binop(stack, |a, b| a | b);
+                    None
+                }
+                Operator::I32Shl => {
+                    fold_binop(stack, |a, b| a.wrapping_shl(b as u32));
+                    None
+                }
+                Operator::Drop => {
+                    pop_val(stack);
+                    None
+                }
+
+                Operator::I32Load { memarg } => {
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(MemoryOperation {
+                        operation_type: MemoryOpType::Load { size_bytes: 4 },
+                        offset: Some(memarg.offset as u32),
+                        size: Some(4),
+                        effective_address: resolve_effective_address(base, memarg.offset),
+                        preceded_by_grow: seen_grow,
+                        function_index: func_global_idx,
+                        instruction_offset: instruction_offset_counter,
+                    })
+                }
+                Operator::I64Load { memarg } => {
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(MemoryOperation {
+                        operation_type: MemoryOpType::Load { size_bytes: 8 },
+                        offset: Some(memarg.offset as u32),
+                        size: Some(8),
+                        effective_address: resolve_effective_address(base, memarg.offset),
+                        preceded_by_grow: seen_grow,
+                        function_index: func_global_idx,
+                        instruction_offset: instruction_offset_counter,
+                    })
+                }
+                Operator::F32Load { memarg } => {
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(MemoryOperation {
+                        operation_type: MemoryOpType::Load { size_bytes: 4 },
+                        offset: Some(memarg.offset as u32),
+                        size: Some(4),
+                        effective_address: resolve_effective_address(base, memarg.offset),
+                        preceded_by_grow: seen_grow,
+                        function_index: func_global_idx,
+                        instruction_offset: instruction_offset_counter,
+                    })
+                }
+                Operator::F64Load { memarg } => {
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(MemoryOperation {
+                        operation_type: MemoryOpType::Load { size_bytes: 8 },
+                        offset: Some(memarg.offset as u32),
+                        size: Some(8),
+                        effective_address: resolve_effective_address(base, memarg.offset),
+                        preceded_by_grow: seen_grow,
+                        function_index: func_global_idx,
+                        instruction_offset: instruction_offset_counter,
+                    })
+                }
                 Operator::I32Load8S { memarg } | Operator::I32Load8U { memarg } => {
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(MemoryOperation {
+                        operation_type: MemoryOpType::Load { size_bytes: 1 },
+                        offset: Some(memarg.offset as u32),
+                        size: Some(1),
+                        effective_address: resolve_effective_address(base, memarg.offset),
+                        preceded_by_grow: seen_grow,
+                        function_index: func_global_idx,
+                        instruction_offset: instruction_offset_counter,
+                    })
+                }
+                Operator::I32Load16S { memarg } | Operator::I32Load16U { memarg } => {
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(MemoryOperation {
+                        operation_type: MemoryOpType::Load { size_bytes: 2 },
+                        offset: Some(memarg.offset as u32),
+                        size: Some(2),
+                        effective_address: resolve_effective_address(base, memarg.offset),
+                        preceded_by_grow: seen_grow,
+                        function_index: func_global_idx,
+                        instruction_offset: instruction_offset_counter,
+                    })
+                }
+                Operator::I64Load8S { memarg } | Operator::I64Load8U { memarg } => {
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(MemoryOperation {
+                        operation_type: MemoryOpType::Load { size_bytes: 1 },
+                        offset: Some(memarg.offset as u32),
+                        size: Some(1),
+                        effective_address: resolve_effective_address(base, memarg.offset),
+                        preceded_by_grow: seen_grow,
+                        function_index: func_global_idx,
+                        instruction_offset: instruction_offset_counter,
+                    })
+                }
+                Operator::I64Load16S { memarg } | Operator::I64Load16U { memarg } => {
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(MemoryOperation {
+                        operation_type: MemoryOpType::Load { size_bytes: 2 },
+                        offset: Some(memarg.offset as u32),
+                        size: Some(2),
+                        effective_address: resolve_effective_address(base, memarg.offset),
+                        preceded_by_grow: seen_grow,
+                        function_index: func_global_idx,
+                        instruction_offset: instruction_offset_counter,
+                    })
+                }
+                Operator::I64Load32S { memarg } | Operator::I64Load32U { memarg } => {
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(MemoryOperation {
+                        operation_type: MemoryOpType::Load { size_bytes: 4 },
+                        offset: Some(memarg.offset as u32),
+                        size: Some(4),
+                        effective_address: resolve_effective_address(base, memarg.offset),
+                        preceded_by_grow: seen_grow,
+                        function_index: func_global_idx,
+                        instruction_offset: instruction_offset_counter,
+                    })
+                }
+
+                Operator::I32Store { memarg } => {
+                    pop_val(stack); // value
+                    let base = pop_val(stack);
+                    Some(MemoryOperation {
+                        operation_type: MemoryOpType::Store { size_bytes: 4 },
+                        offset: Some(memarg.offset as u32),
+                        size: Some(4),
+                        effective_address: resolve_effective_address(base, memarg.offset),
+                        preceded_by_grow: seen_grow,
+                        function_index: func_global_idx,
+                        instruction_offset: instruction_offset_counter,
+                    })
+                }
+                Operator::I64Store { memarg } => {
+                    pop_val(stack);
+                    let base = pop_val(stack);
+                    Some(MemoryOperation {
+                        operation_type: MemoryOpType::Store { size_bytes: 8 },
+                        offset: Some(memarg.offset as u32),
+                        size: Some(8),
+                        effective_address: resolve_effective_address(base, memarg.offset),
+                        preceded_by_grow: seen_grow,
+                        function_index: func_global_idx,
+                        instruction_offset: instruction_offset_counter,
+                    })
+                }
+                Operator::F32Store { memarg } => {
+                    pop_val(stack);
+                    let base = pop_val(stack);
+                    Some(MemoryOperation {
+                        operation_type: MemoryOpType::Store { size_bytes: 4 },
+                        offset: Some(memarg.offset as u32),
+                        size: Some(4),
+                        effective_address: resolve_effective_address(base, memarg.offset),
+                        preceded_by_grow: seen_grow,
+                        function_index: func_global_idx,
+                        instruction_offset: instruction_offset_counter,
+                    })
+                }
+                Operator::F64Store { memarg } => {
+                    pop_val(stack);
+                    let base = pop_val(stack);
+                    Some(MemoryOperation {
+                        operation_type: MemoryOpType::Store { size_bytes: 8 },
+                        offset: Some(memarg.offset as u32),
+                        size: Some(8),
+                        effective_address: resolve_effective_address(base, memarg.offset),
+                        preceded_by_grow: seen_grow,
+                        function_index: func_global_idx,
+                        instruction_offset: instruction_offset_counter,
+                    })
+                }
+                Operator::I32Store8 { memarg } | Operator::I64Store8 { memarg } => {
+                    pop_val(stack);
+                    let base = pop_val(stack);
+                    Some(MemoryOperation {
+                        operation_type: MemoryOpType::Store { size_bytes: 1 },
+                        offset: Some(memarg.offset as u32),
+                        size: Some(1),
+                        effective_address: resolve_effective_address(base, memarg.offset),
+                        preceded_by_grow: seen_grow,
+                        function_index: func_global_idx,
+                        instruction_offset: instruction_offset_counter,
+                    })
+                }
+                Operator::I32Store16 { memarg } | Operator::I64Store16 { memarg } => {
+                    pop_val(stack);
+                    let base = pop_val(stack);
+                    Some(MemoryOperation {
+                        operation_type: MemoryOpType::Store { size_bytes: 2 },
+                        offset: Some(memarg.offset as u32),
+                        size: Some(2),
+                        effective_address: resolve_effective_address(base, memarg.offset),
+                        preceded_by_grow: seen_grow,
+                        function_index: func_global_idx,
+                        instruction_offset: instruction_offset_counter,
+                    })
+                }
+                Operator::I64Store32 { memarg } => {
+                    pop_val(stack);
+                    let base = pop_val(stack);
+                    Some(MemoryOperation {
+                        operation_type: MemoryOpType::Store { size_bytes: 4 },
+                        offset: Some(memarg.offset as u32),
+                        size: Some(4),
+                        effective_address: resolve_effective_address(base, memarg.offset),
+                        preceded_by_grow: seen_grow,
+                        function_index: func_global_idx,
+                        instruction_offset: instruction_offset_counter,
+                    })
+                }
+
+                Operator::MemorySize { .. } => {
+                    stack.push(AbstractVal::Unknown);
+                    Some(MemoryOperation {
+                        operation_type: MemoryOpType::MemorySize,
+                        offset: None,
+                        size: None,
+                        effective_address: None,
+                        preceded_by_grow: seen_grow,
+                        function_index: func_global_idx,
+                        instruction_offset: instruction_offset_counter,
+                    })
+                }
+                Operator::MemoryGrow { .. } => {
+                    let delta = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    let op = MemoryOperation {
+                        operation_type: MemoryOpType::MemoryGrow,
+                        offset: None,
+                        size: delta.as_const().map(|v| v as u32),
+                        effective_address: None,
+                        preceded_by_grow: seen_grow,
+                        function_index: func_global_idx,
+                        instruction_offset: instruction_offset_counter,
+                    };
+                    self.seen_grow = true;
+                    Some(op)
+                }
+                Operator::MemoryCopy { .. } => {
+                    let len = pop_val(stack);
+                    pop_val(stack); // src
+                    pop_val(stack); // dst
                     Some(MemoryOperation {
-                        operation_type: MemoryOpType::Load { size_bytes: 1 },
-                        offset: Some(memarg.offset as u32),
-                        size: Some(1),
+                        operation_type: MemoryOpType::MemoryCopy,
+                        offset: None,
+                        size: len.as_const().map(|v| v as u32),
+                        effective_address: None,
+                        preceded_by_grow: seen_grow,
                         function_index: func_global_idx,
                         instruction_offset: instruction_offset_counter,
                     })
                 }
-                Operator::I32Load16S { memarg } | Operator::I32Load16U { memarg } => {
+                Operator::MemoryFill { .. } => {
+                    let len = pop_val(stack);
+                    pop_val(stack); // fill value
+                    pop_val(stack); // dst
                     Some(MemoryOperation {
-                        operation_type: MemoryOpType::Load { size_bytes: 2 },
-                        offset: Some(memarg.offset as u32),
-                        size: Some(2),
+                        operation_type: MemoryOpType::MemoryFill,
+                        offset: None,
+                        size: len.as_const().map(|v| v as u32),
+                        effective_address: None,
+                        preceded_by_grow: seen_grow,
                         function_index: func_global_idx,
                         instruction_offset: instruction_offset_counter,
                     })
                 }
-                Operator::I64Load8S { memarg } | Operator::I64Load8U { memarg } => {
+                Operator::MemoryInit { data_index, .. } => {
+                    let len = pop_val(stack);
+                    pop_val(stack); // src
+                    pop_val(stack); // dst
                     Some(MemoryOperation {
-                        operation_type: MemoryOpType::Load { size_bytes: 1 },
+                        operation_type: MemoryOpType::MemoryInit { data_index: *data_index },
+                        offset: None,
+                        size: len.as_const().map(|v| v as u32),
+                        effective_address: None,
+                        preceded_by_grow: seen_grow,
+                        function_index: func_global_idx,
+                        instruction_offset: instruction_offset_counter,
+                    })
+                }
+                // SIMD: plain/widening/splat/zero vector loads only consume the
+                // address; lane loads also consume (and replace a lane of) an
+                // existing vector operand underneath it.
+                Operator::V128Load { memarg }
+                | Operator::V128Load8x8S { memarg }
+                | Operator::V128Load8x8U { memarg }
+                | Operator::V128Load16x4S { memarg }
+                | Operator::V128Load16x4U { memarg }
+                | Operator::V128Load32x2S { memarg }
+                | Operator::V128Load32x2U { memarg }
+                | Operator::V128Load8Splat { memarg }
+                | Operator::V128Load16Splat { memarg }
+                | Operator::V128Load32Splat { memarg }
+                | Operator::V128Load64Splat { memarg }
+                | Operator::V128Load32Zero { memarg }
+                | Operator::V128Load64Zero { memarg } => {
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(MemoryOperation {
+                        operation_type: MemoryOpType::Vector { size_bytes: 16 },
                         offset: Some(memarg.offset as u32),
-                        size: Some(1),
+                        size: Some(16),
+                        effective_address: resolve_effective_address(base, memarg.offset),
+                        preceded_by_grow: seen_grow,
                         function_index: func_global_idx,
                         instruction_offset: instruction_offset_counter,
                     })
                 }
-                Operator::I64Load16S { memarg } | Operator::I64Load16U { memarg } => {
+                Operator::V128Load8Lane { memarg, .. }
+                | Operator::V128Load16Lane { memarg, .. }
+                | Operator::V128Load32Lane { memarg, .. }
+                | Operator::V128Load64Lane { memarg, .. } => {
+                    pop_val(stack); // existing vector being partially replaced
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
                     Some(MemoryOperation {
-                        operation_type: MemoryOpType::Load { size_bytes: 2 },
+                        operation_type: MemoryOpType::Vector { size_bytes: 16 },
                         offset: Some(memarg.offset as u32),
-                        size: Some(2),
+                        size: Some(16),
+                        effective_address: resolve_effective_address(base, memarg.offset),
+                        preceded_by_grow: seen_grow,
                         function_index: func_global_idx,
                         instruction_offset: instruction_offset_counter,
                     })
                 }
-                Operator::I64Load32S { memarg } | Operator::I64Load32U { memarg } => {
+                Operator::V128Store { memarg }
+                | Operator::V128Store8Lane { memarg, .. }
+                | Operator::V128Store16Lane { memarg, .. }
+                | Operator::V128Store32Lane { memarg, .. }
+                | Operator::V128Store64Lane { memarg, .. } => {
+                    pop_val(stack); // vector value
+                    let base = pop_val(stack);
                     Some(MemoryOperation {
-                        operation_type: MemoryOpType::Load { size_bytes: 4 },
+                        operation_type: MemoryOpType::Vector { size_bytes: 16 },
                         offset: Some(memarg.offset as u32),
-                        size: Some(4),
+                        size: Some(16),
+                        effective_address: resolve_effective_address(base, memarg.offset),
+                        preceded_by_grow: seen_grow,
                         function_index: func_global_idx,
                         instruction_offset: instruction_offset_counter,
                     })
                 }
 
-                Operator::I32Store { memarg } => Some(MemoryOperation {
-                    operation_type: MemoryOpType::Store { size_bytes: 4 },
-                    offset: Some(memarg.offset as u32),
-                    size: Some(4),
-                    function_index: func_global_idx,
-                    instruction_offset: instruction_offset_counter,
-                }),
-                Operator::I64Store { memarg } => Some(MemoryOperation {
-                    operation_type: MemoryOpType::Store { size_bytes: 8 },
-                    offset: Some(memarg.offset as u32),
-                    size: Some(8),
-                    function_index: func_global_idx,
-                    instruction_offset: instruction_offset_counter,
-                }),
-                Operator::F32Store { memarg } => Some(MemoryOperation {
-                    operation_type: MemoryOpType::Store { size_bytes: 4 },
-                    offset: Some(memarg.offset as u32),
-                    size: Some(4),
-                    function_index: func_global_idx,
-                    instruction_offset: instruction_offset_counter,
-                }),
-                Operator::F64Store { memarg } => Some(MemoryOperation {
-                    operation_type: MemoryOpType::Store { size_bytes: 8 },
-                    offset: Some(memarg.offset as u32),
-                    size: Some(8),
-                    function_index: func_global_idx,
-                    instruction_offset: instruction_offset_counter,
-                }),
-                Operator::I32Store8 { memarg } | Operator::I64Store8 { memarg } => {
+                // Threads proposal: atomic loads only consume the address.
+                Operator::I32AtomicLoad { memarg } | Operator::I32AtomicLoad8U { memarg } | Operator::I32AtomicLoad16U { memarg } => {
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(atomic_op(func_global_idx, instruction_offset_counter, seen_grow, memarg.offset, base, 4, AtomicKind::Load))
+                }
+                Operator::I64AtomicLoad { memarg }
+                | Operator::I64AtomicLoad8U { memarg }
+                | Operator::I64AtomicLoad16U { memarg }
+                | Operator::I64AtomicLoad32U { memarg } => {
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(atomic_op(func_global_idx, instruction_offset_counter, seen_grow, memarg.offset, base, 8, AtomicKind::Load))
+                }
+
+                // Atomic stores consume a value then the address, like their
+                // non-atomic counterparts.
+                Operator::I32AtomicStore { memarg } | Operator::I32AtomicStore8 { memarg } | Operator::I32AtomicStore16 { memarg } => {
+                    pop_val(stack); // value
+                    let base = pop_val(stack);
+                    Some(atomic_op(func_global_idx, instruction_offset_counter, seen_grow, memarg.offset, base, 4, AtomicKind::Store))
+                }
+                Operator::I64AtomicStore { memarg }
+                | Operator::I64AtomicStore8 { memarg }
+                | Operator::I64AtomicStore16 { memarg }
+                | Operator::I64AtomicStore32 { memarg } => {
+                    pop_val(stack); // value
+                    let base = pop_val(stack);
+                    Some(atomic_op(func_global_idx, instruction_offset_counter, seen_grow, memarg.offset, base, 8, AtomicKind::Store))
+                }
+
+                // Atomic read-modify-write ops (add/sub/and/or/xor/xchg) consume
+                // the operand then the address and push the old value back.
+                Operator::I32AtomicRmwAdd { memarg }
+                | Operator::I32AtomicRmwSub { memarg }
+                | Operator::I32AtomicRmwAnd { memarg }
+                | Operator::I32AtomicRmwOr { memarg }
+                | Operator::I32AtomicRmwXor { memarg }
+                | Operator::I32AtomicRmwXchg { memarg } => {
+                    pop_val(stack); // operand
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(atomic_op(func_global_idx, instruction_offset_counter, seen_grow, memarg.offset, base, 4, AtomicKind::Rmw(atomic_rmw_op_kind(op))))
+                }
+                Operator::I32AtomicRmw8AddU { memarg }
+                | Operator::I32AtomicRmw8SubU { memarg }
+                | Operator::I32AtomicRmw8AndU { memarg }
+                | Operator::I32AtomicRmw8OrU { memarg }
+                | Operator::I32AtomicRmw8XorU { memarg }
+                | Operator::I32AtomicRmw8XchgU { memarg } => {
+                    pop_val(stack);
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(atomic_op(func_global_idx, instruction_offset_counter, seen_grow, memarg.offset, base, 1, AtomicKind::Rmw(atomic_rmw_op_kind(op))))
+                }
+                Operator::I32AtomicRmw16AddU { memarg }
+                | Operator::I32AtomicRmw16SubU { memarg }
+                | Operator::I32AtomicRmw16AndU { memarg }
+                | Operator::I32AtomicRmw16OrU { memarg }
+                | Operator::I32AtomicRmw16XorU { memarg }
+                | Operator::I32AtomicRmw16XchgU { memarg } => {
+                    pop_val(stack);
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(atomic_op(func_global_idx, instruction_offset_counter, seen_grow, memarg.offset, base, 2, AtomicKind::Rmw(atomic_rmw_op_kind(op))))
+                }
+                Operator::I64AtomicRmwAdd { memarg }
+                | Operator::I64AtomicRmwSub { memarg }
+                | Operator::I64AtomicRmwAnd { memarg }
+                | Operator::I64AtomicRmwOr { memarg }
+                | Operator::I64AtomicRmwXor { memarg }
+                | Operator::I64AtomicRmwXchg { memarg } => {
+                    pop_val(stack);
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(atomic_op(func_global_idx, instruction_offset_counter, seen_grow, memarg.offset, base, 8, AtomicKind::Rmw(atomic_rmw_op_kind(op))))
+                }
+                Operator::I64AtomicRmw8AddU { memarg }
+                | Operator::I64AtomicRmw8SubU { memarg }
+                | Operator::I64AtomicRmw8AndU { memarg }
+                | Operator::I64AtomicRmw8OrU { memarg }
+                | Operator::I64AtomicRmw8XorU { memarg }
+                | Operator::I64AtomicRmw8XchgU { memarg } => {
+                    pop_val(stack);
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(atomic_op(func_global_idx, instruction_offset_counter, seen_grow, memarg.offset, base, 1, AtomicKind::Rmw(atomic_rmw_op_kind(op))))
+                }
+                Operator::I64AtomicRmw16AddU { memarg }
+                | Operator::I64AtomicRmw16SubU { memarg }
+                | Operator::I64AtomicRmw16AndU { memarg }
+                | Operator::I64AtomicRmw16OrU { memarg }
+                | Operator::I64AtomicRmw16XorU { memarg }
+                | Operator::I64AtomicRmw16XchgU { memarg } => {
+                    pop_val(stack);
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(atomic_op(func_global_idx, instruction_offset_counter, seen_grow, memarg.offset, base, 2, AtomicKind::Rmw(atomic_rmw_op_kind(op))))
+                }
+                Operator::I64AtomicRmw32AddU { memarg }
+                | Operator::I64AtomicRmw32SubU { memarg }
+                | Operator::I64AtomicRmw32AndU { memarg }
+                | Operator::I64AtomicRmw32OrU { memarg }
+                | Operator::I64AtomicRmw32XorU { memarg }
+                | Operator::I64AtomicRmw32XchgU { memarg } => {
+                    pop_val(stack);
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(atomic_op(func_global_idx, instruction_offset_counter, seen_grow, memarg.offset, base, 4, AtomicKind::Rmw(atomic_rmw_op_kind(op))))
+                }
+
+                // Compare-and-exchange consumes both the expected and
+                // replacement operands, plus the address, and pushes the old value.
+                Operator::I32AtomicRmwCmpxchg { memarg } => {
+                    pop_val(stack); // replacement
+                    pop_val(stack); // expected
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(atomic_op(func_global_idx, instruction_offset_counter, seen_grow, memarg.offset, base, 4, AtomicKind::Cmpxchg))
+                }
+                Operator::I32AtomicRmw8CmpxchgU { memarg } => {
+                    pop_val(stack);
+                    pop_val(stack);
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(atomic_op(func_global_idx, instruction_offset_counter, seen_grow, memarg.offset, base, 1, AtomicKind::Cmpxchg))
+                }
+                Operator::I32AtomicRmw16CmpxchgU { memarg } => {
+                    pop_val(stack);
+                    pop_val(stack);
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(atomic_op(func_global_idx, instruction_offset_counter, seen_grow, memarg.offset, base, 2, AtomicKind::Cmpxchg))
+                }
+                Operator::I64AtomicRmwCmpxchg { memarg } => {
+                    pop_val(stack);
+                    pop_val(stack);
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(atomic_op(func_global_idx, instruction_offset_counter, seen_grow, memarg.offset, base, 8, AtomicKind::Cmpxchg))
+                }
+                Operator::I64AtomicRmw8CmpxchgU { memarg } => {
+                    pop_val(stack);
+                    pop_val(stack);
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(atomic_op(func_global_idx, instruction_offset_counter, seen_grow, memarg.offset, base, 1, AtomicKind::Cmpxchg))
+                }
+                Operator::I64AtomicRmw16CmpxchgU { memarg } => {
+                    pop_val(stack);
+                    pop_val(stack);
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(atomic_op(func_global_idx, instruction_offset_counter, seen_grow, memarg.offset, base, 2, AtomicKind::Cmpxchg))
+                }
+                Operator::I64AtomicRmw32CmpxchgU { memarg } => {
+                    pop_val(stack);
+                    pop_val(stack);
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
+                    Some(atomic_op(func_global_idx, instruction_offset_counter, seen_grow, memarg.offset, base, 4, AtomicKind::Cmpxchg))
+                }
+
+                // `memory.atomic.notify`/`memory.atomic.wait32/64`: both take an
+                // address plus further operands (count, or expected+timeout) and
+                // push a result, but neither resolves to a byte-size access the
+                // way a load/store does.
+                Operator::MemoryAtomicNotify { memarg } => {
+                    pop_val(stack); // count
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
                     Some(MemoryOperation {
-                        operation_type: MemoryOpType::Store { size_bytes: 1 },
+                        operation_type: MemoryOpType::AtomicNotify,
                         offset: Some(memarg.offset as u32),
-                        size: Some(1),
+                        size: None,
+                        effective_address: resolve_effective_address(base, memarg.offset),
+                        preceded_by_grow: seen_grow,
                         function_index: func_global_idx,
                         instruction_offset: instruction_offset_counter,
                     })
                 }
-                Operator::I32Store16 { memarg } | Operator::I64Store16 { memarg } => {
+                Operator::MemoryAtomicWait32 { memarg } | Operator::MemoryAtomicWait64 { memarg } => {
+                    pop_val(stack); // timeout
+                    pop_val(stack); // expected
+                    let base = pop_val(stack);
+                    stack.push(AbstractVal::Unknown);
                     Some(MemoryOperation {
-                        operation_type: MemoryOpType::Store { size_bytes: 2 },
+                        operation_type: MemoryOpType::AtomicWait,
                         offset: Some(memarg.offset as u32),
-                        size: Some(2),
+                        size: None,
+                        effective_address: resolve_effective_address(base, memarg.offset),
+                        preceded_by_grow: seen_grow,
                         function_index: func_global_idx,
                         instruction_offset: instruction_offset_counter,
                     })
                 }
-                Operator::I64Store32 { memarg } => Some(MemoryOperation {
-                    operation_type: MemoryOpType::Store { size_bytes: 4 },
-                    offset: Some(memarg.offset as u32),
-                    size: Some(4),
-                    function_index: func_global_idx,
-                    instruction_offset: instruction_offset_counter,
-                }),
-
-                Operator::MemorySize { .. } => Some(MemoryOperation {
-                    operation_type: MemoryOpType::MemorySize,
-                    offset: None,
-                    size: None,
-                    function_index: func_global_idx,
-                    instruction_offset: instruction_offset_counter,
-                }),
-                Operator::MemoryGrow { .. } => Some(MemoryOperation {
-                    operation_type: MemoryOpType::MemoryGrow,
-                    offset: None,
-                    size: None,
-                    function_index: func_global_idx,
-                    instruction_offset: instruction_offset_counter,
-                }),
-                Operator::MemoryCopy { .. } => Some(MemoryOperation {
-                    operation_type: MemoryOpType::MemoryCopy,
-                    offset: None,
-                    size: None,
-                    function_index: func_global_idx,
-                    instruction_offset: instruction_offset_counter,
-                }),
-                Operator::MemoryFill { .. } => Some(MemoryOperation {
-                    operation_type: MemoryOpType::MemoryFill,
-                    offset: None,
-                    size: None,
-                    function_index: func_global_idx,
-                    instruction_offset: instruction_offset_counter,
-                }),
+
                 _ => None,
             };
-            if let Some(op) = mem_op {
-                operations_for_func.push(op);
-            }
-        }
-        if !operations_for_func.is_empty() {
-            self.memory_operations
-                .insert(func_global_idx, operations_for_func);
+        if let Some(op) = mem_op {
+            self.operations.push(op);
         }
-        Ok(())
     }
+}
 
+impl<'a> MemoryAnalyzer<'a> {
     fn analyze_allocation_patterns(&mut self) {
         let mut growth_functions = HashSet::new();
         let mut bulk_op_functions = HashSet::new();
         let mut frequent_small_access_funcs = HashSet::new();
+        let mut grow_page_deltas: Vec<u32> = Vec::new();
+        let mut bulk_byte_lengths: Vec<u32> = Vec::new();
 
         for (&func_idx, operations) in &self.memory_operations {
             let mut small_access_count = 0;
@@ -427,9 +2037,17 @@ impl<'a> MemoryAnalyzer<'a> {
                 match op.operation_type {
                     MemoryOpType::MemoryGrow => {
                         growth_functions.insert(func_idx);
+                        if let Some(delta) = op.size {
+                            grow_page_deltas.push(delta);
+                        }
                     }
-                    MemoryOpType::MemoryCopy | MemoryOpType::MemoryFill => {
+                    MemoryOpType::MemoryCopy
+                    | MemoryOpType::MemoryFill
+                    | MemoryOpType::MemoryInit { .. } => {
                         bulk_op_functions.insert(func_idx);
+                        if let Some(len) = op.size {
+                            bulk_byte_lengths.push(len);
+                        }
                     }
                     MemoryOpType::Load { size_bytes } | MemoryOpType::Store { size_bytes } => {
                         if size_bytes <= 8 {
@@ -445,11 +2063,19 @@ impl<'a> MemoryAnalyzer<'a> {
             }
         }
 
+        // Resolved via the abstract interpreter in `analyze_function_body_for_memory_ops`
+        // when the `memory.grow`/`memory.copy`/`memory.fill` operand was a statically
+        // known constant; `average()` returns `None` (-> 0) if none of them were.
+        let average_grow_bytes = average(&grow_page_deltas)
+            .map(|pages| pages.saturating_mul(WASM_PAGE_SIZE_BYTES))
+            .unwrap_or(0);
+        let average_bulk_bytes = average(&bulk_byte_lengths).unwrap_or(0);
+
         if !growth_functions.is_empty() {
             self.allocation_patterns.push(AllocationPattern {
                 pattern_type: AllocationType::DynamicGrowth,
                 frequency: growth_functions.len() as u32,
-                average_size: 0, // Hard to determine statically, could be improved with taint analysis
+                average_size: average_grow_bytes,
                 functions_involved: growth_functions.into_iter().collect(),
                 risk_assessment: MemoryRisk {
                     risk_level: RiskLevel::Medium,
@@ -465,7 +2091,7 @@ impl<'a> MemoryAnalyzer<'a> {
             self.allocation_patterns.push(AllocationPattern {
                 pattern_type: AllocationType::BulkOperations,
                 frequency: bulk_op_functions.len() as u32,
-                average_size: 0, // Hard to determine statically
+                average_size: average_bulk_bytes,
                 functions_involved: bulk_op_functions.into_iter().collect(),
                 risk_assessment: MemoryRisk {
                     risk_level: RiskLevel::Low,
@@ -519,7 +2145,7 @@ impl<'a> MemoryAnalyzer<'a> {
         }
     }
 
-    fn analyze_memory_layout(&self) -> MemoryLayout {
+    fn analyze_memory_layout(&self, call_graph: &CallGraph) -> MemoryLayout {
         let (total_initial_size, total_max_size) =
             if let Some(ref memory_info) = self.module_info.memory {
                 (
@@ -546,39 +2172,188 @@ impl<'a> MemoryAnalyzer<'a> {
             total_initial_size,
             total_max_size,
             data_segments,
-            stack_estimation: self.analyze_stack_usage(),
+            stack_estimation: self.analyze_stack_usage(call_graph),
             heap_estimation: self.analyze_heap_usage(),
+            data_segment_report: self.build_data_segment_report(),
         }
     }
 
-    fn analyze_stack_usage(&self) -> StackAnalysis {
-        // Simplified: estimate max locals size for any single function
-        let estimated_max_depth = self
+    /// Raw bytes of each data segment, in declaration order (matching
+    /// `module_info.data_segments`'s indexing), re-parsed from `wasm_bytes`
+    /// since `DataSegment` itself only carries metadata. Parse failures yield
+    /// an empty vec rather than propagating, since the zero-fill-gap check
+    /// this feeds is an optional enhancement, not a correctness-critical one.
+    fn data_segment_raw_bytes(&self) -> Vec<Vec<u8>> {
+        use wasmparser::{Parser, Payload};
+
+        let mut bytes_by_segment = Vec::new();
+        let parser = Parser::new(0);
+        for payload_result in parser.parse_all(self.wasm_bytes) {
+            let Ok(payload) = payload_result else {
+                return Vec::new();
+            };
+            if let Payload::DataSection(reader) = payload {
+                for data_result in reader {
+                    let Ok(data) = data_result else {
+                        return Vec::new();
+                    };
+                    bytes_by_segment.push(data.data.to_vec());
+                }
+            }
+        }
+        bytes_by_segment
+    }
+
+    /// The length of the run of `0x00` bytes at the very end of `data`, i.e.
+    /// how much of it duplicates a linear memory's already-zeroed default.
+    fn trailing_zero_run(data: &[u8]) -> u32 {
+        data.iter().rev().take_while(|&&b| b == 0).count() as u32
+    }
+
+    fn build_data_segment_report(&self) -> DataSegmentReport {
+        const LARGE_ZERO_FILL_THRESHOLD: u32 = 256;
+
+        let raw_bytes = self.data_segment_raw_bytes();
+        let memory_initial_bytes = self
             .module_info
-            .functions
+            .memory
+            .as_ref()
+            .map(|m| m.initial * WASM_PAGE_SIZE_BYTES);
+
+        let segments: Vec<DataSegmentInventoryEntry> = self
+            .module_info
+            .data_segments
             .iter()
-            .map(|f| {
-                f.locals
-                    .iter()
-                    .map(|l| {
-                        let type_size = match l.value_type.as_str() {
-                            "i32" | "f32" => 4,
-                            "i64" | "f64" => 8,
-                            _ => 4, // Default for other types like v128 (though it's 16) or refs
-                        };
-                        l.count * type_size
-                    })
-                    .sum::<u32>()
+            .map(|ds| {
+                let (start, end) = if ds.is_passive {
+                    (None, None)
+                } else {
+                    let offset = ds.offset.as_u32().unwrap_or(0);
+                    (Some(offset), Some(offset.saturating_add(ds.size)))
+                };
+                DataSegmentInventoryEntry {
+                    index: ds.index,
+                    memory_index: ds.memory_index,
+                    is_active: !ds.is_passive,
+                    start,
+                    end,
+                    size: ds.size,
+                }
             })
-            .max()
-            .unwrap_or(0);
+            .collect();
+
+        let mut overlapping_segments = Vec::new();
+        for (i, a) in segments.iter().enumerate() {
+            let (Some(a_start), Some(a_end)) = (a.start, a.end) else {
+                continue;
+            };
+            for b in &segments[i + 1..] {
+                if b.memory_index != a.memory_index {
+                    continue;
+                }
+                let (Some(b_start), Some(b_end)) = (b.start, b.end) else {
+                    continue;
+                };
+                if a_start < b_end && b_start < a_end {
+                    overlapping_segments.push((a.index, b.index));
+                }
+            }
+        }
+
+        let out_of_bounds_segments: Vec<u32> = segments
+            .iter()
+            .filter_map(|s| {
+                let end = s.end?;
+                let limit = memory_initial_bytes?;
+                (end > limit).then_some(s.index)
+            })
+            .collect();
+
+        let zero_fill_gaps: Vec<ZeroFillGap> = raw_bytes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, data)| {
+                let trailing_zero_bytes = Self::trailing_zero_run(data);
+                (trailing_zero_bytes > LARGE_ZERO_FILL_THRESHOLD).then_some(ZeroFillGap {
+                    segment_index: i as u32,
+                    trailing_zero_bytes,
+                })
+            })
+            .collect();
+
+        DataSegmentReport {
+            segments,
+            overlapping_segments,
+            out_of_bounds_segments,
+            zero_fill_gaps,
+        }
+    }
+
+    /// Per-function frame estimate: locals size (as before) plus a coarse
+    /// operand-stack estimate of 8 bytes (one typical value slot) per level of
+    /// `block`/`loop`/`if` nesting, since we don't track the operand stack's
+    /// true depth outside of `analyze_function_body_for_memory_ops`.
+    fn frame_size_estimate(&self, func: &Function) -> u32 {
+        let locals_size: u32 = func
+            .locals
+            .iter()
+            .map(|l| {
+                let type_size = match l.value_type.as_str() {
+                    "i32" | "f32" => 4,
+                    "i64" | "f64" => 8,
+                    _ => 4, // Default for other types like v128 (though it's 16) or refs
+                };
+                l.count * type_size
+            })
+            .sum();
+        locals_size + func.max_block_depth * 8
+    }
+
+    fn analyze_stack_usage(&self, call_graph: &CallGraph) -> StackAnalysis {
+        let frame_size: HashMap<u32, u32> = self
+            .module_info
+            .functions
+            .iter()
+            .map(|f| (f.index, self.frame_size_estimate(f)))
+            .collect();
+
+        let mut adjacency: HashMap<u32, Vec<u32>> = HashMap::new();
+        for edge in &call_graph.edges {
+            adjacency.entry(edge.from).or_default().push(edge.to);
+        }
+
+        // `call_graph.recursive_components` is the same Tarjan SCC computation
+        // (already filtered to actual cycles: size > 1, or a single function
+        // with a self-loop) computed once per analysis in `CallGraphBuilder`,
+        // so there's no need for a second, independent SCC pass here.
+        let recursive_functions: HashSet<u32> = call_graph
+            .recursive_components
+            .iter()
+            .flatten()
+            .copied()
+            .collect();
+        let recursive_risk = !recursive_functions.is_empty();
+
+        // Excluding every function involved in a cycle leaves a strictly acyclic
+        // subgraph, so the longest-path search below never needs a fixpoint or
+        // a separate condensation step.
+        let mut longest_chains =
+            longest_call_chains(&call_graph.entry_points, &adjacency, &recursive_functions, &frame_size);
+        longest_chains.sort_by(|a, b| b.0.cmp(&a.0));
+
+        let estimated_max_depth = longest_chains.first().map(|(depth, _)| *depth).unwrap_or(0);
+
+        const TOP_CHAINS_TO_REPORT: usize = 5;
+        let deep_call_chains = longest_chains
+            .into_iter()
+            .take(TOP_CHAINS_TO_REPORT)
+            .map(|(_, chain)| chain)
+            .collect();
 
-        // Recursive risk needs call graph analysis, which is separate.
-        // For now, set to false or use a simple heuristic.
         StackAnalysis {
-            estimated_max_depth,      // This is locals, not true stack depth
-            recursive_risk: false,    // Placeholder
-            deep_call_chains: vec![], // Placeholder
+            estimated_max_depth,
+            recursive_risk,
+            deep_call_chains,
         }
     }
 
@@ -611,10 +2386,18 @@ impl<'a> MemoryAnalyzer<'a> {
             0
         };
 
+        let has_frequent_small_allocations = self
+            .allocation_patterns
+            .iter()
+            .any(|p| matches!(p.pattern_type, AllocationType::FrequentSmallAllocations));
+        let allocator_kind =
+            fingerprint_allocator(self.module_info, has_frequent_small_allocations);
+
         HeapAnalysis {
             uses_dynamic_allocation,
             allocation_functions,
             estimated_heap_usage,
+            allocator_kind,
         }
     }
 
@@ -624,6 +2407,8 @@ impl<'a> MemoryAnalyzer<'a> {
         let mut store_ops = 0;
         let mut bulk_ops = 0;
         let mut growth_ops = 0;
+        let mut vector_ops = 0;
+        let mut atomic_ops = 0;
 
         for ops_in_func in self.memory_operations.values() {
             total_ops += ops_in_func.len() as u32;
@@ -631,9 +2416,18 @@ impl<'a> MemoryAnalyzer<'a> {
                 match op.operation_type {
                     MemoryOpType::Load { .. } => load_ops += 1,
                     MemoryOpType::Store { .. } => store_ops += 1,
-                    MemoryOpType::MemoryCopy | MemoryOpType::MemoryFill => bulk_ops += 1,
+                    MemoryOpType::MemoryCopy
+                    | MemoryOpType::MemoryFill
+                    | MemoryOpType::MemoryInit { .. } => bulk_ops += 1,
                     MemoryOpType::MemoryGrow => growth_ops += 1,
                     MemoryOpType::MemorySize => {} // Not counted as modifying or heavy access
+                    MemoryOpType::Vector { .. } => vector_ops += 1,
+                    MemoryOpType::AtomicLoad { .. }
+                    | MemoryOpType::AtomicStore { .. }
+                    | MemoryOpType::AtomicRmw { .. }
+                    | MemoryOpType::AtomicCmpxchg { .. }
+                    | MemoryOpType::AtomicWait
+                    | MemoryOpType::AtomicNotify => atomic_ops += 1,
                 }
             }
         }
@@ -644,6 +2438,8 @@ impl<'a> MemoryAnalyzer<'a> {
             store_operations: store_ops,
             bulk_operations: bulk_ops,
             memory_growth_operations: growth_ops,
+            vector_operations: vector_ops,
+            atomic_operations: atomic_ops,
             operation_density: if defined_func_count > 0 {
                 total_ops as f64 / defined_func_count as f64
             } else {
@@ -663,6 +2459,7 @@ impl<'a> MemoryAnalyzer<'a> {
             let mut pressure_score = 0.0;
             let mut has_grow = false;
             let mut bulk_op_count = 0;
+            let mut atomic_contention_count = 0;
 
             for op in operations {
                 match op.operation_type {
@@ -673,11 +2470,35 @@ impl<'a> MemoryAnalyzer<'a> {
                         pressure_score += 100.0;
                         has_grow = true;
                     }
-                    MemoryOpType::MemoryCopy | MemoryOpType::MemoryFill => {
+                    MemoryOpType::MemoryCopy
+                    | MemoryOpType::MemoryFill
+                    | MemoryOpType::MemoryInit { .. } => {
                         pressure_score += 50.0;
                         bulk_op_count += 1;
                     }
-                    _ => {}
+                    MemoryOpType::Vector { size_bytes } => pressure_score += size_bytes as f64,
+                    // Atomic ops serialize across threads, so they cost more than a
+                    // plain load/store of the same size: a load/store is weighted 1x,
+                    // a plain atomic load/store 1.5x, an RMW 3x (it's a read and a
+                    // write that can't be interleaved with another agent's access),
+                    // and a cmpxchg 4x (the heaviest shape: two operands plus the
+                    // address, still serialized).
+                    MemoryOpType::AtomicLoad { size_bytes } | MemoryOpType::AtomicStore { size_bytes } => {
+                        pressure_score += size_bytes as f64 * 1.5
+                    }
+                    MemoryOpType::AtomicRmw { size_bytes, .. } => {
+                        pressure_score += size_bytes as f64 * 3.0;
+                        atomic_contention_count += 1;
+                    }
+                    MemoryOpType::AtomicCmpxchg { size_bytes } => {
+                        pressure_score += size_bytes as f64 * 4.0;
+                        atomic_contention_count += 1;
+                    }
+                    MemoryOpType::AtomicWait | MemoryOpType::AtomicNotify => {
+                        pressure_score += 20.0;
+                        atomic_contention_count += 1;
+                    }
+                    MemoryOpType::MemorySize => {}
                 }
             }
             // Normalize pressure or use threshold
@@ -701,6 +2522,8 @@ impl<'a> MemoryAnalyzer<'a> {
 
                 let hotspot_type = if has_grow {
                     HotspotType::MemoryGrowth
+                } else if atomic_contention_count > operation_count / 4 {
+                    HotspotType::AtomicContention
                 } else if bulk_op_count > operation_count / 4 {
                     HotspotType::LargeDataMovement
                 } else {
@@ -724,7 +2547,59 @@ impl<'a> MemoryAnalyzer<'a> {
         hotspots
     }
 
-    fn identify_optimizations(&self) -> Vec<MemoryOptimization> {
+    /// Bytes belonging to data segments that no reachable function ever touches:
+    /// active segments whose `[offset, offset+size)` interval never overlaps a
+    /// resolved load/store address, and passive segments no reachable
+    /// `memory.init` ever references.
+    fn dead_data_segment_bytes(&self, call_graph: &CallGraph) -> u32 {
+        let unreachable: HashSet<u32> = call_graph.unreachable_functions.iter().copied().collect();
+
+        let mut accessed_ranges: Vec<(u32, u32)> = Vec::new();
+        let mut initialized_segments: HashSet<u32> = HashSet::new();
+        for (func_idx, operations) in &self.memory_operations {
+            if unreachable.contains(func_idx) {
+                continue;
+            }
+            for op in operations {
+                match op.operation_type {
+                    MemoryOpType::Load { size_bytes } | MemoryOpType::Store { size_bytes } => {
+                        if let Some(addr) = op.effective_address {
+                            accessed_ranges.push((addr, addr.saturating_add(size_bytes)));
+                        }
+                    }
+                    MemoryOpType::MemoryInit { data_index } => {
+                        initialized_segments.insert(data_index);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        self.module_info
+            .data_segments
+            .iter()
+            .filter(|seg| {
+                if seg.is_passive {
+                    !initialized_segments.contains(&seg.index)
+                } else {
+                    let seg_start = seg.offset.as_u32().unwrap_or(0);
+                    let seg_end = seg_start.saturating_add(seg.size);
+                    !accessed_ranges
+                        .iter()
+                        .any(|&(start, end)| start < seg_end && seg_start < end)
+                }
+            })
+            .map(|seg| seg.size)
+            .sum()
+    }
+
+    fn identify_optimizations(
+        &self,
+        call_graph: &CallGraph,
+        pooling_recommendation: &PoolingRecommendation,
+        heap: &HeapAnalysis,
+        data_segment_report: &DataSegmentReport,
+    ) -> Vec<MemoryOptimization> {
         let mut opts = Vec::new();
         if self
             .module_info
@@ -742,70 +2617,208 @@ impl<'a> MemoryAnalyzer<'a> {
             });
         }
 
-        let total_data_size: u32 = self.module_info.data_segments.iter().map(|d| d.size).sum();
-        if total_data_size > 100 * 1024 {
-            // Over 100KB in static data
+        const LARGE_SEGMENT_THRESHOLD: u32 = 100 * 1024; // 100KB
+        let large_segments: Vec<u32> = data_segment_report
+            .segments
+            .iter()
+            .filter(|s| s.size > LARGE_SEGMENT_THRESHOLD)
+            .map(|s| s.index)
+            .collect();
+        if !large_segments.is_empty() {
             opts.push(MemoryOptimization {
                 optimization_type: OptimizationType::ReduceMemoryFootprint,
-                description:
-                    "Large total size of data segments. Consider if all data is needed at startup."
-                        .to_string(),
+                description: format!(
+                    "Data segment(s) {:?} are individually over 100KB. Consider if all of their data is needed at startup.",
+                    large_segments
+                ),
                 estimated_savings: Some(
-                    "Potential size reduction by lazy loading or compressing data.".to_string(),
+                    "Potential size reduction by lazy loading or compressing the oversized segments.".to_string(),
                 ),
                 implementation_difficulty: DifficultyLevel::Medium,
             });
         }
 
+        let dead_bytes = self.dead_data_segment_bytes(call_graph);
+        if dead_bytes > 0 {
+            opts.push(MemoryOptimization {
+                optimization_type: OptimizationType::ReduceMemoryFootprint,
+                description: "One or more data segments are never reached by a resolved load/store (active segments) or a `memory.init` (passive segments), and can likely be removed."
+                    .to_string(),
+                estimated_savings: Some(format!("{} bytes of dead data segments", dead_bytes)),
+                implementation_difficulty: DifficultyLevel::Easy,
+            });
+        }
+
         if self
             .allocation_patterns
             .iter()
             .any(|p| matches!(p.pattern_type, AllocationType::FrequentSmallAllocations))
         {
+            let (description, estimated_savings, implementation_difficulty) = match heap.allocator_kind {
+                AllocatorKind::WeeAlloc => (
+                    "wee_alloc is already a small bump allocator, but the recurring sizes in this \
+                     module's small-allocation traffic suggest a fixed size-class pool carved up \
+                     front would skip its free-list walk entirely."
+                        .to_string(),
+                    Some("Eliminates wee_alloc's free-list search for the hottest allocation sizes.".to_string()),
+                    DifficultyLevel::Medium,
+                ),
+                AllocatorKind::Dlmalloc | AllocatorKind::EmscriptenMalloc => (
+                    "Detected heavy traffic through a general-purpose dlmalloc/libc-style allocator \
+                     at recurring sizes; a single large pool carved into fixed-size objects up front \
+                     (a persistent arena) would replace per-call bookkeeping with index arithmetic."
+                        .to_string(),
+                    Some("Performance improvement by replacing repeated malloc/free calls with a pre-sized arena.".to_string()),
+                    DifficultyLevel::Hard,
+                ),
+                AllocatorKind::RustSystemAlloc => (
+                    "Rust's global allocator pays dlmalloc-style bookkeeping on every call; the \
+                     recurring allocation sizes here are a good fit for a bump/pool arena instead of \
+                     per-call `Box`/`Vec` churn."
+                        .to_string(),
+                    Some("Performance improvement from batching allocations into a bump/pool arena.".to_string()),
+                    DifficultyLevel::Medium,
+                ),
+                AllocatorKind::CustomPool => (
+                    "Allocator exposes a malloc-compatible API that doesn't match a known \
+                     general-purpose allocator; given the recurring allocation sizes, extending its \
+                     pool with explicit size classes would avoid a variable-size free-list search."
+                        .to_string(),
+                    Some("Avoids variable-size bookkeeping for the hottest allocation sizes.".to_string()),
+                    DifficultyLevel::Medium,
+                ),
+                AllocatorKind::Unknown => (
+                    "Detected patterns of frequent small memory accesses, potentially indicating inefficient small allocations if custom allocator is used.".to_string(),
+                    Some("Performance improvement by using memory pooling or optimizing data structures.".to_string()),
+                    DifficultyLevel::Hard,
+                ),
+            };
             opts.push(MemoryOptimization {
                 optimization_type: OptimizationType::MinimizeAllocations,
-                description: "Detected patterns of frequent small memory accesses, potentially indicating inefficient small allocations if custom allocator is used.".to_string(),
-                estimated_savings: Some("Performance improvement by using memory pooling or optimizing data structures.".to_string()),
-                implementation_difficulty: DifficultyLevel::Hard,
+                description,
+                estimated_savings,
+                implementation_difficulty,
+            });
+        }
+
+        if self.module_info.memory.is_some() {
+            opts.push(MemoryOptimization {
+                optimization_type: OptimizationType::PoolingAllocatorConfig,
+                description: format!(
+                    "A pooling instance allocator could pre-reserve a {}-byte slot ({} page{}) per instance, reset via {}.",
+                    pooling_recommendation.recommended_slot_bytes,
+                    pooling_recommendation.max_memory_pages,
+                    if pooling_recommendation.max_memory_pages == 1 { "" } else { "s" },
+                    match pooling_recommendation.reset_strategy {
+                        MemoryResetStrategy::CopyOnWrite => "copy-on-write",
+                        MemoryResetStrategy::ZeroFill => "zero-fill",
+                    },
+                ),
+                estimated_savings: Some(
+                    "Avoids a fresh mmap per instance for hosts running many short-lived instances."
+                        .to_string(),
+                ),
+                implementation_difficulty: DifficultyLevel::Medium,
             });
         }
+
         opts
     }
 
-    fn analyze_memory_safety(&self) -> MemorySafetyAnalysis {
+    /// The static per-instance bounds a pooling instance allocator needs:
+    /// `memory.maximum` when declared, or an estimate from observed
+    /// `MemoryGrow` traffic when it isn't, plus table slot sizing.
+    fn build_pooling_recommendation(&self) -> PoolingRecommendation {
+        let memory = self.module_info.memory.as_ref();
+
+        let initial_pages = memory.map_or(0, |m| m.initial);
+        let (max_memory_pages, requires_growth) = match memory.and_then(|m| m.maximum) {
+            Some(maximum) => (maximum, maximum > initial_pages),
+            None => {
+                let growth_patterns: Vec<&AllocationPattern> = self
+                    .allocation_patterns
+                    .iter()
+                    .filter(|p| matches!(p.pattern_type, AllocationType::DynamicGrowth))
+                    .collect();
+                if growth_patterns.is_empty() {
+                    (initial_pages, false)
+                } else {
+                    let estimated_growth_bytes: u64 = growth_patterns
+                        .iter()
+                        .map(|p| p.average_size as u64 * p.frequency as u64)
+                        .sum();
+                    let estimated_growth_pages =
+                        (estimated_growth_bytes / WASM_PAGE_SIZE_BYTES as u64) as u32;
+                    (initial_pages.saturating_add(estimated_growth_pages), true)
+                }
+            }
+        };
+
+        let reset_strategy = if memory.map_or(false, |m| m.shared) {
+            MemoryResetStrategy::ZeroFill
+        } else {
+            MemoryResetStrategy::CopyOnWrite
+        };
+
+        let table_element_capacity: u32 = self
+            .module_info
+            .tables
+            .iter()
+            .map(|t| t.table_type.maximum.unwrap_or(t.table_type.initial))
+            .sum();
+
+        PoolingRecommendation {
+            max_memory_pages,
+            requires_growth,
+            reset_strategy,
+            recommended_slot_bytes: max_memory_pages * WASM_PAGE_SIZE_BYTES,
+            table_element_capacity,
+        }
+    }
+
+    fn analyze_memory_safety(&self, data_segment_report: &DataSegmentReport) -> MemorySafetyAnalysis {
         let mut potential_overflows = Vec::new();
         let mut buffer_safety_score: f64 = 100.0;
 
         for (func_idx, operations) in &self.memory_operations {
             for op in operations {
-                if let Some(offset) = op.offset {
-                    // Check against initial memory size if no max. This is a very rough heuristic.
-                    let limit = self
-                        .module_info
-                        .memory
-                        .as_ref()
-                        .map_or(WASM_PAGE_SIZE_BYTES, |m| {
-                            m.maximum.unwrap_or(m.initial) * WASM_PAGE_SIZE_BYTES
-                        });
+                if op.preceded_by_grow {
+                    // A `memory.grow` earlier in this function means memory may
+                    // already be larger than the module's declared size by the
+                    // time this op runs, so a static bound computed from that
+                    // declared size would be a false positive.
+                    continue;
+                }
 
-                    let access_size = op.size.unwrap_or(1); // Min 1 byte accessed
+                // Check against initial memory size if no max. This is a very rough heuristic.
+                let limit = self.memory_limit_bytes();
 
-                    if offset.saturating_add(access_size) > limit && limit > 0 {
-                        // If offset + size > known limit
+                if let Some(address) = op.effective_address {
+                    // The abstract interpreter resolved the load/store's base
+                    // operand to a constant, so `address` is the true effective
+                    // address rather than just the instruction's static offset.
+                    let access_size = op.size.unwrap_or(1); // Min 1 byte accessed
+                    if address.saturating_add(access_size) > limit && limit > 0 {
                         potential_overflows.push(PotentialOverflow {
                             function_index: *func_idx,
                             operation_type: format!("{:?}", op.operation_type),
                             risk_level: RiskLevel::Medium,
-                            description: format!("Memory operation at offset {} (size {}) may exceed memory limit {}.", offset, access_size, limit),
+                            description: format!("Memory operation at resolved address {} (size {}) may exceed memory limit {}.", address, access_size, limit),
+                            inferred_address_range: None,
                         });
                         buffer_safety_score -= 5.0; // Penalize
-                    } else if offset > 1_000_000 && limit == 0 {
-                        // Large offset with no memory info
+                    }
+                } else if let Some(offset) = op.offset {
+                    if offset > 1_000_000 && limit == 0 {
+                        // Large static offset with no memory info and an
+                        // unresolved dynamic base — less certain than the
+                        // resolved-address case above.
                         potential_overflows.push(PotentialOverflow {
                             function_index: *func_idx,
                             operation_type: format!("{:?}", op.operation_type),
                             risk_level: RiskLevel::Low, // Lower risk as it's less certain
-                            description: format!("Memory operation at large offset {} with no explicit memory limits.", offset),
+                            description: format!("Memory operation at large static offset {} with no explicit memory limits.", offset),
+                            inferred_address_range: None,
                         });
                         buffer_safety_score -= 1.0;
                     }
@@ -813,6 +2826,80 @@ impl<'a> MemoryAnalyzer<'a> {
             }
         }
 
+        // `BoundsAnalysisPass` proves a tighter class of overflow than the
+        // two checks above: a base address built from arithmetic or a
+        // bitmask, not just a bare constant, statically exceeding `limit`.
+        for finding in &self.bounds_findings {
+            potential_overflows.push(PotentialOverflow {
+                function_index: finding.function_index,
+                operation_type: "BoundsAnalysis".to_string(),
+                risk_level: RiskLevel::High,
+                description: format!(
+                    "Abstract interpretation proved the access at instruction offset {} falls in address range [{}, {}), which exceeds the memory limit.",
+                    finding.instruction_offset, finding.proven_range.0, finding.proven_range.1
+                ),
+                inferred_address_range: Some(finding.proven_range),
+            });
+            buffer_safety_score -= 10.0;
+        }
+
+        for finding in &self.unchecked_allocations {
+            let (operation_type, description) = match finding.origin {
+                AllocOrigin::Grow => (
+                    "UncheckedAllocation(Grow)".to_string(),
+                    "memory.grow's result (-1 on failure) is used as a load/store address with no \
+                     i32.eqz/-1-compare/br_if check first."
+                        .to_string(),
+                ),
+                AllocOrigin::HeapCall => (
+                    "UncheckedAllocation(HeapCall)".to_string(),
+                    "An allocation function's result (a null 0 pointer on failure) is used as a \
+                     load/store address with no check first."
+                        .to_string(),
+                ),
+            };
+            potential_overflows.push(PotentialOverflow {
+                function_index: finding.function_index,
+                operation_type,
+                risk_level: RiskLevel::Medium,
+                description,
+                inferred_address_range: None,
+            });
+            buffer_safety_score -= 5.0;
+        }
+
+        // Overlapping or out-of-bounds data segments fail at instantiation
+        // time, before the module ever runs, so they're `High` risk rather
+        // than the `Medium`/`Low` heuristics above for in-function accesses.
+        // There's no function to blame these on, so `function_index` carries
+        // the offending data segment's index instead.
+        for &(a, b) in &data_segment_report.overlapping_segments {
+            potential_overflows.push(PotentialOverflow {
+                function_index: a,
+                operation_type: format!("DataSegmentOverlap(segment {})", b),
+                risk_level: RiskLevel::High,
+                description: format!(
+                    "Data segment {} overlaps data segment {}; instantiation will write garbage data or trap.",
+                    a, b
+                ),
+                inferred_address_range: None,
+            });
+            buffer_safety_score -= 10.0;
+        }
+        for &segment_index in &data_segment_report.out_of_bounds_segments {
+            potential_overflows.push(PotentialOverflow {
+                function_index: segment_index,
+                operation_type: "DataSegmentOutOfBounds".to_string(),
+                risk_level: RiskLevel::High,
+                description: format!(
+                    "Data segment {} extends past the memory's initial size; instantiation will trap.",
+                    segment_index
+                ),
+                inferred_address_range: None,
+            });
+            buffer_safety_score -= 10.0;
+        }
+
         let uses_grow = self
             .allocation_patterns
             .iter()
@@ -838,6 +2925,98 @@ impl<'a> MemoryAnalyzer<'a> {
                 RiskLevel::Low
             }, // Very basic heuristic
             buffer_safety_score: buffer_safety_score.max(0.0),
+            data_race_risk: self.analyze_data_race_risk(),
+        }
+    }
+
+    fn analyze_data_race_risk(&self) -> DataRaceRisk {
+        let is_shared = self.module_info.memory.as_ref().map_or(false, |m| m.shared);
+        if !is_shared {
+            return DataRaceRisk {
+                risk_level: RiskLevel::Low,
+                functions_involved: Vec::new(),
+                description: "Module does not declare a shared linear memory.".to_string(),
+            };
+        }
+
+        let mut functions_involved: Vec<u32> = self
+            .memory_operations
+            .iter()
+            .filter(|(_, operations)| {
+                let has_atomic = operations
+                    .iter()
+                    .any(|op| is_atomic_op(&op.operation_type));
+                let has_non_atomic = operations.iter().any(|op| {
+                    matches!(
+                        op.operation_type,
+                        MemoryOpType::Load { .. }
+                            | MemoryOpType::Store { .. }
+                            | MemoryOpType::Vector { .. }
+                    )
+                });
+                has_atomic && has_non_atomic
+            })
+            .map(|(&func_idx, _)| func_idx)
+            .collect();
+        functions_involved.sort_unstable();
+
+        if functions_involved.is_empty() {
+            DataRaceRisk {
+                risk_level: RiskLevel::Low,
+                functions_involved,
+                description: "Shared memory is declared, but no function mixes atomic and \
+                              non-atomic accesses."
+                    .to_string(),
+            }
+        } else {
+            DataRaceRisk {
+                risk_level: RiskLevel::High,
+                functions_involved,
+                description: "Functions mix non-atomic loads/stores with atomic accesses on \
+                              shared memory; concurrent non-atomic access to shared memory is \
+                              a data race."
+                    .to_string(),
+            }
+        }
+    }
+
+    /// Threads-proposal sanity checks: is `shared` actually being used the way
+    /// its presence (or absence) implies, and do any hotspots come from
+    /// contended atomics rather than plain traffic. Takes the already-computed
+    /// `hotspots` rather than recomputing them, since `find_memory_hotspots`
+    /// has already done the per-function pressure accounting `analyze` needs.
+    fn analyze_thread_safety(&self, hotspots: &[MemoryHotspot]) -> ThreadSafetyAnalysis {
+        let is_shared_memory = self.module_info.memory.as_ref().map_or(false, |m| m.shared);
+
+        let mut atomics_on_non_shared_memory: Vec<u32> = if is_shared_memory {
+            Vec::new()
+        } else {
+            self.memory_operations
+                .iter()
+                .filter(|(_, operations)| operations.iter().any(|op| is_atomic_op(&op.operation_type)))
+                .map(|(&func_idx, _)| func_idx)
+                .collect()
+        };
+        atomics_on_non_shared_memory.sort_unstable();
+
+        let shared_memory_with_no_atomics = is_shared_memory
+            && !self
+                .memory_operations
+                .values()
+                .any(|operations| operations.iter().any(|op| is_atomic_op(&op.operation_type)));
+
+        let mut atomic_contention_hotspots: Vec<u32> = hotspots
+            .iter()
+            .filter(|h| matches!(h.hotspot_type, HotspotType::AtomicContention))
+            .map(|h| h.function_index)
+            .collect();
+        atomic_contention_hotspots.sort_unstable();
+
+        ThreadSafetyAnalysis {
+            is_shared_memory,
+            atomics_on_non_shared_memory,
+            shared_memory_with_no_atomics,
+            atomic_contention_hotspots,
         }
     }
 }
@@ -16,12 +16,88 @@ pub struct WasmParser<'a> {
     module_info: ModuleInfo,
     type_signatures: Vec<wasmparser::FuncType>, // Keep for parsing
     function_names: HashMap<u32, String>, // Key is global function index
+    global_names: HashMap<u32, String>,
+    memory_names: HashMap<u32, String>,
+    table_names: HashMap<u32, String>,
     imported_function_count: u32, // Added field
+    imported_table_count: u32,
+    imported_memory_count: u32,
+    imported_global_count: u32,
 }
 
 
 
 
+/// Evaluates a WASM init expression (global initializer, or active data/element
+/// segment offset) into a [`ConstValue`]. Recognizes the single-instruction
+/// forms produced by real-world toolchains — `I32Const`/`I64Const`/`F32Const`/
+/// `F64Const`/`V128Const` literals, a `GlobalGet` reference to another global,
+/// a `RefFunc` function reference, and a typed `RefNull` — and falls back to
+/// `ConstValue::Unknown` for anything else (e.g. an `extended-const`
+/// arithmetic sequence) rather than discarding the expression. Always reads
+/// through to `End` and calls `ensure_end()` before returning, regardless of
+/// whether the expression was understood.
+fn eval_const_expr(ops_reader: &mut wasmparser::OperatorsReader) -> Result<ConstValue> {
+    let mut value = ConstValue::Unknown;
+    let mut seen_first = false;
+    loop {
+        let op = ops_reader.read()?;
+        if matches!(op, Operator::End) {
+            break;
+        }
+        if seen_first {
+            // A second instruction before `end` means this is a multi-op sequence
+            // (e.g. extended-const arithmetic) that the single-literal model above
+            // doesn't cover.
+            value = ConstValue::Unknown;
+            continue;
+        }
+        seen_first = true;
+        value = match op {
+            Operator::I32Const { value } => ConstValue::I32(value),
+            Operator::I64Const { value } => ConstValue::I64(value),
+            Operator::F32Const { value } => ConstValue::F32(f32::from_bits(value.bits())),
+            Operator::F64Const { value } => ConstValue::F64(f64::from_bits(value.bits())),
+            Operator::V128Const { value } => ConstValue::V128(value.bytes()),
+            Operator::GlobalGet { global_index } => ConstValue::GlobalRef(global_index),
+            Operator::RefFunc { function_index } => ConstValue::FuncRef(function_index),
+            Operator::RefNull { hty } => ConstValue::RefNull(format!("{:?}", hty)),
+            _ => ConstValue::Unknown,
+        };
+    }
+    ops_reader.ensure_end()?;
+    Ok(value)
+}
+
+/// Which mangling scheme successfully decoded a symbol, so callers that need
+/// a majority-language guess (e.g. `ModuleAnalyzer::detect_source_language`)
+/// don't have to re-derive it from the demangled string's shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DemangledLanguage {
+    Rust,
+    Cpp,
+}
+
+/// Attempts to demangle a raw symbol name from the name section or export
+/// table, trying Rust (v0 or legacy) mangling first and falling back to
+/// Itanium C++ mangling. Returns `None` if `raw` doesn't decode as either —
+/// typically because it's already a plain, unmangled name.
+pub(crate) fn demangle_symbol_with_language(raw: &str) -> Option<(String, DemangledLanguage)> {
+    let rust_demangled = rustc_demangle::demangle(raw).to_string();
+    if rust_demangled != raw {
+        return Some((rust_demangled, DemangledLanguage::Rust));
+    }
+    cpp_demangle::Symbol::new(raw)
+        .ok()
+        .map(|symbol| (symbol.to_string(), DemangledLanguage::Cpp))
+}
+
+/// Convenience wrapper over [`demangle_symbol_with_language`] for callers
+/// that only want the demangled string, not which scheme produced it.
+pub(crate) fn demangle_symbol(raw: &str) -> Option<String> {
+    demangle_symbol_with_language(raw).map(|(name, _)| name)
+}
+
 impl<'a> WasmParser<'a> {
     pub fn new(bytes: &'a [u8]) -> Result<Self> {
         Ok(Self {
@@ -38,30 +114,126 @@ impl<'a> WasmParser<'a> {
                 element_segments: Vec::new(),
                 start_function: None,
                 custom_sections: Vec::new(),
+                module_name: None,
+                local_names: std::collections::BTreeMap::new(),
+                type_names: std::collections::BTreeMap::new(),
                 function_call_instructions: Vec::new(),
+                indirect_call_instructions: Vec::new(),
                 type_signatures: Vec::new(), // Initialize as empty Vec<String>
+                function_code_maps: std::collections::BTreeMap::new(),
             },
             type_signatures: Vec::new(), // This is Vec<wasmparser::FuncType>
             function_names: HashMap::new(),
+            global_names: HashMap::new(),
+            memory_names: HashMap::new(),
+            table_names: HashMap::new(),
             imported_function_count: 0, // Initialize
+            imported_table_count: 0,
+            imported_memory_count: 0,
+            imported_global_count: 0,
         })
     }
 
-    pub fn parse(mut self) -> Result<ModuleInfo> {
+    /// Parses `bytes` and dispatches on the encoding reported by the leading
+    /// `Payload::Version` to either the core-module path (`parse_module`) or
+    /// the Component Model path (`parse_component`) — components are the
+    /// common distribution format for WASI Preview 2 and newer, and can't be
+    /// force-fit through the core-module parser.
+    pub fn parse(mut self) -> Result<ParsedArtifact> {
         let parser = Parser::new(0);
-        let mut defined_function_idx_counter: u32 = 0;
-
-        // Pre-calculate imported function count as it's needed for global indexing early
-        // This requires a preliminary pass or careful ordering.
-        // For simplicity, we'll parse imports first, then use the count.
-        // A full parser might do multiple passes or collect sections first.
-        // Let's parse imports first to get this count.
-        
         let mut payloads = Vec::new();
         for payload_result in parser.parse_all(self.bytes) {
             payloads.push(payload_result?);
         }
 
+        let is_component = matches!(
+            payloads.first(),
+            Some(Payload::Version { encoding: wasmparser::Encoding::Component, .. })
+        );
+
+        if is_component {
+            Ok(ParsedArtifact::Component(self.parse_component(payloads)?))
+        } else {
+            Ok(ParsedArtifact::Module(self.parse_module(payloads)?))
+        }
+    }
+
+    /// Parses the component-model payloads gathered by `parse`: nested
+    /// module/component sections are counted (not recursively parsed), and
+    /// component-level type/import/export/instance/alias/canonical sections
+    /// are summarized into a [`ComponentInfo`].
+    fn parse_component(self, payloads: Vec<Payload>) -> Result<ComponentInfo> {
+        let mut info = ComponentInfo::default();
+
+        for payload in payloads {
+            match payload {
+                Payload::ModuleSection { .. } => {
+                    info.nested_modules += 1;
+                }
+                Payload::ComponentSection { .. } => {
+                    info.nested_components += 1;
+                }
+                Payload::CoreTypeSection(reader) => {
+                    for ty in reader {
+                        ty?;
+                        info.core_type_count += 1;
+                    }
+                }
+                Payload::ComponentTypeSection(reader) => {
+                    for ty in reader {
+                        ty?;
+                        info.type_count += 1;
+                    }
+                }
+                Payload::ComponentImportSection(reader) => {
+                    for import in reader {
+                        let import = import?;
+                        info.imports.push(ComponentImport {
+                            name: format!("{:?}", import.name),
+                            interface_type: format!("{:?}", import.ty),
+                        });
+                    }
+                }
+                Payload::ComponentExportSection(reader) => {
+                    for export in reader {
+                        let export = export?;
+                        info.exports.push(ComponentExport {
+                            name: format!("{:?}", export.name),
+                            interface_type: format!("{:?}", export.kind),
+                        });
+                    }
+                }
+                Payload::ComponentInstanceSection(reader) => {
+                    for instance in reader {
+                        instance?;
+                        info.instance_count += 1;
+                    }
+                }
+                Payload::ComponentAliasSection(reader) => {
+                    for alias in reader {
+                        alias?;
+                        info.alias_count += 1;
+                    }
+                }
+                Payload::ComponentCanonicalSection(reader) => {
+                    for canonical in reader {
+                        canonical?;
+                    }
+                }
+                _ => {
+                    // Nested core-module payloads (type/import/code/etc. sections
+                    // belonging to a `ModuleSection`) and anything else not
+                    // summarized above.
+                }
+            }
+        }
+
+        Ok(info)
+    }
+
+    fn parse_module(mut self, payloads: Vec<Payload>) -> Result<ModuleInfo> {
+        let mut defined_function_idx_counter: u32 = 0;
+
         // First pass for imports to count imported functions
         for payload in &payloads {
             if let Payload::ImportSection(reader) = payload {
@@ -135,6 +307,20 @@ impl<'a> WasmParser<'a> {
         Ok(self.module_info)
     }
 
+    /// Resolve a type index (as held by a `Function` or `ImportKind::Function`) to
+    /// its param/result value types via the type section collected earlier by
+    /// `parse_type_section`. Returns empty vecs for an out-of-range index rather
+    /// than failing the whole parse over one malformed reference.
+    fn resolve_signature(&self, type_index: u32) -> (Vec<ValType>, Vec<ValType>) {
+        match self.type_signatures.get(type_index as usize) {
+            Some(func_type) => (
+                func_type.params().iter().map(|t| convert_val_type(*t)).collect(),
+                func_type.results().iter().map(|t| convert_val_type(*t)).collect(),
+            ),
+            None => (Vec::new(), Vec::new()),
+        }
+    }
+
        fn parse_type_section(&mut self, reader: TypeSectionReader) -> Result<()> {
         for result_rec_group in reader {
             let rec_group = result_rec_group?; 
@@ -158,28 +344,66 @@ impl<'a> WasmParser<'a> {
                 wasmparser::TypeRef::Func(type_index) => {
                     // let global_func_idx = _current_func_import_idx; // This is the global index for this imported function
                     _current_func_import_idx += 1;
-                    ImportKind::Function { type_index } // Store type_index, global_func_idx handled by position
+                    let (params, results) = self.resolve_signature(type_index);
+                    ImportKind::Function { type_index, params, results } // Store type_index, global_func_idx handled by position
                 }
-                wasmparser::TypeRef::Table(table_type) => ImportKind::Table {
-                    table_type: TableType {
+                wasmparser::TypeRef::Table(table_type) => {
+                    let table_type = TableType {
                         element_type: format!("{:?}", table_type.element_type),
                         initial: table_type.initial,
                         maximum: table_type.maximum,
-                    },
-                },
-                wasmparser::TypeRef::Memory(memory_type) => ImportKind::Memory {
-                    memory_type: MemoryType {
+                    };
+                    // Tables live in the same global index space as defined-section
+                    // tables (offset in `parse_table_section`), so an imported table
+                    // gets a real entry here instead of leaving `is_imported` false
+                    // for a host-provided table the module never defines itself.
+                    self.module_info.tables.push(Table {
+                        index: self.imported_table_count,
+                        table_type: table_type.clone(),
+                        is_imported: true,
+                        is_exported: false,
+                        name: None, // Filled from the name section in update_function_metadata
+                    });
+                    self.imported_table_count += 1;
+                    ImportKind::Table { table_type }
+                }
+                wasmparser::TypeRef::Memory(memory_type) => {
+                    let memory_type = MemoryType {
                         initial: memory_type.initial as u32,
                         maximum: memory_type.maximum.map(|m| m as u32),
                         shared: memory_type.shared,
-                    },
-                },
-                wasmparser::TypeRef::Global(global_type) => ImportKind::Global {
-                    global_type: GlobalType {
+                    };
+                    // `module_info.memory` models a single memory (pre-multi-memory
+                    // WASM); an imported memory is the common "host provides memory"
+                    // case, so it gets recorded here rather than leaving `memory` as
+                    // `None` whenever there's no local `MemorySection` entry.
+                    self.module_info.memory = Some(Memory {
+                        initial: memory_type.initial,
+                        maximum: memory_type.maximum,
+                        shared: memory_type.shared,
+                        is_imported: true,
+                        is_exported: false,
+                        name: None,
+                    });
+                    self.imported_memory_count += 1;
+                    ImportKind::Memory { memory_type }
+                }
+                wasmparser::TypeRef::Global(global_type) => {
+                    let global_type = GlobalType {
                         value_type: format!("{:?}", global_type.content_type),
                         mutable: global_type.mutable,
-                    },
-                },
+                    };
+                    self.module_info.globals.push(Global {
+                        index: self.imported_global_count,
+                        global_type: global_type.clone(),
+                        init_value: ConstValue::Unknown, // An import has no init expression of its own
+                        is_imported: true,
+                        is_exported: false,
+                        name: None,
+                    });
+                    self.imported_global_count += 1;
+                    ImportKind::Global { global_type }
+                }
                 _ => continue, // Other import types like Tag
             };
 
@@ -199,24 +423,31 @@ impl<'a> WasmParser<'a> {
         for (defined_idx, type_index_result) in reader.into_iter().enumerate() {
             let type_index = type_index_result?;
             let global_function_index = self.imported_function_count + defined_idx as u32;
+            let (params, results) = self.resolve_signature(type_index);
             self.module_info.functions.push(Function {
                 index: global_function_index, // Store global index
                 type_index,
+                params,
+                results,
                 locals: Vec::new(),
                 body_size: 0, // Will be set in CodeSectionEntry
                 is_imported: false, // These are defined functions
                 is_exported: false, // Will be set later
                 name: None,         // Will be set later
+                demangled_name: None, // Will be set alongside `name` in update_function_metadata
+                max_block_depth: 0, // Will be set in CodeSectionEntry
+                instruction_count: 0, // Will be set in CodeSectionEntry
             });
         }
         Ok(())
     }
 
     fn parse_table_section(&mut self, reader: TableSectionReader) -> Result<()> {
-        for (index, table) in reader.into_iter().enumerate() {
+        // Defined tables sit after any imported ones in the global table index space.
+        for (defined_idx, table) in reader.into_iter().enumerate() {
             let table = table?;
             self.module_info.tables.push(Table {
-                index: index as u32,
+                index: self.imported_table_count + defined_idx as u32,
                 table_type: TableType {
                     element_type: format!("{:?}", table.ty.element_type),
                     initial: table.ty.initial,
@@ -224,6 +455,7 @@ impl<'a> WasmParser<'a> {
                 },
                 is_imported: false,
                 is_exported: false,
+                name: None,
             });
         }
         Ok(())
@@ -238,24 +470,29 @@ impl<'a> WasmParser<'a> {
                 shared: memory.shared,
                 is_imported: false,
                 is_exported: false,
+                name: None,
             });
-            break; 
+            break;
         }
         Ok(())
     }
 
     fn parse_global_section(&mut self, reader: GlobalSectionReader) -> Result<()> {
-        for (index, global) in reader.into_iter().enumerate() {
+        // Defined globals sit after any imported ones in the global index space.
+        for (defined_idx, global) in reader.into_iter().enumerate() {
             let global = global?;
+            let mut ops_reader = global.init_expr.get_operators_reader();
+            let init_value = eval_const_expr(&mut ops_reader)?;
             self.module_info.globals.push(Global {
-                index: index as u32,
+                index: self.imported_global_count + defined_idx as u32,
                 global_type: GlobalType {
                     value_type: format!("{:?}", global.ty.content_type),
                     mutable: global.ty.mutable,
                 },
-                init_value: None, 
+                init_value,
                 is_imported: false,
                 is_exported: false,
+                name: None,
             });
         }
         Ok(())
@@ -272,10 +509,21 @@ impl<'a> WasmParser<'a> {
                 _ => continue, // Other export kinds like Tag
             };
             // export.index is the global index of the exported item (e.g. global func index)
+            let (points_to_import, resolved_index) = if kind == ExportKind::Function {
+                if export.index < self.imported_function_count {
+                    (true, export.index)
+                } else {
+                    (false, export.index - self.imported_function_count)
+                }
+            } else {
+                (false, 0)
+            };
             self.module_info.exports.push(Export {
                 name: export.name.to_string(),
                 kind,
-                index: export.index, 
+                index: export.index,
+                points_to_import,
+                resolved_index,
             });
         }
         Ok(())
@@ -284,8 +532,16 @@ impl<'a> WasmParser<'a> {
     fn parse_element_section(&mut self, reader: ElementSectionReader) -> Result<()> {
         for (index, result_element) in reader.into_iter().enumerate() {
             let element = result_element?;
+            let function_indices: Vec<u32> = match &element.items {
+                wasmparser::ElementItems::Functions(reader) => {
+                    reader.clone().into_iter().collect::<Result<Vec<u32>, _>>()?
+                }
+                // `ref.func`/`ref.null` expression items aren't resolved to a static
+                // function-index list; callers fall back to type-signature matching.
+                wasmparser::ElementItems::Expressions(_ref_type, _reader) => Vec::new(),
+            };
             let element_count = match &element.items {
-                wasmparser::ElementItems::Functions(reader) => reader.clone().count() as u32,
+                wasmparser::ElementItems::Functions(_) => function_indices.len() as u32,
                 wasmparser::ElementItems::Expressions(_ref_type, reader) => reader.clone().count() as u32,
             };
 
@@ -293,16 +549,8 @@ impl<'a> WasmParser<'a> {
                 wasmparser::ElementKind::Active { table_index, offset_expr} => {
                     let resolved_table_index = table_index.unwrap_or(0);
                     let mut ops_reader = offset_expr.get_operators_reader();
-                    let offset_val = match ops_reader.read()? {
-                        wasmparser::Operator::I32Const { value } => {
-                            match ops_reader.read()? {
-                                wasmparser::Operator::End => { ops_reader.ensure_end()?; Some(value as u32) }
-                                _ => None 
-                            }
-                        }
-                        _ => None,
-                    };
-                    (Some(resolved_table_index), offset_val)
+                    let offset_val = eval_const_expr(&mut ops_reader)?;
+                    (Some(resolved_table_index), Some(offset_val))
                 }
                 wasmparser::ElementKind::Passive | wasmparser::ElementKind::Declared => (None, None),
             };
@@ -313,6 +561,7 @@ impl<'a> WasmParser<'a> {
                 offset: final_offset,
                 element_count,
                 is_passive,
+                function_indices,
             });
         }
         Ok(())
@@ -324,18 +573,9 @@ impl<'a> WasmParser<'a> {
             let (memory_index, offset_val) = match data.kind {
                  wasmparser::DataKind::Active { memory_index, offset_expr } => {
                     let mut ops_reader = offset_expr.get_operators_reader();
-                    let offset_val = match ops_reader.read()? {
-                        wasmparser::Operator::I32Const { value } => {
-                             match ops_reader.read()? {
-                                wasmparser::Operator::End => { ops_reader.ensure_end()?; Some(value as u32) }
-                                _ => None
-                            }
-                        }
-                         _ => None,
-                    };
-                    (memory_index, offset_val.unwrap_or(0)) // Default offset 0 if expr complex
+                    (memory_index, eval_const_expr(&mut ops_reader)?)
                  }
-                 wasmparser::DataKind::Passive => (0,0), // Passive has no mem_idx/offset here
+                 wasmparser::DataKind::Passive => (0, ConstValue::Unknown), // Passive has no mem_idx/offset here
             };
 
             self.module_info.data_segments.push(DataSegment {
@@ -360,6 +600,10 @@ impl<'a> WasmParser<'a> {
             });
         }
 
+        // Absolute byte range of this function's whole code-section entry (locals +
+        // operators), recorded up front for `FunctionCodeMap::body_range` below.
+        let body_range = body.range();
+
         // Update the corresponding Function struct for defined functions
         if let Some(func) = self.module_info.functions.get_mut(defined_func_idx as usize) {
             func.locals = locals_for_func;
@@ -375,19 +619,73 @@ impl<'a> WasmParser<'a> {
             anyhow::bail!("Function at defined index {} not found when parsing body.", defined_func_idx);
         }
 
-        // Parse operators for calls
+        // Parse operators for calls, tracking block/loop/if nesting depth along the way
+        // so we can report `max_block_depth` without a second pass over the body.
         let mut ops_reader = body.get_operators_reader()?;
+        let mut block_depth: u32 = 0;
+        let mut max_block_depth: u32 = 0;
+        // Tracks the most recently seen `i32.const` value, so a `call_indirect`
+        // immediately preceded by one (the common vtable/closure-table codegen
+        // pattern) can resolve its target table slot statically instead of
+        // falling back to type-signature matching.
+        let mut pending_const_index: Option<u32> = None;
+        let mut instruction_count: u32 = 0;
+        let mut instruction_offsets: Vec<(u32, u32)> = Vec::new();
         while !ops_reader.eof() {
+            let offset = ops_reader.original_position() as u32;
             let operator = ops_reader.read()?;
-            match operator {
-                Operator::Call { function_index } => {
-                    // function_index is the global index of the callee
-                    self.module_info.function_call_instructions.push((current_func_global_idx, function_index));
+            instruction_offsets.push((instruction_count, offset));
+            instruction_count += 1;
+            let mut next_pending_const_index = None;
+
+            match &operator {
+                Operator::Block { .. } | Operator::Loop { .. } | Operator::If { .. } => {
+                    block_depth += 1;
+                    max_block_depth = max_block_depth.max(block_depth);
+                }
+                Operator::End => {
+                    block_depth = block_depth.saturating_sub(1);
+                }
+                Operator::Call { function_index } | Operator::ReturnCall { function_index } => {
+                    // function_index is the global index of the callee. A tail call
+                    // (`return_call`) still reaches its callee, so it's recorded the
+                    // same as a regular `call` for call-graph purposes.
+                    self.module_info.function_call_instructions.push((current_func_global_idx, *function_index));
+                }
+                Operator::CallIndirect { type_index, .. }
+                | Operator::ReturnCallIndirect { type_index, .. } => {
+                    // The concrete callee is only known at runtime (it comes off the table),
+                    // so record the call site's type signature plus any statically-known
+                    // table slot for conservative resolution in CallGraphBuilder. Tail
+                    // indirect calls are recorded identically since they still dispatch
+                    // through the table.
+                    self.module_info.indirect_call_instructions.push((
+                        current_func_global_idx,
+                        *type_index,
+                        pending_const_index,
+                    ));
+                }
+                Operator::I32Const { value } => {
+                    next_pending_const_index = Some(*value as u32);
                 }
-                // TODO: Handle Operator::CallIndirect if needed for more detailed graph
                 _ => {}
             }
+
+            pending_const_index = next_pending_const_index;
+        }
+        if let Some(func) = self.module_info.functions.get_mut(defined_func_idx as usize) {
+            func.max_block_depth = max_block_depth;
+            func.instruction_count = instruction_count;
         }
+
+        self.module_info.function_code_maps.insert(
+            current_func_global_idx,
+            FunctionCodeMap {
+                body_range: body_range.start as u32..body_range.end as u32,
+                instruction_offsets,
+            },
+        );
+
         Ok(())
     }
 
@@ -411,6 +709,9 @@ impl<'a> WasmParser<'a> {
         let name_reader = wasmparser::NameSectionReader::new(data, 0);
         for subsection_result in name_reader {
             match subsection_result? {
+                wasmparser::Name::Module { name, .. } => {
+                    self.module_info.module_name = Some(name.to_string());
+                }
                 wasmparser::Name::Function(names) => {
                     for name_map_entry in names {
                         let naming = name_map_entry?;
@@ -418,7 +719,46 @@ impl<'a> WasmParser<'a> {
                         self.function_names.insert(naming.index, naming.name.to_string());
                     }
                 }
-                // TODO: Parse other name subsections if needed (module, locals, etc.)
+                wasmparser::Name::Local(indirect_names) => {
+                    for indirect_entry in indirect_names {
+                        let indirect_naming = indirect_entry?;
+                        let locals_for_func = self
+                            .module_info
+                            .local_names
+                            .entry(indirect_naming.index)
+                            .or_default();
+                        for naming_entry in indirect_naming.names {
+                            let naming = naming_entry?;
+                            locals_for_func.insert(naming.index, naming.name.to_string());
+                        }
+                    }
+                }
+                wasmparser::Name::Type(names) => {
+                    for name_map_entry in names {
+                        let naming = name_map_entry?;
+                        self.module_info.type_names.insert(naming.index, naming.name.to_string());
+                    }
+                }
+                wasmparser::Name::Table(names) => {
+                    for name_map_entry in names {
+                        let naming = name_map_entry?;
+                        self.table_names.insert(naming.index, naming.name.to_string());
+                    }
+                }
+                wasmparser::Name::Memory(names) => {
+                    for name_map_entry in names {
+                        let naming = name_map_entry?;
+                        self.memory_names.insert(naming.index, naming.name.to_string());
+                    }
+                }
+                wasmparser::Name::Global(names) => {
+                    for name_map_entry in names {
+                        let naming = name_map_entry?;
+                        self.global_names.insert(naming.index, naming.name.to_string());
+                    }
+                }
+                // Label/Element/Field/Tag/Data names and unrecognized subsections
+                // aren't surfaced on `ModuleInfo` today.
                 _ => {}
             }
         }
@@ -442,25 +782,55 @@ impl<'a> WasmParser<'a> {
                      func_info.name = Some(export.name.clone());
                 }
             }
+            func_info.demangled_name = func_info
+                .name
+                .as_deref()
+                .and_then(demangle_symbol);
         }
-        
-        // Update export status for memory, tables, globals
+
+        // Update export status and debug names for memory, tables, globals.
+        // `is_imported` itself is already accurate: `parse_import_section` pushes a
+        // real entry (with `is_imported: true`) for every imported table/memory/
+        // global, so a host-provided memory or table is no longer misreported as
+        // absent or locally defined.
         if let Some(ref mut memory) = self.module_info.memory {
             // Memory index is always 0 for current WASM
             if self.module_info.exports.iter().any(|exp| exp.kind == ExportKind::Memory && exp.index == 0) {
                 memory.is_exported = true;
             }
+            memory.name = self.memory_names.get(&0).cloned();
         }
         for table_info in self.module_info.tables.iter_mut() {
             if self.module_info.exports.iter().any(|exp| exp.kind == ExportKind::Table && exp.index == table_info.index) {
                 table_info.is_exported = true;
             }
+            table_info.name = self.table_names.get(&table_info.index).cloned();
         }
         for global_info in self.module_info.globals.iter_mut() {
             if self.module_info.exports.iter().any(|exp| exp.kind == ExportKind::Global && exp.index == global_info.index) {
                 global_info.is_exported = true;
             }
+            global_info.name = self.global_names.get(&global_info.index).cloned();
+        }
+    }
+}
+
+/// Translate a `wasmparser` value type into our own `ValType`, collapsing both
+/// reference type flavors down to `FuncRef`/`ExternRef` (we don't yet distinguish
+/// concrete heap types).
+fn convert_val_type(ty: wasmparser::ValType) -> ValType {
+    match ty {
+        wasmparser::ValType::I32 => ValType::I32,
+        wasmparser::ValType::I64 => ValType::I64,
+        wasmparser::ValType::F32 => ValType::F32,
+        wasmparser::ValType::F64 => ValType::F64,
+        wasmparser::ValType::V128 => ValType::V128,
+        wasmparser::ValType::Ref(r) => {
+            if r.is_func_ref() {
+                ValType::FuncRef
+            } else {
+                ValType::ExternRef
+            }
         }
-        // TODO: Update is_imported for tables, memory, globals based on import section analysis.
     }
 }
@@ -0,0 +1,37 @@
+// ===== passes.rs =====
+// src/passes.rs
+use anyhow::Result;
+use wasmparser::{FunctionBody, Operator};
+
+/// A single analysis that wants to see every operator in a function body,
+/// in program order, exactly once. Implementations hold whatever running
+/// state they need (an abstract-interpreter stack, a set of call edges, a
+/// block-nesting counter, ...) and accumulate it across `visit` calls.
+///
+/// `finish()` isn't part of this trait: passes return different result
+/// types, and a trait object can't express that. Callers keep their pass in
+/// a named variable (rather than behind `dyn FunctionBodyPass`) and call
+/// whatever `finish`-style method the concrete type exposes once driving is
+/// done.
+pub trait FunctionBodyPass {
+    fn visit(&mut self, func_idx: u32, offset: u32, op: &Operator);
+}
+
+/// Parse `body` exactly once, feeding every operator to each pass in
+/// `passes` in turn, so that N analyses of the same function body cost one
+/// parse rather than N.
+pub fn drive_function_body(
+    func_idx: u32,
+    body: &FunctionBody<'_>,
+    passes: &mut [&mut dyn FunctionBodyPass],
+) -> Result<()> {
+    let mut ops_reader = body.get_operators_reader()?;
+    while !ops_reader.eof() {
+        let offset = ops_reader.original_position() as u32;
+        let op = ops_reader.read()?;
+        for pass in passes.iter_mut() {
+            pass.visit(func_idx, offset, &op);
+        }
+    }
+    Ok(())
+}
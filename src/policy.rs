@@ -0,0 +1,147 @@
+// ===== policy.rs =====
+// src/policy.rs
+//! Import allow/denylist enforcement, in the spirit of host environments that
+//! whitelist the exact set of host functions a contract may call rather than just
+//! reporting the descriptive `capabilities`/`vulnerabilities` lists `SecurityAnalysis`
+//! already produces.
+use crate::types::{ImportKind, ModuleInfo};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PolicyDecision {
+    Allow,
+    Deny,
+}
+
+/// One import that a `Policy` rejected.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyViolation {
+    pub module: String,
+    pub name: String,
+    pub reason: String,
+}
+
+/// An import allowlist/denylist, checked against a module's `imports` by
+/// `analyze_with_policy`. Rules are `"<module>.<name>"` pairs; a name of `*`
+/// matches every import from that module (e.g. `"wasi_snapshot_preview1.*"`).
+/// The denylist always wins over the allowlist, and anything neither list
+/// mentions is denied by default.
+#[derive(Debug, Clone, Default)]
+pub struct Policy {
+    allow_exact: HashSet<(String, String)>,
+    allow_modules: HashSet<String>,
+    deny_exact: HashSet<(String, String)>,
+    deny_modules: HashSet<String>,
+}
+
+impl Policy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow a `"module.name"` import, or `"module.*"` to allow every import from
+    /// that module.
+    pub fn allow(mut self, rule: &str) -> Self {
+        self.add_rule(rule, true);
+        self
+    }
+
+    /// Deny a `"module.name"` import, or `"module.*"` to deny every import from
+    /// that module. Denials are checked first, so they always override a broader
+    /// allow rule.
+    pub fn deny(mut self, rule: &str) -> Self {
+        self.add_rule(rule, false);
+        self
+    }
+
+    fn add_rule(&mut self, rule: &str, is_allow: bool) {
+        let (module, name) = rule.rsplit_once('.').unwrap_or((rule, "*"));
+        match (name, is_allow) {
+            ("*", true) => {
+                self.allow_modules.insert(module.to_string());
+            }
+            ("*", false) => {
+                self.deny_modules.insert(module.to_string());
+            }
+            (_, true) => {
+                self.allow_exact
+                    .insert((module.to_string(), name.to_string()));
+            }
+            (_, false) => {
+                self.deny_exact
+                    .insert((module.to_string(), name.to_string()));
+            }
+        }
+    }
+
+    fn is_denied(&self, module: &str, name: &str) -> bool {
+        self.deny_modules.contains(module)
+            || self.deny_exact.contains(&(module.to_string(), name.to_string()))
+    }
+
+    fn is_allowed(&self, module: &str, name: &str) -> bool {
+        self.allow_modules.contains(module)
+            || self.allow_exact.contains(&(module.to_string(), name.to_string()))
+    }
+
+    /// O(1) verdict for one `(module, name)` import.
+    pub fn decide(&self, module: &str, name: &str) -> PolicyDecision {
+        if self.is_denied(module, name) {
+            PolicyDecision::Deny
+        } else if self.is_allowed(module, name) {
+            PolicyDecision::Allow
+        } else {
+            PolicyDecision::Deny
+        }
+    }
+}
+
+/// The result of checking a module's imports against a `Policy`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyReport {
+    pub violations: Vec<PolicyViolation>,
+}
+
+impl PolicyReport {
+    /// Single pass/fail verdict, so `quick_analyze`-style summaries can surface one
+    /// boolean instead of inspecting `violations` themselves.
+    pub fn passes_policy(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Check every function import in `module_info` against `policy`, in `imports`
+/// order, matching each with a `HashSet` lookup so the whole pass is O(imports).
+pub fn check_policy(module_info: &ModuleInfo, policy: &Policy) -> PolicyReport {
+    let mut violations = Vec::new();
+
+    for import in &module_info.imports {
+        if !matches!(import.kind, ImportKind::Function { .. }) {
+            // The policy governs callable host capabilities; imported tables,
+            // memories, and globals aren't something a contract can "call".
+            continue;
+        }
+
+        if policy.decide(&import.module, &import.name) == PolicyDecision::Deny {
+            let reason = if policy.is_denied(&import.module, &import.name) {
+                format!(
+                    "\"{}.{}\" is explicitly denied by policy",
+                    import.module, import.name
+                )
+            } else {
+                format!(
+                    "\"{}.{}\" is not covered by the policy's allowlist",
+                    import.module, import.name
+                )
+            };
+            violations.push(PolicyViolation {
+                module: import.module.clone(),
+                name: import.name.clone(),
+                reason,
+            });
+        }
+    }
+
+    PolicyReport { violations }
+}
@@ -0,0 +1,106 @@
+// ===== profile.rs =====
+// src/profile.rs
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// One timed analysis pass: wall-clock duration plus a rough throughput
+/// signal (how many functions it looked at), so a breakdown can show "ms"
+/// and "functions/ms" side by side.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PassTiming {
+    pub name: String,
+    pub elapsed_ms: f64,
+    pub functions_visited: u32,
+}
+
+/// A [`PassTiming`] annotated with its share of total elapsed time and
+/// throughput — the shape the CLI's `--profile` breakdown prints, sorted by
+/// `elapsed_ms` descending.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PassReportEntry {
+    pub name: String,
+    pub elapsed_ms: f64,
+    pub percent_of_total: f64,
+    pub functions_per_ms: f64,
+}
+
+/// Opt-in wall-clock profiler for `ModuleAnalyzer::analyze_profiled`'s passes
+/// (security, performance, memory, compatibility, call-graph). Disabled by
+/// default: `record` skips the `Instant::now()` calls entirely when disabled,
+/// so the non-profiling path (`ModuleAnalyzer::analyze`) pays only one
+/// boolean check per pass.
+#[derive(Debug, Clone, Default)]
+pub struct Profiler {
+    enabled: bool,
+    timings: Vec<PassTiming>,
+}
+
+impl Profiler {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            timings: Vec::new(),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Run `pass`, timing it when profiling is enabled and recording its
+    /// elapsed time and `functions_visited` under `name`. `pass` always runs;
+    /// when disabled, no `Instant` is taken and nothing is recorded.
+    pub fn record<T>(&mut self, name: &str, functions_visited: u32, pass: impl FnOnce() -> T) -> T {
+        if !self.enabled {
+            return pass();
+        }
+
+        let start = Instant::now();
+        let result = pass();
+        let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+        self.timings.push(PassTiming {
+            name: name.to_string(),
+            elapsed_ms,
+            functions_visited,
+        });
+        result
+    }
+
+    /// Raw per-pass timings, in the order passes ran — attachable to JSON
+    /// output so callers can compare analysis cost across module versions.
+    pub fn timings(&self) -> &[PassTiming] {
+        &self.timings
+    }
+
+    /// Timings sorted by `elapsed_ms` descending, each annotated with its
+    /// percentage of total time and functions/ms throughput.
+    pub fn report(&self) -> Vec<PassReportEntry> {
+        let total_ms: f64 = self.timings.iter().map(|t| t.elapsed_ms).sum();
+
+        let mut entries: Vec<PassReportEntry> = self
+            .timings
+            .iter()
+            .map(|t| PassReportEntry {
+                name: t.name.clone(),
+                elapsed_ms: t.elapsed_ms,
+                percent_of_total: if total_ms > 0.0 {
+                    100.0 * t.elapsed_ms / total_ms
+                } else {
+                    0.0
+                },
+                functions_per_ms: if t.elapsed_ms > 0.0 {
+                    t.functions_visited as f64 / t.elapsed_ms
+                } else {
+                    0.0
+                },
+            })
+            .collect();
+
+        entries.sort_by(|a, b| {
+            b.elapsed_ms
+                .partial_cmp(&a.elapsed_ms)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        entries
+    }
+}
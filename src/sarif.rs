@@ -0,0 +1,212 @@
+// ===== sarif.rs =====
+// src/sarif.rs
+use crate::{Capability, ModuleAnalysis, RiskLevel};
+use serde::Serialize;
+
+/// Minimal SARIF 2.1.0 log, covering just the fields CI code-scanning
+/// dashboards need to render `SecurityAnalysis` vulnerabilities/capabilities
+/// and `safety_analysis.potential_overflows` as file/line annotations. This is
+/// a new rendering subsystem alongside `print_*`, not a general-purpose SARIF
+/// writer — only the shapes these finding kinds need are modeled.
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifLog {
+    pub version: String,
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub runs: Vec<SarifRun>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifRun {
+    pub tool: SarifTool,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifTool {
+    pub driver: SarifDriver,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifDriver {
+    pub name: String,
+    pub rules: Vec<SarifRule>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifRule {
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifResult {
+    #[serde(rename = "ruleId")]
+    pub rule_id: String,
+    #[serde(rename = "ruleIndex")]
+    pub rule_index: u32,
+    pub level: String,
+    pub message: SarifMessage,
+    pub locations: Vec<SarifLocation>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifMessage {
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifLocation {
+    #[serde(rename = "physicalLocation")]
+    pub physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifPhysicalLocation {
+    #[serde(rename = "artifactLocation")]
+    pub artifact_location: SarifArtifactLocation,
+    pub region: SarifRegion,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifArtifactLocation {
+    pub uri: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifRegion {
+    #[serde(rename = "startLine")]
+    pub start_line: u32,
+}
+
+/// `Low`/`Medium` map to SARIF's `warning` level, `High`/`Critical` to `error`
+/// — there's no SARIF level with four rungs, so the two least-severe and two
+/// most-severe `RiskLevel`s double up.
+fn sarif_level(risk: &RiskLevel) -> &'static str {
+    match risk {
+        RiskLevel::Low | RiskLevel::Medium => "warning",
+        RiskLevel::High | RiskLevel::Critical => "error",
+    }
+}
+
+/// Returns the index of `id` in `rule_ids`, appending it first if this is the
+/// first time it's been seen, so every distinct rule id gets exactly one
+/// `rules` entry no matter how many results reference it.
+fn rule_index_of(rule_ids: &mut Vec<String>, id: &str) -> u32 {
+    if let Some(pos) = rule_ids.iter().position(|r| r == id) {
+        pos as u32
+    } else {
+        rule_ids.push(id.to_string());
+        (rule_ids.len() - 1) as u32
+    }
+}
+
+/// Render one `Capability` as a SARIF result, rule-keyed by its name (unlike
+/// vulnerabilities, capabilities have no shared `id` scheme to key on) with
+/// its evidence folded into the message text.
+fn capability_sarif_result(
+    rule_ids: &mut Vec<String>,
+    capability: &Capability,
+    file_path: &str,
+) -> SarifResult {
+    let rule_index = rule_index_of(rule_ids, &capability.name);
+    let text = if capability.evidence.is_empty() {
+        capability.description.clone()
+    } else {
+        format!(
+            "{} (evidence: {})",
+            capability.description,
+            capability.evidence.join(", ")
+        )
+    };
+    SarifResult {
+        rule_id: capability.name.clone(),
+        rule_index,
+        level: sarif_level(&capability.risk_level).to_string(),
+        message: SarifMessage { text },
+        locations: vec![SarifLocation {
+            physical_location: SarifPhysicalLocation {
+                artifact_location: SarifArtifactLocation {
+                    uri: file_path.to_string(),
+                },
+                region: SarifRegion { start_line: 1 },
+            },
+        }],
+    }
+}
+
+/// Build a SARIF 2.1.0 report covering `security_analysis.vulnerabilities`,
+/// `security_analysis.capabilities`, and
+/// `memory_analysis.safety_analysis.potential_overflows`, so CI code-scanning
+/// dashboards can annotate the same findings the `detailed` format prints.
+///
+/// Wasm has no source lines, so `region.startLine` encodes the defined
+/// function index (offset by one, since SARIF lines are 1-based) for overflow
+/// findings, which carry a `function_index`. Vulnerabilities and capabilities
+/// aren't tied to a single function — their free-text `location`/`evidence`
+/// is folded into the message instead, with `startLine` fixed at 1.
+pub fn build_sarif_report(analysis: &ModuleAnalysis, file_path: &str) -> SarifLog {
+    let mut rule_ids: Vec<String> = Vec::new();
+    let mut results = Vec::new();
+
+    for vuln in &analysis.security_analysis.vulnerabilities {
+        let rule_index = rule_index_of(&mut rule_ids, &vuln.id);
+        results.push(SarifResult {
+            rule_id: vuln.id.clone(),
+            rule_index,
+            level: sarif_level(&vuln.severity).to_string(),
+            message: SarifMessage {
+                text: format!("{} (at {})", vuln.description, vuln.location),
+            },
+            locations: vec![SarifLocation {
+                physical_location: SarifPhysicalLocation {
+                    artifact_location: SarifArtifactLocation {
+                        uri: file_path.to_string(),
+                    },
+                    region: SarifRegion { start_line: 1 },
+                },
+            }],
+        });
+    }
+
+    for capability in &analysis.security_analysis.capabilities {
+        results.push(capability_sarif_result(&mut rule_ids, capability, file_path));
+    }
+
+    for overflow in &analysis.memory_analysis.safety_analysis.potential_overflows {
+        let rule_index = rule_index_of(&mut rule_ids, &overflow.operation_type);
+        results.push(SarifResult {
+            rule_id: overflow.operation_type.clone(),
+            rule_index,
+            level: sarif_level(&overflow.risk_level).to_string(),
+            message: SarifMessage {
+                text: overflow.description.clone(),
+            },
+            locations: vec![SarifLocation {
+                physical_location: SarifPhysicalLocation {
+                    artifact_location: SarifArtifactLocation {
+                        uri: file_path.to_string(),
+                    },
+                    region: SarifRegion {
+                        start_line: overflow.function_index + 1,
+                    },
+                },
+            }],
+        });
+    }
+
+    let rules = rule_ids.into_iter().map(|id| SarifRule { id }).collect();
+
+    SarifLog {
+        version: "2.1.0".to_string(),
+        schema: "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json".to_string(),
+        runs: vec![SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: "wasm-inspector".to_string(),
+                    rules,
+                },
+            },
+            results,
+        }],
+    }
+}
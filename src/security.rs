@@ -2,20 +2,197 @@
 // ===== security.rs =====
 use crate::types::*;
 use anyhow::Result;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 pub struct SecurityAnalyzer<'a> {
     module_info: &'a ModuleInfo,
+    wasm_bytes: &'a [u8],
+}
+
+/// Whether an import's module name identifies it as a WASI host import: a
+/// Preview 1/legacy-unstable flat namespace (`wasi_snapshot_preview1`,
+/// `wasi_unstable`), or a Preview 2 / Component Model interface namespace
+/// (`wasi:filesystem/types`, `wasi:sockets/tcp`, ...). Shared with the
+/// conformance checker in `analyzer.rs` so "is this a WASI module" is decided
+/// in exactly one place.
+pub(crate) fn is_wasi_module(module: &str) -> bool {
+    module.starts_with("wasi_snapshot") || module.starts_with("wasi_unstable") || module.starts_with("wasi:")
+}
+
+/// The interface package of a Preview 2 / Component Model WASI import module
+/// name, e.g. `"filesystem"` for `wasi:filesystem/types`. `None` for flat
+/// Preview 1 module names, which have no `:`/`/` namespacing.
+fn wasi_interface_package(module: &str) -> Option<&str> {
+    module.strip_prefix("wasi:")?.split('/').next()
+}
+
+/// Map a Preview 2 interface import's module name to the same
+/// `required_capabilities` tag vocabulary Preview 1's flat `fd_`/`sock_`-style
+/// imports use, so `WasiUsage::required_capabilities` reads the same either way.
+fn wasi_interface_required_capability(module: &str) -> Option<&'static str> {
+    let rest = module.strip_prefix("wasi:")?;
+    let (package, interface) = rest.split_once('/').unwrap_or((rest, ""));
+    match (package, interface) {
+        ("filesystem", _) => Some("file-system"),
+        ("sockets", _) => Some("sockets"),
+        ("http", _) => Some("sockets"),
+        ("cli", "environment") => Some("environment-variables"),
+        ("cli", "exit") => Some("process-control"),
+        ("cli", _) => Some("cli"),
+        ("clocks", _) => Some("clocks"),
+        ("random", _) => Some("randomness"),
+        _ => None,
+    }
+}
+
+/// One cryptographic primitive's embedded constant table, as one or more
+/// exact byte-pattern variants — a plain byte table for the AES S-boxes, or
+/// both little- and big-endian word encodings for the 32-bit constant sets,
+/// since a statically linked implementation could emit either depending on
+/// how the source language declared them.
+struct CryptoSignature {
+    name: &'static str,
+    patterns: Vec<Vec<u8>>,
+}
+
+impl CryptoSignature {
+    /// Byte offset of the first matching pattern variant found contiguously
+    /// in `haystack`, if any.
+    fn find_in(&self, haystack: &[u8]) -> Option<usize> {
+        self.patterns.iter().find_map(|pattern| {
+            if pattern.is_empty() || pattern.len() > haystack.len() {
+                return None;
+            }
+            haystack.windows(pattern.len()).position(|w| w == pattern.as_slice())
+        })
+    }
+}
+
+/// Little-endian and big-endian byte encodings of a 32-bit word table.
+fn word_table_patterns(words: &[u32]) -> Vec<Vec<u8>> {
+    let mut little_endian = Vec::with_capacity(words.len() * 4);
+    let mut big_endian = Vec::with_capacity(words.len() * 4);
+    for &word in words {
+        little_endian.extend_from_slice(&word.to_le_bytes());
+        big_endian.extend_from_slice(&word.to_be_bytes());
+    }
+    vec![little_endian, big_endian]
+}
+
+#[rustfmt::skip]
+const AES_SBOX: [u8; 256] = [
+    0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7, 0xab, 0x76,
+    0xca, 0x82, 0xc9, 0x7d, 0xfa, 0x59, 0x47, 0xf0, 0xad, 0xd4, 0xa2, 0xaf, 0x9c, 0xa4, 0x72, 0xc0,
+    0xb7, 0xfd, 0x93, 0x26, 0x36, 0x3f, 0xf7, 0xcc, 0x34, 0xa5, 0xe5, 0xf1, 0x71, 0xd8, 0x31, 0x15,
+    0x04, 0xc7, 0x23, 0xc3, 0x18, 0x96, 0x05, 0x9a, 0x07, 0x12, 0x80, 0xe2, 0xeb, 0x27, 0xb2, 0x75,
+    0x09, 0x83, 0x2c, 0x1a, 0x1b, 0x6e, 0x5a, 0xa0, 0x52, 0x3b, 0xd6, 0xb3, 0x29, 0xe3, 0x2f, 0x84,
+    0x53, 0xd1, 0x00, 0xed, 0x20, 0xfc, 0xb1, 0x5b, 0x6a, 0xcb, 0xbe, 0x39, 0x4a, 0x4c, 0x58, 0xcf,
+    0xd0, 0xef, 0xaa, 0xfb, 0x43, 0x4d, 0x33, 0x85, 0x45, 0xf9, 0x02, 0x7f, 0x50, 0x3c, 0x9f, 0xa8,
+    0x51, 0xa3, 0x40, 0x8f, 0x92, 0x9d, 0x38, 0xf5, 0xbc, 0xb6, 0xda, 0x21, 0x10, 0xff, 0xf3, 0xd2,
+    0xcd, 0x0c, 0x13, 0xec, 0x5f, 0x97, 0x44, 0x17, 0xc4, 0xa7, 0x7e, 0x3d, 0x64, 0x5d, 0x19, 0x73,
+    0x60, 0x81, 0x4f, 0xdc, 0x22, 0x2a, 0x90, 0x88, 0x46, 0xee, 0xb8, 0x14, 0xde, 0x5e, 0x0b, 0xdb,
+    0xe0, 0x32, 0x3a, 0x0a, 0x49, 0x06, 0x24, 0x5c, 0xc2, 0xd3, 0xac, 0x62, 0x91, 0x95, 0xe4, 0x79,
+    0xe7, 0xc8, 0x37, 0x6d, 0x8d, 0xd5, 0x4e, 0xa9, 0x6c, 0x56, 0xf4, 0xea, 0x65, 0x7a, 0xae, 0x08,
+    0xba, 0x78, 0x25, 0x2e, 0x1c, 0xa6, 0xb4, 0xc6, 0xe8, 0xdd, 0x74, 0x1f, 0x4b, 0xbd, 0x8b, 0x8a,
+    0x70, 0x3e, 0xb5, 0x66, 0x48, 0x03, 0xf6, 0x0e, 0x61, 0x35, 0x57, 0xb9, 0x86, 0xc1, 0x1d, 0x9e,
+    0xe1, 0xf8, 0x98, 0x11, 0x69, 0xd9, 0x8e, 0x94, 0x9b, 0x1e, 0x87, 0xe9, 0xce, 0x55, 0x28, 0xdf,
+    0x8c, 0xa1, 0x89, 0x0d, 0xbf, 0xe6, 0x42, 0x68, 0x41, 0x99, 0x2d, 0x0f, 0xb0, 0x54, 0xbb, 0x16,
+];
+
+#[rustfmt::skip]
+const AES_INV_SBOX: [u8; 256] = [
+    0x52, 0x09, 0x6a, 0xd5, 0x30, 0x36, 0xa5, 0x38, 0xbf, 0x40, 0xa3, 0x9e, 0x81, 0xf3, 0xd7, 0xfb,
+    0x7c, 0xe3, 0x39, 0x82, 0x9b, 0x2f, 0xff, 0x87, 0x34, 0x8e, 0x43, 0x44, 0xc4, 0xde, 0xe9, 0xcb,
+    0x54, 0x7b, 0x94, 0x32, 0xa6, 0xc2, 0x23, 0x3d, 0xee, 0x4c, 0x95, 0x0b, 0x42, 0xfa, 0xc3, 0x4e,
+    0x08, 0x2e, 0xa1, 0x66, 0x28, 0xd9, 0x24, 0xb2, 0x76, 0x5b, 0xa2, 0x49, 0x6d, 0x8b, 0xd1, 0x25,
+    0x72, 0xf8, 0xf6, 0x64, 0x86, 0x68, 0x98, 0x16, 0xd4, 0xa4, 0x5c, 0xcc, 0x5d, 0x65, 0xb6, 0x92,
+    0x6c, 0x70, 0x48, 0x50, 0xfd, 0xed, 0xb9, 0xda, 0x5e, 0x15, 0x46, 0x57, 0xa7, 0x8d, 0x9d, 0x84,
+    0x90, 0xd8, 0xab, 0x00, 0x8c, 0xbc, 0xd3, 0x0a, 0xf7, 0xe4, 0x58, 0x05, 0xb8, 0xb3, 0x45, 0x06,
+    0xd0, 0x2c, 0x1e, 0x8f, 0xca, 0x3f, 0x0f, 0x02, 0xc1, 0xaf, 0xbd, 0x03, 0x01, 0x13, 0x8a, 0x6b,
+    0x3a, 0x91, 0x11, 0x41, 0x4f, 0x67, 0xdc, 0xea, 0x97, 0xf2, 0xcf, 0xce, 0xf0, 0xb4, 0xe6, 0x73,
+    0x96, 0xac, 0x74, 0x22, 0xe7, 0xad, 0x35, 0x85, 0xe2, 0xf9, 0x37, 0xe8, 0x1c, 0x75, 0xdf, 0x6e,
+    0x47, 0xf1, 0x1a, 0x71, 0x1d, 0x29, 0xc5, 0x89, 0x6f, 0xb7, 0x62, 0x0e, 0xaa, 0x18, 0xbe, 0x1b,
+    0xfc, 0x56, 0x3e, 0x4b, 0xc6, 0xd2, 0x79, 0x20, 0x9a, 0xdb, 0xc0, 0xfe, 0x78, 0xcd, 0x5a, 0xf4,
+    0x1f, 0xdd, 0xa8, 0x33, 0x88, 0x07, 0xc7, 0x31, 0xb1, 0x12, 0x10, 0x59, 0x27, 0x80, 0xec, 0x5f,
+    0x60, 0x51, 0x7f, 0xa9, 0x19, 0xb5, 0x4a, 0x0d, 0x2d, 0xe5, 0x7a, 0x9f, 0x93, 0xc9, 0x9c, 0xef,
+    0xa0, 0xe0, 0x3b, 0x4d, 0xae, 0x2a, 0xf5, 0xb0, 0xc8, 0xeb, 0xbb, 0x3c, 0x83, 0x53, 0x99, 0x61,
+    0x17, 0x2b, 0x04, 0x7e, 0xba, 0x77, 0xd6, 0x26, 0xe1, 0x69, 0x14, 0x63, 0x55, 0x21, 0x0c, 0x7d,
+];
+
+const SHA256_INITIAL_HASH: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a,
+    0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// First 8 of SHA-256's 64 round constants — enough to fingerprint the
+/// table without hardcoding the full set.
+const SHA256_ROUND_CONSTANTS_PREFIX: [u32; 8] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5,
+    0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+];
+
+const SHA1_CONSTANTS: [u32; 4] = [0x5a827999, 0x6ed9eba1, 0x8f1bbcdc, 0xca62c1d6];
+
+#[rustfmt::skip]
+const MD5_SINE_TABLE: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee,
+    0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+    0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be,
+    0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+    0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa,
+    0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+    0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed,
+    0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+    0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c,
+    0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+    0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05,
+    0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+    0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039,
+    0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+    0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1,
+    0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+/// Signature table `fingerprint_crypto_constants` scans data segments and
+/// constant globals against, one entry per recognizable algorithm.
+fn crypto_signatures() -> Vec<CryptoSignature> {
+    vec![
+        CryptoSignature {
+            name: "AES S-box",
+            patterns: vec![AES_SBOX.to_vec()],
+        },
+        CryptoSignature {
+            name: "AES inverse S-box",
+            patterns: vec![AES_INV_SBOX.to_vec()],
+        },
+        CryptoSignature {
+            name: "SHA-256",
+            patterns: {
+                let mut patterns = word_table_patterns(&SHA256_INITIAL_HASH);
+                patterns.extend(word_table_patterns(&SHA256_ROUND_CONSTANTS_PREFIX));
+                patterns
+            },
+        },
+        CryptoSignature {
+            name: "SHA-1",
+            patterns: word_table_patterns(&SHA1_CONSTANTS),
+        },
+        CryptoSignature {
+            name: "MD5",
+            patterns: word_table_patterns(&MD5_SINE_TABLE),
+        },
+    ]
 }
 
 impl<'a> SecurityAnalyzer<'a> {
-    pub fn new(module_info: &'a ModuleInfo) -> Self {
-        Self { module_info }
+    pub fn new(module_info: &'a ModuleInfo, wasm_bytes: &'a [u8]) -> Self {
+        Self { module_info, wasm_bytes }
     }
 
-    pub fn analyze(&self) -> Result<SecurityAnalysis> {
-        let capabilities = self.detect_capabilities();
-        let vulnerabilities = self.detect_vulnerabilities();
+    pub fn analyze(&self, call_graph: &CallGraph) -> Result<SecurityAnalysis> {
+        let mut capabilities = self.detect_capabilities();
+        capabilities.extend(self.fingerprint_crypto_constants());
+        let vulnerabilities = self.detect_vulnerabilities(call_graph);
         let sandbox_compatibility = self.assess_sandbox_compatibility();
         let wasi_usage = self.analyze_wasi_usage();
 
@@ -28,11 +205,12 @@ impl<'a> SecurityAnalyzer<'a> {
     }
 
     fn has_filesystem_access(&self, imports: &HashSet<(&String, &String)>) -> bool {
-        imports.iter().any(|(_, name)| {
+        imports.iter().any(|(module, name)| {
             name.contains("fd_")
                 || name.contains("path_")
                 || name.contains("file")
                 || name.contains("dir")
+                || wasi_interface_package(module.as_str()) == Some("filesystem")
         })
     }
 
@@ -56,12 +234,13 @@ impl<'a> SecurityAnalyzer<'a> {
         }
 
         // Network access
-        if imports.iter().any(|(_, name)| {
+        if imports.iter().any(|(module, name)| {
             name.contains("sock_")
                 || name.contains("poll_")
                 || name.contains("network")
                 || name.contains("tcp")
                 || name.contains("udp")
+                || matches!(wasi_interface_package(module.as_str()), Some("sockets") | Some("http"))
         }) {
             capabilities.push(Capability {
                 name: "Network Access".to_string(),
@@ -72,11 +251,12 @@ impl<'a> SecurityAnalyzer<'a> {
         }
 
         // Process/system access
-        if imports.iter().any(|(_, name)| {
+        if imports.iter().any(|(module, name)| {
             name.contains("proc_")
                 || name.contains("environ")
                 || name.contains("exit")
                 || name.contains("signal")
+                || wasi_interface_package(module.as_str()) == Some("cli")
         }) {
             capabilities.push(Capability {
                 name: "System Access".to_string(),
@@ -87,10 +267,11 @@ impl<'a> SecurityAnalyzer<'a> {
         }
 
         // Clock/time access
-        if imports
-            .iter()
-            .any(|(_, name)| name.contains("clock_") || name.contains("time"))
-        {
+        if imports.iter().any(|(module, name)| {
+            name.contains("clock_")
+                || name.contains("time")
+                || wasi_interface_package(module.as_str()) == Some("clocks")
+        }) {
             capabilities.push(Capability {
                 name: "Time Access".to_string(),
                 description: "Module can access system time".to_string(),
@@ -100,10 +281,11 @@ impl<'a> SecurityAnalyzer<'a> {
         }
 
         // Random number generation
-        if imports
-            .iter()
-            .any(|(_, name)| name.contains("random") || name.contains("rand"))
-        {
+        if imports.iter().any(|(module, name)| {
+            name.contains("random")
+                || name.contains("rand")
+                || wasi_interface_package(module.as_str()) == Some("random")
+        }) {
             capabilities.push(Capability {
                 name: "Random Generation".to_string(),
                 description: "Module can generate random numbers".to_string(),
@@ -145,10 +327,44 @@ impl<'a> SecurityAnalyzer<'a> {
             });
         }
 
+        // Re-exported host imports: a module that re-exports an imported function
+        // under its own export name lets outside callers invoke that host
+        // capability directly, bypassing whatever validation the module's own code
+        // would normally wrap around it.
+        let reexported_imports: Vec<String> = self
+            .module_info
+            .exports
+            .iter()
+            .filter(|e| e.kind == ExportKind::Function && e.points_to_import)
+            .filter_map(|e| {
+                self.function_import_at(e.resolved_index)
+                    .map(|i| format!("\"{}\" re-exports {}.{}", e.name, i.module, i.name))
+            })
+            .collect();
+
+        if !reexported_imports.is_empty() {
+            capabilities.push(Capability {
+                name: "Re-exported Host Import".to_string(),
+                description: "Module directly re-exports one or more imported host functions, widening its trust surface".to_string(),
+                risk_level: RiskLevel::High,
+                evidence: reexported_imports,
+            });
+        }
+
         capabilities
     }
 
-    fn detect_vulnerabilities(&self) -> Vec<Vulnerability> {
+    /// The `Import` for the `n`th *function* import (0-based among only
+    /// `ImportKind::Function` entries), i.e. the import at global function index `n`.
+    fn function_import_at(&self, global_func_index: u32) -> Option<&Import> {
+        self.module_info
+            .imports
+            .iter()
+            .filter(|i| matches!(i.kind, ImportKind::Function { .. }))
+            .nth(global_func_index as usize)
+    }
+
+    fn detect_vulnerabilities(&self, call_graph: &CallGraph) -> Vec<Vulnerability> {
         let mut vulnerabilities = Vec::new();
 
         // Check for unbounded memory growth
@@ -209,9 +425,321 @@ impl<'a> SecurityAnalyzer<'a> {
             });
         }
 
+        // Recursion cycles have no static depth bound, the way an interpreter
+        // like wasmi guards against with a frame-stack limit: any strongly
+        // connected component the call graph already found (self-loop or
+        // mutual cycle) is reported directly.
+        for component in &call_graph.recursive_components {
+            let names: Vec<String> = component
+                .iter()
+                .map(|&f| Self::function_label(call_graph, f))
+                .collect();
+            vulnerabilities.push(Vulnerability {
+                id: "UNBOUNDED_RECURSION".to_string(),
+                description: format!(
+                    "Recursive call cycle with no static depth bound, risking stack exhaustion: {}",
+                    names.join(", ")
+                ),
+                severity: RiskLevel::Medium,
+                location: format!("Functions: {}", names.join(", ")),
+            });
+        }
+
+        if let Some(vuln) = self.detect_stack_exhaustion_risk(call_graph) {
+            vulnerabilities.push(vuln);
+        }
+
+        if let Some(vuln) = self.detect_fs_network_exfil_risk(call_graph) {
+            vulnerabilities.push(vuln);
+        }
+
         vulnerabilities
     }
 
+    /// Whether `import` is a filesystem-read entry point: Preview 1's
+    /// `fd_read`/`path_open` (the latter being how a file gets a descriptor
+    /// to read from in the first place).
+    fn is_filesystem_read_import(import: &Import) -> bool {
+        let name = import.name.to_lowercase();
+        name.contains("fd_read") || name.contains("path_open")
+    }
+
+    /// Whether `import` can send data out of the module: Preview 1's
+    /// `sock_send`/`sock_connect`, or a host HTTP import (module or function
+    /// name mentioning `http`, the common non-WASI way engines expose
+    /// outbound requests).
+    fn is_outbound_import(import: &Import) -> bool {
+        let name = import.name.to_lowercase();
+        let module = import.module.to_lowercase();
+        name.contains("sock_send") || name.contains("sock_connect") || module.contains("http") || name.contains("http")
+    }
+
+    /// Global function indices of every function import matching `predicate`,
+    /// in the same 0-based-among-`ImportKind::Function` indexing
+    /// `function_import_at` uses.
+    fn function_import_indices(&self, predicate: impl Fn(&Import) -> bool) -> Vec<u32> {
+        let mut global_idx = 0u32;
+        let mut matches = Vec::new();
+        for import in &self.module_info.imports {
+            if let ImportKind::Function { .. } = import.kind {
+                if predicate(import) {
+                    matches.push(global_idx);
+                }
+                global_idx += 1;
+            }
+        }
+        matches
+    }
+
+    /// Raises a high-severity `FS_PLUS_NETWORK_EXFIL` finding when some
+    /// function reachable from an exported entry point can itself reach both
+    /// a filesystem-read import and an outbound-capable import — i.e. the
+    /// same code path touches both, not just independent, coincidental
+    /// co-presence of the two capabilities anywhere in the module. Modeled
+    /// on confidential-computing pipelines that read sensitive input from
+    /// the filesystem and must never let it reach the network.
+    fn detect_fs_network_exfil_risk(&self, call_graph: &CallGraph) -> Option<Vulnerability> {
+        let read_imports = self.function_import_indices(Self::is_filesystem_read_import);
+        let outbound_imports = self.function_import_indices(Self::is_outbound_import);
+        if read_imports.is_empty() || outbound_imports.is_empty() {
+            return None;
+        }
+
+        let reachable_from_entries =
+            call_graph.reachable_from(&call_graph.entry_points, Direction::Forward);
+
+        let mut confluence = None;
+        for &candidate in &reachable_from_entries {
+            let reach = call_graph.reachable_from(&[candidate], Direction::Forward);
+            let read_hit = read_imports.iter().find(|r| reach.contains(r));
+            let send_hit = outbound_imports.iter().find(|s| reach.contains(s));
+            if let (Some(&read_fn), Some(&send_fn)) = (read_hit, send_hit) {
+                confluence = Some((candidate, read_fn, send_fn));
+                break;
+            }
+        }
+        let (confluence_fn, read_fn, send_fn) = confluence?;
+
+        let entry_point = call_graph
+            .entry_points
+            .iter()
+            .find(|&&ep| call_graph.reachable_from(&[ep], Direction::Forward).contains(&confluence_fn))
+            .copied();
+
+        let confluence_label = Self::function_label(call_graph, confluence_fn);
+        let read_label = Self::function_label(call_graph, read_fn);
+        let send_label = Self::function_label(call_graph, send_fn);
+
+        let location = match entry_point {
+            Some(ep) => format!(
+                "Entry `{}` reaches `{}`, which can reach both `{}` (file read) and `{}` (outbound send)",
+                Self::function_label(call_graph, ep),
+                confluence_label,
+                read_label,
+                send_label
+            ),
+            None => format!(
+                "`{}` can reach both `{}` (file read) and `{}` (outbound send)",
+                confluence_label, read_label, send_label
+            ),
+        };
+
+        Some(Vulnerability {
+            id: "FS_PLUS_NETWORK_EXFIL".to_string(),
+            description: "Module reads local files and can send data externally from the same code path, risking data exfiltration".to_string(),
+            severity: RiskLevel::High,
+            location,
+        })
+    }
+
+    /// A defined or imported function's display name, falling back to
+    /// `func_N` for the synthetic placeholder nodes `CallGraphBuilder` can
+    /// produce for partially-resolved call sites.
+    fn function_label(call_graph: &CallGraph, function_index: u32) -> String {
+        call_graph
+            .nodes
+            .iter()
+            .find(|n| n.function_index == function_index)
+            .and_then(|n| n.demangled_name.clone().or_else(|| n.name.clone()))
+            .unwrap_or_else(|| format!("func_{}", function_index))
+    }
+
+    /// Declared params + locals for `function_index`, used as a proxy for
+    /// how many value slots one call frame holds live — the same role
+    /// `max_block_depth` plays for operand-stack footprint in
+    /// `stackdepth.rs`, but for the call stack rather than the operand stack.
+    fn frame_weight(&self, function_index: u32) -> u32 {
+        self.module_info
+            .functions
+            .iter()
+            .find(|f| f.index == function_index)
+            .map(|f| f.params.len() as u32 + f.locals.iter().map(|l| l.count).sum::<u32>())
+            .unwrap_or(0)
+    }
+
+    /// Minimum chain length (in functions) before a heavy-framed call chain
+    /// is worth flagging.
+    const STACK_RISK_MIN_DEPTH: u32 = 6;
+    /// Minimum total param/local slots summed along the chain before it's
+    /// worth flagging, alongside `STACK_RISK_MIN_DEPTH`.
+    const STACK_RISK_MIN_TOTAL_SLOTS: u32 = 256;
+
+    /// Estimates worst-case stack growth from the call graph's longest call
+    /// chain, weighted by each frame's declared param/local count, and flags
+    /// it as a `RiskLevel::Medium` vulnerability when both the chain is deep
+    /// and its frames are heavy enough to risk exhausting a bounded host
+    /// stack (e.g. wasmi's fixed-size call stack). Recursive cycles are
+    /// condensed to a single node first so the search runs over a DAG —
+    /// unbounded recursion itself is already reported separately as
+    /// `UNBOUNDED_RECURSION`.
+    fn detect_stack_exhaustion_risk(&self, call_graph: &CallGraph) -> Option<Vulnerability> {
+        let mut component_of: HashMap<u32, usize> = HashMap::new();
+        let mut components: Vec<Vec<u32>> = Vec::new();
+        for component in &call_graph.recursive_components {
+            let id = components.len();
+            for &f in component {
+                component_of.insert(f, id);
+            }
+            components.push(component.clone());
+        }
+        for node in &call_graph.nodes {
+            component_of.entry(node.function_index).or_insert_with(|| {
+                let id = components.len();
+                components.push(vec![node.function_index]);
+                id
+            });
+        }
+
+        let weights: Vec<u32> = components
+            .iter()
+            .map(|members| members.iter().map(|&f| self.frame_weight(f)).sum())
+            .collect();
+
+        let mut adj: Vec<HashSet<usize>> = vec![HashSet::new(); components.len()];
+        for edge in &call_graph.edges {
+            if let (Some(&from), Some(&to)) =
+                (component_of.get(&edge.from), component_of.get(&edge.to))
+            {
+                if from != to {
+                    adj[from].insert(to);
+                }
+            }
+        }
+        let adj: Vec<Vec<usize>> = adj.into_iter().map(|s| s.into_iter().collect()).collect();
+
+        // Condensing SCCs into single nodes always yields a DAG, so every
+        // component's longest path is well-defined — but a module with
+        // thousands of functions chained in a non-recursive sequence still
+        // produces thousands of singleton components on one DAG path, so the
+        // search itself has to be iterative rather than recursing that deep.
+        let memo = Self::longest_chain_iterative(components.len(), &adj, &weights);
+        let mut best: Option<(u32, u32, usize)> = None; // (total_weight, depth, start_component)
+        for c in 0..components.len() {
+            let (total_weight, depth, _) = memo[&c];
+            if best.map_or(true, |(w, _, _)| total_weight > w) {
+                best = Some((total_weight, depth, c));
+            }
+        }
+        let (total_weight, depth, start) = best?;
+
+        if depth < Self::STACK_RISK_MIN_DEPTH || total_weight < Self::STACK_RISK_MIN_TOTAL_SLOTS {
+            return None;
+        }
+
+        let mut path = Vec::new();
+        let mut current = start;
+        loop {
+            path.push(components[current][0]);
+            match memo[&current].2 {
+                Some(next) => current = next,
+                None => break,
+            }
+        }
+        let labels: Vec<String> = path
+            .iter()
+            .map(|&f| Self::function_label(call_graph, f))
+            .collect();
+
+        Some(Vulnerability {
+            id: "STACK_EXHAUSTION_RISK".to_string(),
+            description: format!(
+                "Longest call chain reaches {} frames with an estimated {} param/local slots live at once, which could exhaust a bounded host stack",
+                depth, total_weight
+            ),
+            severity: RiskLevel::Medium,
+            location: format!("Call chain: {}", labels.join(" -> ")),
+        })
+    }
+
+    /// Longest weighted path from every component over the condensation DAG
+    /// `adj`, returned as a `component -> (total_weight, depth,
+    /// next_component)` map, where `next_component` is the first step of that
+    /// component's best path (`None` at a sink).
+    ///
+    /// Iterative rather than a plain memoized recursive DFS: condensing SCCs
+    /// always yields a DAG so recursion would terminate, but a long
+    /// non-recursive call chain still condenses to a long chain of singleton
+    /// components, and walking it with one native stack frame per component
+    /// is exactly the unbounded-recursion risk this whole analysis exists to
+    /// flag. Instead, do an explicit-stack postorder DFS to get an order in
+    /// which every component is visited after all the components it points
+    /// to, then fold `weights`/`adj` over that order — each lookup is already
+    /// in `memo` by the time it's needed.
+    fn longest_chain_iterative(
+        num_components: usize,
+        adj: &[Vec<usize>],
+        weights: &[u32],
+    ) -> HashMap<usize, (u32, u32, Option<usize>)> {
+        struct Frame {
+            component: usize,
+            succ_idx: usize,
+        }
+
+        let mut order: Vec<usize> = Vec::with_capacity(num_components);
+        let mut visited: HashSet<usize> = HashSet::with_capacity(num_components);
+
+        for start in 0..num_components {
+            if !visited.insert(start) {
+                continue;
+            }
+            let mut work = vec![Frame {
+                component: start,
+                succ_idx: 0,
+            }];
+            while let Some(frame) = work.last_mut() {
+                if let Some(&next) = adj[frame.component].get(frame.succ_idx) {
+                    frame.succ_idx += 1;
+                    if visited.insert(next) {
+                        work.push(Frame {
+                            component: next,
+                            succ_idx: 0,
+                        });
+                    }
+                    continue;
+                }
+                order.push(frame.component);
+                work.pop();
+            }
+        }
+
+        let mut memo: HashMap<usize, (u32, u32, Option<usize>)> = HashMap::with_capacity(num_components);
+        for component in order {
+            let mut best_weight = weights[component];
+            let mut best_depth = 1;
+            let mut best_next = None;
+            for &next in &adj[component] {
+                let (next_weight, next_depth, _) = memo[&next];
+                if weights[component] + next_weight > best_weight {
+                    best_weight = weights[component] + next_weight;
+                    best_depth = 1 + next_depth;
+                    best_next = Some(next);
+                }
+            }
+            memo.insert(component, (best_weight, best_depth, best_next));
+        }
+        memo
+    }
+
     fn assess_sandbox_compatibility(&self) -> SandboxCompatibility {
         let mut restrictions = Vec::new();
         let mut browser_safe = true;
@@ -276,7 +804,7 @@ impl<'a> SecurityAnalyzer<'a> {
             .module_info
             .imports
             .iter()
-            .filter(|i| i.module.starts_with("wasi_snapshot") || i.module.starts_with("wasi_unstable")) // More specific
+            .filter(|i| is_wasi_module(&i.module))
             .collect();
 
         if wasi_imports.is_empty() {
@@ -287,7 +815,9 @@ impl<'a> SecurityAnalyzer<'a> {
             };
         }
 
-        let wasi_version = if wasi_imports.iter().any(|i| i.module == "wasi_snapshot_preview1") {
+        let wasi_version = if wasi_imports.iter().any(|i| i.module.starts_with("wasi:")) {
+            Some("Preview 2".to_string())
+        } else if wasi_imports.iter().any(|i| i.module == "wasi_snapshot_preview1") {
             Some("Preview 1".to_string())
         } else if wasi_imports.iter().any(|i| i.module.contains("preview2")) { // Future-proofing
             Some("Preview 2".to_string())
@@ -299,6 +829,11 @@ impl<'a> SecurityAnalyzer<'a> {
 
         let mut capabilities = HashSet::new();
         for import in &wasi_imports {
+            if let Some(tag) = wasi_interface_required_capability(&import.module) {
+                // Preview 2 / Component Model interface import, e.g. `wasi:filesystem/types`.
+                capabilities.insert(tag);
+                continue;
+            }
             // Simplified mapping based on common prefixes from WASI Preview 1
             if import.name.starts_with("fd_") { capabilities.insert("file-system"); }
             else if import.name.starts_with("path_") { capabilities.insert("file-system-paths"); }
@@ -324,7 +859,10 @@ impl<'a> SecurityAnalyzer<'a> {
             .imports
             .iter()
             .filter(|i| {
-                i.name.contains("fd_") || i.name.contains("path_") || i.name.contains("file")
+                i.name.contains("fd_")
+                    || i.name.contains("path_")
+                    || i.name.contains("file")
+                    || wasi_interface_package(&i.module) == Some("filesystem")
             })
             .map(|i| format!("{}::{}", i.module, i.name))
             .take(5) // Limit evidence
@@ -336,7 +874,10 @@ impl<'a> SecurityAnalyzer<'a> {
             .imports
             .iter()
             .filter(|i| {
-                i.name.contains("sock_") || i.name.contains("poll_") || i.name.contains("network")
+                i.name.contains("sock_")
+                    || i.name.contains("poll_")
+                    || i.name.contains("network")
+                    || matches!(wasi_interface_package(&i.module), Some("sockets") | Some("http"))
             })
             .map(|i| format!("{}::{}", i.module, i.name))
             .take(5)
@@ -348,7 +889,10 @@ impl<'a> SecurityAnalyzer<'a> {
             .imports
             .iter()
             .filter(|i| {
-                i.name.contains("proc_") || i.name.contains("environ") || i.name.contains("exit")
+                i.name.contains("proc_")
+                    || i.name.contains("environ")
+                    || i.name.contains("exit")
+                    || wasi_interface_package(&i.module) == Some("cli")
             })
             .map(|i| format!("{}::{}", i.module, i.name))
             .take(5)
@@ -359,7 +903,11 @@ impl<'a> SecurityAnalyzer<'a> {
         self.module_info
             .imports
             .iter()
-            .filter(|i| i.name.contains("clock_") || i.name.contains("time"))
+            .filter(|i| {
+                i.name.contains("clock_")
+                    || i.name.contains("time")
+                    || wasi_interface_package(&i.module) == Some("clocks")
+            })
             .map(|i| format!("{}::{}", i.module, i.name))
             .take(5)
             .collect()
@@ -369,7 +917,11 @@ impl<'a> SecurityAnalyzer<'a> {
         self.module_info
             .imports
             .iter()
-            .filter(|i| i.name.contains("random") || i.name.contains("rand"))
+            .filter(|i| {
+                i.name.contains("random")
+                    || i.name.contains("rand")
+                    || wasi_interface_package(&i.module) == Some("random")
+            })
             .map(|i| format!("{}::{}", i.module, i.name))
             .take(5)
             .collect()
@@ -405,6 +957,176 @@ impl<'a> SecurityAnalyzer<'a> {
             .collect()
     }
 
+    /// Turn each capability this module was observed to use into a concrete
+    /// restriction directive for a WASI-Virt-style virtualization/host
+    /// layer: deny what it doesn't need, stub what can be made
+    /// deterministic, and allow only the minimal surface it actually
+    /// exercises. Capabilities with no virtualizable host-layer equivalent
+    /// (e.g. "Dynamic Memory", "Cryptographic Operations") are omitted
+    /// rather than forced into an arbitrary directive.
+    pub fn recommend_lockdown(&self) -> LockdownPolicy {
+        let mut directives = Vec::new();
+        let mut clear_environment_variables = false;
+        let mut stubbed_clock_value = None;
+
+        for capability in self.detect_capabilities() {
+            let directive = match capability.name.as_str() {
+                "File System Access" => {
+                    if Self::filesystem_evidence_is_read_only(&capability.evidence) {
+                        LockdownDirective {
+                            capability: capability.name,
+                            restriction: CapabilityRestriction::AllowReadOnly,
+                            rationale: "Only fd_*/path_* reads were observed, so writes can be denied while reads pass through a read-only preopen".to_string(),
+                        }
+                    } else {
+                        LockdownDirective {
+                            capability: capability.name,
+                            restriction: CapabilityRestriction::Deny,
+                            rationale: "Filesystem writes were observed; deny by default and re-grant only the specific preopened directories the deployer intends".to_string(),
+                        }
+                    }
+                }
+                "Network Access" => LockdownDirective {
+                    capability: capability.name,
+                    restriction: CapabilityRestriction::Deny,
+                    rationale: "Sockets are denied by default in the lockdown profile".to_string(),
+                },
+                "System Access" => {
+                    if capability.evidence.iter().any(|e| e.contains("environ")) {
+                        clear_environment_variables = true;
+                    }
+                    LockdownDirective {
+                        capability: capability.name,
+                        restriction: CapabilityRestriction::Deny,
+                        rationale: "Process/environment access is denied; environment variables are cleared rather than forwarded".to_string(),
+                    }
+                }
+                "Time Access" => {
+                    let fixed = "1970-01-01T00:00:00Z".to_string();
+                    stubbed_clock_value = Some(fixed.clone());
+                    LockdownDirective {
+                        capability: capability.name,
+                        restriction: CapabilityRestriction::Stub,
+                        rationale: format!("Clocks are stubbed to a fixed value ({}) so the module can't fingerprint the host or use time as a side channel", fixed),
+                    }
+                }
+                "Random Generation" => LockdownDirective {
+                    capability: capability.name,
+                    restriction: CapabilityRestriction::Allow,
+                    rationale: "A secure RNG is allowed through as-is; stubbing or denying it tends to break crypto and hash-map seeding".to_string(),
+                },
+                _ => continue,
+            };
+            directives.push(directive);
+        }
+
+        LockdownPolicy {
+            directives,
+            clear_environment_variables,
+            stubbed_clock_value,
+        }
+    }
+
+    /// Whether `evidence` (as collected by `collect_filesystem_evidence`)
+    /// shows only read-style `fd_*`/`path_*` imports, with no writes,
+    /// creates, removals, or renames.
+    fn filesystem_evidence_is_read_only(evidence: &[String]) -> bool {
+        const WRITE_MARKERS: [&str; 6] = [
+            "fd_write",
+            "path_create",
+            "path_remove",
+            "path_rename",
+            "path_unlink",
+            "path_symlink",
+        ];
+        !evidence
+            .iter()
+            .any(|e| WRITE_MARKERS.iter().any(|marker| e.contains(marker)))
+    }
+
+    /// Re-parses the data section from `wasm_bytes` for each segment's raw
+    /// bytes, in declaration order matching `module_info.data_segments`,
+    /// since `DataSegment` itself only carries `size`. Returns an empty vec
+    /// on any parse failure, matching `memory::MemoryAnalyzer`'s equivalent
+    /// helper: crypto fingerprinting is a best-effort enhancement, not a
+    /// correctness-critical pass.
+    fn data_segment_raw_bytes(&self) -> Vec<Vec<u8>> {
+        use wasmparser::{Parser, Payload};
+
+        let mut bytes_by_segment = Vec::new();
+        let parser = Parser::new(0);
+        for payload_result in parser.parse_all(self.wasm_bytes) {
+            let Ok(payload) = payload_result else {
+                return Vec::new();
+            };
+            if let Payload::DataSection(reader) = payload {
+                for data_result in reader {
+                    let Ok(data) = data_result else {
+                        return Vec::new();
+                    };
+                    bytes_by_segment.push(data.data.to_vec());
+                }
+            }
+        }
+        bytes_by_segment
+    }
+
+    /// Raw bytes of every non-imported `i32`-constant global, concatenated
+    /// in declaration order, for the same byte-pattern scan
+    /// `fingerprint_crypto_constants` runs over data segments — some
+    /// toolchains emit a crypto constant table as a run of individual
+    /// globals rather than a data segment.
+    fn global_constant_bytes(&self) -> Vec<u8> {
+        self.module_info
+            .globals
+            .iter()
+            .filter(|g| !g.is_imported)
+            .filter_map(|g| match g.init_value {
+                ConstValue::I32(v) => Some(v.to_le_bytes()),
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Scans `data_segments` and constant globals for the byte patterns of
+    /// well-known cryptographic constant tables, recognizing statically
+    /// linked crypto that name-based `detect_capabilities` checks would miss
+    /// entirely (no import, no telltale symbol name). Each hit becomes a
+    /// `Capability` naming the concrete algorithm, with evidence pointing at
+    /// the segment/offset (or "globals") where the table was found.
+    fn fingerprint_crypto_constants(&self) -> Vec<Capability> {
+        let segment_bytes = self.data_segment_raw_bytes();
+        let global_bytes = self.global_constant_bytes();
+
+        let mut capabilities = Vec::new();
+        for signature in crypto_signatures() {
+            let mut evidence = Vec::new();
+
+            for (segment, bytes) in self.module_info.data_segments.iter().zip(segment_bytes.iter()) {
+                if let Some(offset) = signature.find_in(bytes) {
+                    evidence.push(format!("data segment {} at offset {}", segment.index, offset));
+                }
+            }
+            if let Some(offset) = signature.find_in(&global_bytes) {
+                evidence.push(format!("constant globals at byte offset {}", offset));
+            }
+
+            if !evidence.is_empty() {
+                capabilities.push(Capability {
+                    name: format!("Static Cryptographic Constants: {}", signature.name),
+                    description: format!(
+                        "Module embeds the well-known {} constant table, indicating the algorithm is statically linked rather than imported",
+                        signature.name
+                    ),
+                    risk_level: RiskLevel::Medium,
+                    evidence,
+                });
+            }
+        }
+        capabilities
+    }
+
     fn estimate_module_size(&self) -> u32 {
         let mut size = 0u32;
         size += self.module_info.functions.iter().map(|f| f.body_size).sum::<u32>();
@@ -0,0 +1,413 @@
+// ===== stackdepth.rs =====
+// src/stackdepth.rs
+use crate::types::*;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use wasmparser::{BlockType, Operator, Parser, Payload};
+
+/// Maximum operand-stack height one function's body reaches, in stack slots
+/// (not bytes), computed by abstract interpretation rather than approximated
+/// from declared locals.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionStackDepth {
+    pub function_index: u32,
+    pub max_stack_depth: u32,
+}
+
+/// A `block`/`loop`/`if` control frame tracked while walking a function body,
+/// recording enough state at entry to restore the stack height on `else`/`end`.
+struct Frame {
+    /// Operand-stack height when this construct's parameters (if any) were pushed.
+    entry_height: u32,
+    /// Number of values this construct's block type takes as parameters.
+    param_count: u32,
+    /// Number of values this construct leaves on the stack once it completes.
+    result_count: u32,
+}
+
+/// Walks one function body maintaining a running operand-stack height and its
+/// high-water mark. `func_arity`/`type_arity` resolve the pop/push effect of
+/// `call`/`call_indirect` and the parameter/result counts of `block`/`loop`/`if`
+/// block types, since those aren't fixed per opcode the way arithmetic ops are.
+struct StackDepthWalker<'a> {
+    height: u32,
+    max_height: u32,
+    /// Set once `br`, `br_table`, `return`, or `unreachable` makes the rest of the
+    /// current block polymorphic; cleared at the next `else`/`end`, which restores
+    /// a known height from the enclosing frame regardless.
+    unreachable: bool,
+    control_stack: Vec<Frame>,
+    func_arity: &'a HashMap<u32, (u32, u32)>,
+    type_arity: &'a HashMap<u32, (u32, u32)>,
+}
+
+impl<'a> StackDepthWalker<'a> {
+    fn new(func_arity: &'a HashMap<u32, (u32, u32)>, type_arity: &'a HashMap<u32, (u32, u32)>) -> Self {
+        Self {
+            height: 0,
+            max_height: 0,
+            unreachable: false,
+            control_stack: Vec::new(),
+            func_arity,
+            type_arity,
+        }
+    }
+
+    fn block_arity(&self, blockty: &BlockType) -> (u32, u32) {
+        match blockty {
+            BlockType::Empty => (0, 0),
+            BlockType::Type(_) => (0, 1),
+            BlockType::FuncType(idx) => self.type_arity.get(idx).copied().unwrap_or((0, 0)),
+        }
+    }
+
+    fn push_frame(&mut self, param_count: u32, result_count: u32, extra_pop: u32) {
+        self.height = self.height.saturating_sub(extra_pop);
+        let entry_height = self.height.saturating_sub(param_count);
+        self.control_stack.push(Frame {
+            entry_height,
+            param_count,
+            result_count,
+        });
+        // Params stay on the stack as the block's operands; height is unchanged
+        // by entering the block itself (only `extra_pop`, e.g. `if`'s condition,
+        // actually leaves the stack).
+    }
+
+    /// Apply a plain (non-control) opcode's pop/push effect, unless the current
+    /// position is unreachable, in which case it contributes nothing.
+    fn apply(&mut self, pop: u32, push: u32) {
+        if self.unreachable {
+            return;
+        }
+        self.height = self.height.saturating_sub(pop) + push;
+        self.max_height = self.max_height.max(self.height);
+    }
+
+    fn visit(&mut self, op: &Operator) {
+        match op {
+            Operator::Block { blockty } => {
+                let (params, results) = self.block_arity(blockty);
+                self.push_frame(params, results, 0);
+            }
+            Operator::Loop { blockty } => {
+                let (params, results) = self.block_arity(blockty);
+                self.push_frame(params, results, 0);
+            }
+            Operator::If { blockty } => {
+                let (params, results) = self.block_arity(blockty);
+                // `if` additionally consumes the condition at the top of the stack.
+                self.push_frame(params, results, 1);
+            }
+            Operator::Else => {
+                if let Some(frame) = self.control_stack.last() {
+                    self.height = frame.entry_height + frame.param_count;
+                    self.max_height = self.max_height.max(self.height);
+                }
+                self.unreachable = false;
+            }
+            Operator::End => {
+                if let Some(frame) = self.control_stack.pop() {
+                    self.height = frame.entry_height + frame.result_count;
+                    self.max_height = self.max_height.max(self.height);
+                }
+                self.unreachable = false;
+            }
+            Operator::BrIf { .. } => {
+                self.apply(1, 0);
+                self.unreachable = true;
+            }
+            Operator::BrTable { .. } => {
+                self.apply(1, 0);
+                self.unreachable = true;
+            }
+            Operator::Br { .. } | Operator::Return | Operator::Unreachable => {
+                self.unreachable = true;
+            }
+            Operator::Call { function_index } => {
+                let (pop, push) = self.func_arity.get(function_index).copied().unwrap_or((0, 0));
+                self.apply(pop, push);
+            }
+            Operator::ReturnCall { function_index } => {
+                let (pop, push) = self.func_arity.get(function_index).copied().unwrap_or((0, 0));
+                self.apply(pop, push);
+                self.unreachable = true;
+            }
+            Operator::CallIndirect { type_index, .. } => {
+                let (pop, push) = self.type_arity.get(type_index).copied().unwrap_or((0, 0));
+                // Plus the table index operand itself.
+                self.apply(pop + 1, push);
+            }
+            Operator::ReturnCallIndirect { type_index, .. } => {
+                let (pop, push) = self.type_arity.get(type_index).copied().unwrap_or((0, 0));
+                self.apply(pop + 1, push);
+                self.unreachable = true;
+            }
+            Operator::Drop => self.apply(1, 0),
+            Operator::Select | Operator::TypedSelect { .. } => self.apply(3, 1),
+            Operator::LocalGet { .. } | Operator::GlobalGet { .. } => self.apply(0, 1),
+            Operator::LocalSet { .. } | Operator::GlobalSet { .. } => self.apply(1, 0),
+            Operator::LocalTee { .. } => self.apply(1, 1),
+            Operator::I32Const { .. }
+            | Operator::I64Const { .. }
+            | Operator::F32Const { .. }
+            | Operator::F64Const { .. }
+            | Operator::RefNull { .. }
+            | Operator::RefFunc { .. }
+            | Operator::MemorySize { .. }
+            | Operator::TableSize { .. } => self.apply(0, 1),
+            Operator::RefIsNull => self.apply(1, 1),
+            Operator::MemoryGrow { .. } | Operator::TableGrow { .. } => self.apply(1, 1),
+            Operator::TableGet { .. } => self.apply(1, 1),
+            Operator::TableSet { .. } => self.apply(2, 0),
+            Operator::MemoryFill { .. } | Operator::MemoryCopy { .. } | Operator::MemoryInit { .. } => {
+                self.apply(3, 0)
+            }
+            Operator::TableFill { .. } | Operator::TableCopy { .. } | Operator::TableInit { .. } => {
+                self.apply(3, 0)
+            }
+            Operator::DataDrop { .. } | Operator::ElemDrop { .. } | Operator::Nop => {}
+            // All plain loads: one address operand consumed, one value produced.
+            Operator::I32Load { .. }
+            | Operator::I64Load { .. }
+            | Operator::F32Load { .. }
+            | Operator::F64Load { .. }
+            | Operator::I32Load8S { .. }
+            | Operator::I32Load8U { .. }
+            | Operator::I32Load16S { .. }
+            | Operator::I32Load16U { .. }
+            | Operator::I64Load8S { .. }
+            | Operator::I64Load8U { .. }
+            | Operator::I64Load16S { .. }
+            | Operator::I64Load16U { .. }
+            | Operator::I64Load32S { .. }
+            | Operator::I64Load32U { .. } => self.apply(1, 1),
+            // All plain stores: address and value operands consumed, nothing produced.
+            Operator::I32Store { .. }
+            | Operator::I64Store { .. }
+            | Operator::F32Store { .. }
+            | Operator::F64Store { .. }
+            | Operator::I32Store8 { .. }
+            | Operator::I32Store16 { .. }
+            | Operator::I64Store8 { .. }
+            | Operator::I64Store16 { .. }
+            | Operator::I64Store32 { .. } => self.apply(2, 0),
+            // Unary arithmetic/conversion/reinterpret ops: one operand, one result.
+            Operator::I32Eqz
+            | Operator::I64Eqz
+            | Operator::I32Clz
+            | Operator::I32Ctz
+            | Operator::I32Popcnt
+            | Operator::I64Clz
+            | Operator::I64Ctz
+            | Operator::I64Popcnt
+            | Operator::F32Abs
+            | Operator::F32Neg
+            | Operator::F32Ceil
+            | Operator::F32Floor
+            | Operator::F32Trunc
+            | Operator::F32Nearest
+            | Operator::F32Sqrt
+            | Operator::F64Abs
+            | Operator::F64Neg
+            | Operator::F64Ceil
+            | Operator::F64Floor
+            | Operator::F64Trunc
+            | Operator::F64Nearest
+            | Operator::F64Sqrt
+            | Operator::I32WrapI64
+            | Operator::I64ExtendI32S
+            | Operator::I64ExtendI32U
+            | Operator::I32TruncF32S
+            | Operator::I32TruncF32U
+            | Operator::I32TruncF64S
+            | Operator::I32TruncF64U
+            | Operator::I64TruncF32S
+            | Operator::I64TruncF32U
+            | Operator::I64TruncF64S
+            | Operator::I64TruncF64U
+            | Operator::F32ConvertI32S
+            | Operator::F32ConvertI32U
+            | Operator::F32ConvertI64S
+            | Operator::F32ConvertI64U
+            | Operator::F32DemoteF64
+            | Operator::F64ConvertI32S
+            | Operator::F64ConvertI32U
+            | Operator::F64ConvertI64S
+            | Operator::F64ConvertI64U
+            | Operator::F64PromoteF32
+            | Operator::I32ReinterpretF32
+            | Operator::I64ReinterpretF64
+            | Operator::F32ReinterpretI32
+            | Operator::F64ReinterpretI64
+            | Operator::I32Extend8S
+            | Operator::I32Extend16S
+            | Operator::I64Extend8S
+            | Operator::I64Extend16S
+            | Operator::I64Extend32S
+            | Operator::I32TruncSatF32S
+            | Operator::I32TruncSatF32U
+            | Operator::I32TruncSatF64S
+            | Operator::I32TruncSatF64U
+            | Operator::I64TruncSatF32S
+            | Operator::I64TruncSatF32U
+            | Operator::I64TruncSatF64S
+            | Operator::I64TruncSatF64U => self.apply(1, 1),
+            // Binary arithmetic/comparison ops: two operands, one result.
+            Operator::I32Add
+            | Operator::I32Sub
+            | Operator::I32Mul
+            | Operator::I32DivS
+            | Operator::I32DivU
+            | Operator::I32RemS
+            | Operator::I32RemU
+            | Operator::I32And
+            | Operator::I32Or
+            | Operator::I32Xor
+            | Operator::I32Shl
+            | Operator::I32ShrS
+            | Operator::I32ShrU
+            | Operator::I32Rotl
+            | Operator::I32Rotr
+            | Operator::I32Eq
+            | Operator::I32Ne
+            | Operator::I32LtS
+            | Operator::I32LtU
+            | Operator::I32GtS
+            | Operator::I32GtU
+            | Operator::I32LeS
+            | Operator::I32LeU
+            | Operator::I32GeS
+            | Operator::I32GeU
+            | Operator::I64Add
+            | Operator::I64Sub
+            | Operator::I64Mul
+            | Operator::I64DivS
+            | Operator::I64DivU
+            | Operator::I64RemS
+            | Operator::I64RemU
+            | Operator::I64And
+            | Operator::I64Or
+            | Operator::I64Xor
+            | Operator::I64Shl
+            | Operator::I64ShrS
+            | Operator::I64ShrU
+            | Operator::I64Rotl
+            | Operator::I64Rotr
+            | Operator::I64Eq
+            | Operator::I64Ne
+            | Operator::I64LtS
+            | Operator::I64LtU
+            | Operator::I64GtS
+            | Operator::I64GtU
+            | Operator::I64LeS
+            | Operator::I64LeU
+            | Operator::I64GeS
+            | Operator::I64GeU
+            | Operator::F32Eq
+            | Operator::F32Ne
+            | Operator::F32Lt
+            | Operator::F32Gt
+            | Operator::F32Le
+            | Operator::F32Ge
+            | Operator::F64Eq
+            | Operator::F64Ne
+            | Operator::F64Lt
+            | Operator::F64Gt
+            | Operator::F64Le
+            | Operator::F64Ge
+            | Operator::F32Add
+            | Operator::F32Sub
+            | Operator::F32Mul
+            | Operator::F32Div
+            | Operator::F32Min
+            | Operator::F32Max
+            | Operator::F32Copysign
+            | Operator::F64Add
+            | Operator::F64Sub
+            | Operator::F64Mul
+            | Operator::F64Div
+            | Operator::F64Min
+            | Operator::F64Max
+            | Operator::F64Copysign => self.apply(2, 1),
+            // Anything else (SIMD, threads/atomics, exception handling, GC): this
+            // inspector doesn't model their operand-stack effect precisely, so
+            // treat them as net-zero rather than risk skewing the watermark with a
+            // guess. `max_block_depth`/nesting-based heuristics remain available
+            // for modules that lean heavily on these proposals.
+            _ => {}
+        }
+    }
+}
+
+/// Global function index -> (param count, result count) for every function,
+/// imported and defined, so `call` sites can be resolved to their stack effect.
+fn func_arity(module_info: &ModuleInfo) -> HashMap<u32, (u32, u32)> {
+    let mut map = HashMap::new();
+    let mut imported_func_idx = 0u32;
+    for import in &module_info.imports {
+        if let ImportKind::Function { params, results, .. } = &import.kind {
+            map.insert(imported_func_idx, (params.len() as u32, results.len() as u32));
+            imported_func_idx += 1;
+        }
+    }
+    for func in &module_info.functions {
+        map.insert(func.index, (func.params.len() as u32, func.results.len() as u32));
+    }
+    map
+}
+
+/// Type index -> (param count, result count), for resolving `call_indirect` and
+/// block-type-by-type-index sites. Built from every function's already-resolved
+/// signature rather than re-reading the type section, since any function sharing
+/// a type index has the same param/result counts.
+fn type_arity(module_info: &ModuleInfo) -> HashMap<u32, (u32, u32)> {
+    let mut map = HashMap::new();
+    for import in &module_info.imports {
+        if let ImportKind::Function { type_index, params, results } = &import.kind {
+            map.insert(*type_index, (params.len() as u32, results.len() as u32));
+        }
+    }
+    for func in &module_info.functions {
+        map.insert(func.type_index, (func.params.len() as u32, func.results.len() as u32));
+    }
+    map
+}
+
+/// Compute the maximum operand-stack depth reached by every defined function in
+/// the module, by abstract interpretation over its operator stream rather than
+/// summing declared locals.
+pub fn analyze_all(module_info: &ModuleInfo, wasm_bytes: &[u8]) -> Result<Vec<FunctionStackDepth>> {
+    let imported_function_count = module_info
+        .imports
+        .iter()
+        .filter(|i| matches!(i.kind, ImportKind::Function { .. }))
+        .count() as u32;
+
+    let func_arity = func_arity(module_info);
+    let type_arity = type_arity(module_info);
+
+    let mut results = Vec::new();
+    let mut defined_idx_counter = 0u32;
+    let parser = Parser::new(0);
+    for payload in parser.parse_all(wasm_bytes) {
+        if let Payload::CodeSectionEntry(body) = payload? {
+            let function_index = imported_function_count + defined_idx_counter;
+            defined_idx_counter += 1;
+
+            let mut walker = StackDepthWalker::new(&func_arity, &type_arity);
+            let mut reader = body.get_operators_reader()?;
+            while !reader.eof() {
+                let op = reader.read()?;
+                walker.visit(&op);
+            }
+            results.push(FunctionStackDepth {
+                function_index,
+                max_stack_depth: walker.max_height,
+            });
+        }
+    }
+
+    Ok(results)
+}
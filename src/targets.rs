@@ -0,0 +1,504 @@
+// ===== targets.rs =====
+// src/targets.rs
+//! Data-driven deployment target evaluation. Each runtime/sandbox a module
+//! might be deployed to (wasmtime, a browser, Cloudflare Workers, ...) is
+//! described as a `TargetProfile` of hard limits rather than a bespoke
+//! `check_*_compatibility` method, so a single [`TargetProfile::evaluate`] call
+//! replaces what used to be six near-identical methods on `ModuleAnalyzer`, and
+//! callers can declare their own sandbox (a custom FaaS, an embedded runtime)
+//! as data instead of editing this crate. [`built_in_profiles`] ships the same
+//! six runtimes `CompatibilityMatrix` has always reported on, so existing
+//! behavior is preserved when no user-supplied profile is loaded.
+use crate::types::{ImportKind, ModuleInfo, ValType};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Which WASM proposals a module actually exercises, detected once per
+/// analysis from `ModuleInfo` (no raw byte re-scan needed) and shared across
+/// every profile's evaluation rather than re-derived per target.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ModuleFeatureUsage {
+    /// A memory (imported or defined) is marked `shared`.
+    pub threads: bool,
+    /// Any data/element segment is passive (`memory.init`/`table.init` style
+    /// initialization), the clearest `ModuleInfo`-visible bulk-memory signal.
+    pub bulk_memory: bool,
+    /// `v128` appears in some function's parameter or result signature.
+    pub simd: bool,
+    /// Some function (defined or imported) returns more than one value.
+    pub multi_value: bool,
+    /// `funcref`/`externref` appears in a signature, or more than one table
+    /// is declared.
+    pub reference_types: bool,
+}
+
+fn signature_has_type(types: &[&[ValType]], needle: ValType) -> bool {
+    types.iter().any(|vals| vals.iter().any(|v| *v == needle))
+}
+
+/// Inspect `module_info` for the feature proposals `TargetProfile` can gate
+/// on. Best-effort: it looks at signatures/segments already resolved by the
+/// parser rather than re-walking raw bytecode for every SIMD/atomic opcode,
+/// so it can under-detect (e.g. a `shared` memory that's never actually used
+/// atomically still counts as "uses threads", and a `v128` local that's not
+/// also a parameter or result type goes unseen).
+pub fn detect_module_features(module_info: &ModuleInfo) -> ModuleFeatureUsage {
+    let threads = module_info.memory.as_ref().is_some_and(|m| m.shared)
+        || module_info.imports.iter().any(|i| {
+            matches!(&i.kind, ImportKind::Memory { memory_type } if memory_type.shared)
+        });
+
+    let bulk_memory = module_info.data_segments.iter().any(|d| d.is_passive)
+        || module_info.element_segments.iter().any(|e| e.is_passive);
+
+    let mut value_types: Vec<&[ValType]> = Vec::new();
+    let mut multi_value = false;
+    for function in &module_info.functions {
+        value_types.push(&function.params);
+        value_types.push(&function.results);
+        if function.results.len() > 1 {
+            multi_value = true;
+        }
+    }
+    for import in &module_info.imports {
+        if let ImportKind::Function { params, results, .. } = &import.kind {
+            value_types.push(params);
+            value_types.push(results);
+            if results.len() > 1 {
+                multi_value = true;
+            }
+        }
+    }
+
+    let simd = signature_has_type(&value_types, ValType::V128);
+    let reference_types = module_info.tables.len() > 1
+        || signature_has_type(&value_types, ValType::FuncRef)
+        || signature_has_type(&value_types, ValType::ExternRef);
+
+    ModuleFeatureUsage {
+        threads,
+        bulk_memory,
+        simd,
+        multi_value,
+        reference_types,
+    }
+}
+
+/// One deployment target's hard limits. Every limit is optional (or an empty
+/// list), meaning "don't check this" — a user-supplied profile only needs to
+/// set the fields that actually constrain their sandbox.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetProfile {
+    /// Defaults to the `[target.<name>]` TOML key (or must be set explicitly
+    /// for JSON, which has no equivalent named-table shorthand) when absent.
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub wasi_allowed: bool,
+    /// Issue text to report when the module uses WASI but `wasi_allowed` is
+    /// false. Falls back to a generic message when unset.
+    #[serde(default)]
+    pub wasi_issue_text: Option<String>,
+    /// Required-feature hint to surface when the module uses WASI, whether or
+    /// not `wasi_allowed` is true (e.g. a runtime-specific flag to pass).
+    #[serde(default)]
+    pub wasi_required_feature_hint: Option<String>,
+    #[serde(default = "default_true")]
+    pub multi_memory_allowed: bool,
+    #[serde(default)]
+    pub multi_memory_issue_text: Option<String>,
+    /// Maximum initial memory, in 64KB WASM pages.
+    #[serde(default)]
+    pub max_initial_memory_pages: Option<u32>,
+    #[serde(default)]
+    pub memory_limit_issue_text: Option<String>,
+    #[serde(default)]
+    pub max_module_size_bytes: Option<u32>,
+    #[serde(default)]
+    pub module_size_issue_text: Option<String>,
+    /// Import names containing any of these substrings are flagged (e.g. a
+    /// browser sandbox rejecting `fd_`/`path_`/filesystem-flavored imports).
+    #[serde(default)]
+    pub forbidden_import_name_substrings: Vec<String>,
+    #[serde(default)]
+    pub forbidden_import_name_issue_text: Option<String>,
+    /// Import modules always rejected, regardless of `allowed_import_modules`.
+    #[serde(default)]
+    pub forbidden_import_modules: Vec<String>,
+    /// If non-empty, only imports from one of these modules are permitted.
+    #[serde(default)]
+    pub allowed_import_modules: Vec<String>,
+    /// Maximum memory, in 64KB WASM pages (as opposed to `max_initial_memory_pages`).
+    #[serde(default)]
+    pub max_maximum_memory_pages: Option<u32>,
+    #[serde(default = "default_true")]
+    pub threads_allowed: bool,
+    #[serde(default = "default_true")]
+    pub bulk_memory_allowed: bool,
+    #[serde(default = "default_true")]
+    pub simd_allowed: bool,
+    #[serde(default = "default_true")]
+    pub multi_value_allowed: bool,
+    #[serde(default = "default_true")]
+    pub reference_types_allowed: bool,
+    #[serde(default = "default_true")]
+    pub shared_memory_allowed: bool,
+    #[serde(default = "default_true")]
+    pub start_function_allowed: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// The result of evaluating one module against one [`TargetProfile`]. Mirrors
+/// [`crate::types::CompatibilityStatus`]'s shape so it can be converted 1:1.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetEvaluation {
+    pub profile_name: String,
+    pub compatible: bool,
+    pub issues: Vec<String>,
+    pub required_features: Vec<String>,
+}
+
+impl TargetProfile {
+    /// Evaluate `module_info` against this profile. `module_size_bytes` is the
+    /// caller's own size estimate (e.g. `PerformanceMetrics::module_size` or a
+    /// raw file length), and `features` is the module's detected proposal
+    /// usage (see [`detect_module_features`]) — both are computed once by the
+    /// caller and shared across every profile rather than redone per profile.
+    pub fn evaluate(
+        &self,
+        module_info: &ModuleInfo,
+        module_size_bytes: u32,
+        features: ModuleFeatureUsage,
+    ) -> TargetEvaluation {
+        let mut issues = Vec::new();
+        let mut required_features = Vec::new();
+
+        let uses_wasi = module_info.imports.iter().any(|i| i.module.starts_with("wasi"));
+        if uses_wasi {
+            if let Some(ref hint) = self.wasi_required_feature_hint {
+                required_features.push(hint.clone());
+            }
+            if !self.wasi_allowed {
+                issues.push(self.wasi_issue_text.clone().unwrap_or_else(|| {
+                    format!("module uses WASI, which {} does not support", self.name)
+                }));
+            }
+        }
+
+        if !self.multi_memory_allowed && self.declares_multiple_memories(module_info) {
+            issues.push(self.multi_memory_issue_text.clone().unwrap_or_else(|| {
+                format!("{} may not support multiple memory instances", self.name)
+            }));
+        }
+
+        if let (Some(max_pages), Some(ref memory)) =
+            (self.max_initial_memory_pages, module_info.memory.as_ref())
+        {
+            if memory.initial > max_pages {
+                issues.push(self.memory_limit_issue_text.clone().unwrap_or_else(|| {
+                    format!(
+                        "initial memory {} pages exceeds {}'s {}-page limit",
+                        memory.initial, self.name, max_pages
+                    )
+                }));
+            }
+        }
+
+        if let Some(max_size) = self.max_module_size_bytes {
+            if module_size_bytes > max_size {
+                issues.push(self.module_size_issue_text.clone().unwrap_or_else(|| {
+                    format!(
+                        "module size {} bytes exceeds {}'s {}-byte limit",
+                        module_size_bytes, self.name, max_size
+                    )
+                }));
+            }
+        }
+
+        if !self.forbidden_import_name_substrings.is_empty()
+            && module_info.imports.iter().any(|i| {
+                self.forbidden_import_name_substrings
+                    .iter()
+                    .any(|s| i.name.contains(s.as_str()))
+            })
+        {
+            issues.push(self.forbidden_import_name_issue_text.clone().unwrap_or_else(|| {
+                format!("{} does not permit filesystem-flavored imports", self.name)
+            }));
+        }
+
+        let mut rejected_modules: BTreeSet<&str> = BTreeSet::new();
+        for import in &module_info.imports {
+            let forbidden = self
+                .forbidden_import_modules
+                .iter()
+                .any(|m| m == &import.module);
+            let not_allowlisted = !self.allowed_import_modules.is_empty()
+                && !self.allowed_import_modules.iter().any(|m| m == &import.module);
+            if forbidden || not_allowlisted {
+                rejected_modules.insert(&import.module);
+            }
+        }
+        for module in rejected_modules {
+            issues.push(format!(
+                "import module \"{}\" is not permitted by {}",
+                module, self.name
+            ));
+        }
+
+        if let (Some(max_pages), Some(ref memory)) =
+            (self.max_maximum_memory_pages, module_info.memory.as_ref())
+        {
+            if memory.maximum.is_some_and(|m| m > max_pages) {
+                issues.push(format!(
+                    "maximum memory exceeds {}'s {}-page limit",
+                    self.name, max_pages
+                ));
+            }
+        }
+
+        if !self.start_function_allowed && module_info.start_function.is_some() {
+            issues.push(format!("{} does not permit a start function", self.name));
+        }
+
+        for (allowed, used, feature) in [
+            (self.threads_allowed, features.threads, "the threads proposal"),
+            (self.bulk_memory_allowed, features.bulk_memory, "the bulk-memory proposal"),
+            (self.simd_allowed, features.simd, "the SIMD proposal"),
+            (self.multi_value_allowed, features.multi_value, "the multi-value proposal"),
+            (self.reference_types_allowed, features.reference_types, "the reference-types proposal"),
+            (self.shared_memory_allowed, module_info.memory.as_ref().is_some_and(|m| m.shared), "shared memory"),
+        ] {
+            if !allowed && used {
+                issues.push(format!("module uses {}, which {} does not permit", feature, self.name));
+            }
+        }
+
+        TargetEvaluation {
+            profile_name: self.name.clone(),
+            compatible: issues.is_empty(),
+            issues,
+            required_features,
+        }
+    }
+
+    fn declares_multiple_memories(&self, module_info: &ModuleInfo) -> bool {
+        module_info.memory.is_some()
+            && module_info
+                .imports
+                .iter()
+                .any(|i| matches!(i.kind, ImportKind::Memory { .. }))
+    }
+}
+
+/// The six runtimes `CompatibilityMatrix` has always reported on, as data.
+pub fn built_in_profiles() -> Vec<TargetProfile> {
+    vec![
+        TargetProfile {
+            name: "wasmtime".to_string(),
+            wasi_allowed: true,
+            wasi_issue_text: None,
+            wasi_required_feature_hint: Some("WASI support".to_string()),
+            multi_memory_allowed: false,
+            multi_memory_issue_text: Some("Multiple memory instances may not be supported".to_string()),
+            max_initial_memory_pages: None,
+            memory_limit_issue_text: None,
+            max_module_size_bytes: None,
+            module_size_issue_text: None,
+            forbidden_import_name_substrings: Vec::new(),
+            forbidden_import_name_issue_text: None,
+            forbidden_import_modules: Vec::new(),
+            allowed_import_modules: Vec::new(),
+            max_maximum_memory_pages: None,
+            threads_allowed: true,
+            bulk_memory_allowed: true,
+            simd_allowed: true,
+            multi_value_allowed: true,
+            reference_types_allowed: true,
+            shared_memory_allowed: true,
+            start_function_allowed: true,
+        },
+        TargetProfile {
+            name: "wasmer".to_string(),
+            wasi_allowed: true,
+            wasi_issue_text: None,
+            wasi_required_feature_hint: Some("WASI support".to_string()),
+            multi_memory_allowed: true,
+            multi_memory_issue_text: None,
+            max_initial_memory_pages: None,
+            memory_limit_issue_text: None,
+            max_module_size_bytes: None,
+            module_size_issue_text: None,
+            forbidden_import_name_substrings: Vec::new(),
+            forbidden_import_name_issue_text: None,
+            forbidden_import_modules: Vec::new(),
+            allowed_import_modules: Vec::new(),
+            max_maximum_memory_pages: None,
+            threads_allowed: true,
+            bulk_memory_allowed: true,
+            simd_allowed: true,
+            multi_value_allowed: true,
+            reference_types_allowed: true,
+            shared_memory_allowed: true,
+            start_function_allowed: true,
+        },
+        TargetProfile {
+            name: "browser".to_string(),
+            wasi_allowed: false,
+            wasi_issue_text: Some("WASI imports require polyfill in browser".to_string()),
+            wasi_required_feature_hint: Some("WASI polyfill".to_string()),
+            multi_memory_allowed: true,
+            multi_memory_issue_text: None,
+            max_initial_memory_pages: Some(1000), // > ~64MB
+            memory_limit_issue_text: Some("Large initial memory allocation may fail in browser".to_string()),
+            max_module_size_bytes: None,
+            module_size_issue_text: None,
+            forbidden_import_name_substrings: vec![
+                "fd_".to_string(),
+                "path_".to_string(),
+                "file".to_string(),
+            ],
+            forbidden_import_name_issue_text: Some(
+                "File system access not available in browser sandbox".to_string(),
+            ),
+            forbidden_import_modules: Vec::new(),
+            allowed_import_modules: Vec::new(),
+            max_maximum_memory_pages: None,
+            threads_allowed: true,
+            bulk_memory_allowed: true,
+            simd_allowed: true,
+            multi_value_allowed: true,
+            reference_types_allowed: true,
+            shared_memory_allowed: true,
+            start_function_allowed: true,
+        },
+        TargetProfile {
+            name: "node_js".to_string(),
+            wasi_allowed: true,
+            wasi_issue_text: None,
+            wasi_required_feature_hint: Some(
+                "Node.js WASI support (--experimental-wasi-unstable-preview1)".to_string(),
+            ),
+            multi_memory_allowed: true,
+            multi_memory_issue_text: None,
+            max_initial_memory_pages: None,
+            memory_limit_issue_text: None,
+            max_module_size_bytes: None,
+            module_size_issue_text: None,
+            forbidden_import_name_substrings: Vec::new(),
+            forbidden_import_name_issue_text: None,
+            forbidden_import_modules: Vec::new(),
+            allowed_import_modules: Vec::new(),
+            max_maximum_memory_pages: None,
+            threads_allowed: true,
+            bulk_memory_allowed: true,
+            simd_allowed: true,
+            multi_value_allowed: true,
+            reference_types_allowed: true,
+            shared_memory_allowed: true,
+            start_function_allowed: true,
+        },
+        TargetProfile {
+            name: "deno".to_string(),
+            wasi_allowed: true,
+            wasi_issue_text: None,
+            wasi_required_feature_hint: Some(
+                "Deno WASI support (--allow-read, --allow-write flags)".to_string(),
+            ),
+            multi_memory_allowed: true,
+            multi_memory_issue_text: None,
+            max_initial_memory_pages: None,
+            memory_limit_issue_text: None,
+            max_module_size_bytes: None,
+            module_size_issue_text: None,
+            forbidden_import_name_substrings: Vec::new(),
+            forbidden_import_name_issue_text: None,
+            forbidden_import_modules: Vec::new(),
+            allowed_import_modules: Vec::new(),
+            max_maximum_memory_pages: None,
+            threads_allowed: true,
+            bulk_memory_allowed: true,
+            simd_allowed: true,
+            multi_value_allowed: true,
+            reference_types_allowed: true,
+            shared_memory_allowed: true,
+            start_function_allowed: true,
+        },
+        TargetProfile {
+            name: "cloudflare_workers".to_string(),
+            wasi_allowed: false,
+            wasi_issue_text: Some("WASI not supported in Cloudflare Workers".to_string()),
+            wasi_required_feature_hint: None,
+            multi_memory_allowed: true,
+            multi_memory_issue_text: None,
+            max_initial_memory_pages: Some(128), // > ~8MB
+            memory_limit_issue_text: Some("Memory limit exceeded for Cloudflare Workers".to_string()),
+            max_module_size_bytes: Some(1_000_000), // 1MB compressed
+            module_size_issue_text: Some("Module may exceed Cloudflare Workers size limit".to_string()),
+            forbidden_import_name_substrings: Vec::new(),
+            forbidden_import_name_issue_text: None,
+            forbidden_import_modules: Vec::new(),
+            allowed_import_modules: Vec::new(),
+            max_maximum_memory_pages: None,
+            threads_allowed: true,
+            bulk_memory_allowed: true,
+            simd_allowed: true,
+            multi_value_allowed: true,
+            reference_types_allowed: true,
+            shared_memory_allowed: true,
+            start_function_allowed: true,
+        },
+    ]
+}
+
+/// Parse a user-supplied list of additional/overriding profiles from a JSON
+/// array of `TargetProfile` objects.
+pub fn load_profiles_from_json(contents: &str) -> anyhow::Result<Vec<TargetProfile>> {
+    Ok(serde_json::from_str(contents)?)
+}
+
+/// Parse a user-supplied list of additional/overriding profiles from a TOML
+/// document of `[target.<name>]` tables, in the spirit of a `wrangler.toml`-
+/// style deployment manifest. A profile's `name` field is optional and
+/// defaults to its TOML key, e.g. `[target.my_faas]` needs no `name = ...`
+/// line of its own.
+pub fn load_profiles_from_toml(contents: &str) -> anyhow::Result<Vec<TargetProfile>> {
+    #[derive(Deserialize)]
+    struct ProfileFile {
+        #[serde(default)]
+        target: BTreeMap<String, TargetProfile>,
+    }
+    let parsed: ProfileFile = toml::from_str(contents)?;
+    Ok(parsed
+        .target
+        .into_iter()
+        .map(|(key, mut profile)| {
+            if profile.name.is_empty() {
+                profile.name = key;
+            }
+            profile
+        })
+        .collect())
+}
+
+/// Evaluate a module against the built-in profiles plus any user-supplied
+/// ones, with user profiles replacing a built-in of the same name.
+pub fn evaluate_all(
+    module_info: &ModuleInfo,
+    module_size_bytes: u32,
+    user_profiles: &[TargetProfile],
+) -> Vec<TargetEvaluation> {
+    let mut profiles = built_in_profiles();
+    for user_profile in user_profiles {
+        profiles.retain(|p| p.name != user_profile.name);
+        profiles.push(user_profile.clone());
+    }
+    let features = detect_module_features(module_info);
+    profiles
+        .iter()
+        .map(|p| p.evaluate(module_info, module_size_bytes, features))
+        .collect()
+}
@@ -0,0 +1,762 @@
+// ===== transform.rs =====
+// src/transform.rs
+//! Round-trip re-encoding: decode a module with `wasmparser`, apply a handful of
+//! composable `ModuleTransform` passes, and re-emit a fresh, validated binary with
+//! `wasm-encoder`. Sections no pass touches are copied through verbatim as raw
+//! bytes; only the sections a pass actually changes are rebuilt.
+use crate::policy::{Policy, PolicyDecision};
+use crate::types::*;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use wasm_encoder::{
+    CodeSection, CustomSection, EntityType, ExportKind as EncExportKind, ExportSection, Function,
+    FunctionSection, ImportSection, Instruction, Module as EncodedModule, RawSection,
+    StartSection,
+};
+use wasmparser::{ExternalKind, Operator, Parser, Payload};
+
+/// A single, composable transformation applied by `re_encode`. Passes are applied
+/// together in one re-encoding walk, so `&[StripCustomSections,
+/// RemoveUnreachableFunctions(dead)]` strips names *and* drops dead code in a single
+/// re-emit rather than two separate round-trips.
+pub enum ModuleTransform {
+    /// Drop every custom section (debug names, producers, build-id, etc.).
+    StripCustomSections,
+    /// Keep only custom sections whose name is in this list, dropping all others.
+    KeepOnlyCustom(Vec<String>),
+    /// Drop the function bodies at these *global* function indices (as reported by
+    /// `CallGraph::unreachable_functions`) and renumber every remaining reference
+    /// (calls, exports, the start function) to match.
+    RemoveUnreachableFunctions(Vec<u32>),
+}
+
+/// Re-encode `original_bytes`, applying every pass in `passes`, and validate the
+/// result before returning it. The output is guaranteed to be a well-formed module
+/// that `wasmparser::Validator` accepts, or an error is returned instead.
+pub fn re_encode(original_bytes: &[u8], passes: &[ModuleTransform]) -> Result<Vec<u8>> {
+    let mut strip_custom = false;
+    let mut keep_only_custom: Option<HashSet<String>> = None;
+    let mut removed_functions: HashSet<u32> = HashSet::new();
+
+    for pass in passes {
+        match pass {
+            ModuleTransform::StripCustomSections => strip_custom = true,
+            ModuleTransform::KeepOnlyCustom(names) => {
+                keep_only_custom
+                    .get_or_insert_with(HashSet::new)
+                    .extend(names.iter().cloned());
+            }
+            ModuleTransform::RemoveUnreachableFunctions(indices) => {
+                removed_functions.extend(indices.iter().copied());
+            }
+        }
+    }
+
+    let imported_function_count = count_imported_functions(original_bytes)?;
+    if !removed_functions.is_empty() && has_element_section(original_bytes)? {
+        // An element segment's function references would go stale (or point at the
+        // wrong function) once indices shift under it, and we have no remap logic
+        // for element-segment contents yet. Refuse rather than emit a module that
+        // fails validation or, worse, calls the wrong function through a table.
+        return Err(anyhow!(
+            "RemoveUnreachableFunctions does not yet support modules with an element section"
+        ));
+    }
+    let func_remap = build_function_remap(imported_function_count, &removed_functions);
+
+    let mut module = EncodedModule::new();
+    let mut defined_func_idx: u32 = 0;
+    let mut code_section = CodeSection::new();
+    let mut code_entries_remaining: u32 = 0;
+
+    for payload in Parser::new(0).parse_all(original_bytes) {
+        match payload? {
+            Payload::Version { .. } | Payload::End(_) => {}
+
+            Payload::CustomSection(reader) => {
+                if strip_custom {
+                    continue;
+                }
+                if let Some(ref keep) = keep_only_custom {
+                    if !keep.contains(reader.name()) {
+                        continue;
+                    }
+                }
+                module.section(&CustomSection {
+                    name: reader.name().into(),
+                    data: reader.data().into(),
+                });
+            }
+
+            Payload::FunctionSection(reader) => {
+                let mut section = FunctionSection::new();
+                for type_index in reader {
+                    let global_idx = imported_function_count + defined_func_idx;
+                    defined_func_idx += 1;
+                    if func_remap.get(global_idx).is_some() {
+                        section.function(type_index?);
+                    }
+                }
+                module.section(&section);
+                defined_func_idx = 0; // CodeSectionEntry below re-derives the same indices
+            }
+
+            Payload::ExportSection(reader) => {
+                let mut section = ExportSection::new();
+                for export in reader {
+                    let export = export?;
+                    let (kind, index) = match export.kind {
+                        ExternalKind::Func => (
+                            EncExportKind::Func,
+                            func_remap.get(export.index).ok_or_else(|| {
+                                anyhow!(
+                                    "export \"{}\" references removed function {}",
+                                    export.name,
+                                    export.index
+                                )
+                            })?,
+                        ),
+                        ExternalKind::Table => (EncExportKind::Table, export.index),
+                        ExternalKind::Memory => (EncExportKind::Memory, export.index),
+                        ExternalKind::Global => (EncExportKind::Global, export.index),
+                        ExternalKind::Tag => (EncExportKind::Tag, export.index),
+                    };
+                    section.export(export.name, kind, index);
+                }
+                module.section(&section);
+            }
+
+            Payload::StartSection { func, .. } => {
+                let remapped = func_remap
+                    .get(func)
+                    .ok_or_else(|| anyhow!("start function {} was marked as unreachable", func))?;
+                module.section(&StartSection {
+                    function_index: remapped,
+                });
+            }
+
+            Payload::CodeSectionStart { count, .. } => {
+                code_entries_remaining = count;
+            }
+
+            Payload::CodeSectionEntry(body) => {
+                let global_idx = imported_function_count + defined_func_idx;
+                defined_func_idx += 1;
+                code_entries_remaining -= 1;
+
+                if func_remap.get(global_idx).is_some() {
+                    let locals: Vec<(u32, wasm_encoder::ValType)> = body
+                        .get_locals_reader()?
+                        .into_iter()
+                        .map(|local| local.map(|(count, ty)| (count, convert_val_type(ty))))
+                        .collect::<std::result::Result<_, _>>()?;
+                    let mut function = Function::new(locals);
+
+                    let mut ops = body.get_operators_reader()?;
+                    while !ops.eof() {
+                        let op = ops.read()?;
+                        function.instruction(&remap_instruction(op, &func_remap)?);
+                    }
+                    code_section.function(&function);
+                }
+
+                if code_entries_remaining == 0 && !code_section.is_empty() {
+                    module.section(&code_section);
+                }
+            }
+
+            payload => {
+                if let Some((id, range)) = raw_section_of(&payload) {
+                    module.section(&RawSection {
+                        id,
+                        data: &original_bytes[range],
+                    });
+                }
+            }
+        }
+    }
+
+    let bytes = module.finish();
+    wasmparser::Validator::new().validate_all(&bytes)?;
+    Ok(bytes)
+}
+
+/// Identity-or-not function index remap built from the set of removed defined
+/// functions: every surviving function keeps its relative order, just shifted down
+/// by however many removed functions preceded it.
+struct FunctionRemap {
+    old_to_new: HashMap<u32, u32>,
+}
+
+impl FunctionRemap {
+    /// Returns `None` if `old_index` was itself removed, `Some(new_index)` otherwise
+    /// (the identity mapping when no functions were removed at all).
+    fn get(&self, old_index: u32) -> Option<u32> {
+        if self.old_to_new.is_empty() {
+            return Some(old_index);
+        }
+        self.old_to_new.get(&old_index).copied()
+    }
+}
+
+fn build_function_remap(imported_function_count: u32, removed: &HashSet<u32>) -> FunctionRemap {
+    if removed.is_empty() {
+        return FunctionRemap {
+            old_to_new: HashMap::new(),
+        };
+    }
+
+    let mut old_to_new = HashMap::new();
+    let mut next_new_idx = 0u32;
+    // Imported functions are never removed and keep their indices.
+    for old in 0..imported_function_count {
+        old_to_new.insert(old, old);
+        next_new_idx = old + 1;
+    }
+    let max_old = removed.iter().copied().max().unwrap_or(0).max(next_new_idx);
+    for old in imported_function_count..=max_old {
+        if removed.contains(&old) {
+            continue;
+        }
+        old_to_new.insert(old, next_new_idx);
+        next_new_idx += 1;
+    }
+    FunctionRemap { old_to_new }
+}
+
+fn count_imported_functions(bytes: &[u8]) -> Result<u32> {
+    let mut count = 0u32;
+    for payload in Parser::new(0).parse_all(bytes) {
+        if let Payload::ImportSection(reader) = payload? {
+            for import in reader {
+                if matches!(import?.ty, wasmparser::TypeRef::Func(_)) {
+                    count += 1;
+                }
+            }
+        }
+    }
+    Ok(count)
+}
+
+fn has_element_section(bytes: &[u8]) -> Result<bool> {
+    for payload in Parser::new(0).parse_all(bytes) {
+        if matches!(payload?, Payload::ElementSection(_)) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn convert_val_type(ty: wasmparser::ValType) -> wasm_encoder::ValType {
+    match ty {
+        wasmparser::ValType::I32 => wasm_encoder::ValType::I32,
+        wasmparser::ValType::I64 => wasm_encoder::ValType::I64,
+        wasmparser::ValType::F32 => wasm_encoder::ValType::F32,
+        wasmparser::ValType::F64 => wasm_encoder::ValType::F64,
+        wasmparser::ValType::V128 => wasm_encoder::ValType::V128,
+        wasmparser::ValType::Ref(r) => wasm_encoder::ValType::Ref(if r.is_func_ref() {
+            wasm_encoder::RefType::FUNCREF
+        } else {
+            wasm_encoder::RefType::EXTERNREF
+        }),
+    }
+}
+
+/// Translate one decoded operator into its `wasm-encoder` equivalent, remapping the
+/// function index of `call` through `remap`. Only covers the core MVP instruction
+/// set plus the bulk-memory ops this crate already understands elsewhere
+/// (`memory.copy`/`memory.fill`); an exotic operator (SIMD, reference-types tables,
+/// GC, etc.) is reported as an error rather than silently mistranslated, since
+/// `RemoveUnreachableFunctions` is the only pass that needs to touch function bodies
+/// at all.
+fn remap_instruction<'a>(op: Operator<'a>, remap: &FunctionRemap) -> Result<Instruction<'a>> {
+    use Instruction as I;
+    Ok(match op {
+        Operator::Unreachable => I::Unreachable,
+        Operator::Nop => I::Nop,
+        Operator::Block { blockty } => I::Block(convert_block_type(blockty)),
+        Operator::Loop { blockty } => I::Loop(convert_block_type(blockty)),
+        Operator::If { blockty } => I::If(convert_block_type(blockty)),
+        Operator::Else => I::Else,
+        Operator::End => I::End,
+        Operator::Br { relative_depth } => I::Br(relative_depth),
+        Operator::BrIf { relative_depth } => I::BrIf(relative_depth),
+        Operator::BrTable { targets } => {
+            let default = targets.default();
+            let arms = targets
+                .targets()
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            I::BrTable(arms.into(), default)
+        }
+        Operator::Return => I::Return,
+        Operator::Call { function_index } => I::Call(
+            remap
+                .get(function_index)
+                .ok_or_else(|| anyhow!("call to removed function {}", function_index))?,
+        ),
+        Operator::CallIndirect {
+            type_index,
+            table_index,
+            ..
+        } => I::CallIndirect {
+            type_index,
+            table_index,
+        },
+        Operator::Drop => I::Drop,
+        Operator::Select => I::Select,
+        Operator::LocalGet { local_index } => I::LocalGet(local_index),
+        Operator::LocalSet { local_index } => I::LocalSet(local_index),
+        Operator::LocalTee { local_index } => I::LocalTee(local_index),
+        Operator::GlobalGet { global_index } => I::GlobalGet(global_index),
+        Operator::GlobalSet { global_index } => I::GlobalSet(global_index),
+        Operator::MemorySize { .. } => I::MemorySize(0),
+        Operator::MemoryGrow { .. } => I::MemoryGrow(0),
+        Operator::MemoryCopy { dst_mem, src_mem } => I::MemoryCopy { dst_mem, src_mem },
+        Operator::MemoryFill { mem } => I::MemoryFill(mem),
+        Operator::I32Const { value } => I::I32Const(value),
+        Operator::I64Const { value } => I::I64Const(value),
+        Operator::F32Const { value } => I::F32Const(f32::from_bits(value.bits())),
+        Operator::F64Const { value } => I::F64Const(f64::from_bits(value.bits())),
+        Operator::I32Load { memarg } => I::I32Load(convert_memarg(memarg)),
+        Operator::I64Load { memarg } => I::I64Load(convert_memarg(memarg)),
+        Operator::F32Load { memarg } => I::F32Load(convert_memarg(memarg)),
+        Operator::F64Load { memarg } => I::F64Load(convert_memarg(memarg)),
+        Operator::I32Store { memarg } => I::I32Store(convert_memarg(memarg)),
+        Operator::I64Store { memarg } => I::I64Store(convert_memarg(memarg)),
+        Operator::F32Store { memarg } => I::F32Store(convert_memarg(memarg)),
+        Operator::F64Store { memarg } => I::F64Store(convert_memarg(memarg)),
+        Operator::I32Eqz => I::I32Eqz,
+        Operator::I32Eq => I::I32Eq,
+        Operator::I32Ne => I::I32Ne,
+        Operator::I32LtS => I::I32LtS,
+        Operator::I32LtU => I::I32LtU,
+        Operator::I32GtS => I::I32GtS,
+        Operator::I32GtU => I::I32GtU,
+        Operator::I32LeS => I::I32LeS,
+        Operator::I32LeU => I::I32LeU,
+        Operator::I32GeS => I::I32GeS,
+        Operator::I32GeU => I::I32GeU,
+        Operator::I32Add => I::I32Add,
+        Operator::I32Sub => I::I32Sub,
+        Operator::I32Mul => I::I32Mul,
+        Operator::I32DivS => I::I32DivS,
+        Operator::I32DivU => I::I32DivU,
+        Operator::I32RemS => I::I32RemS,
+        Operator::I32RemU => I::I32RemU,
+        Operator::I32And => I::I32And,
+        Operator::I32Or => I::I32Or,
+        Operator::I32Xor => I::I32Xor,
+        Operator::I64Add => I::I64Add,
+        Operator::I64Sub => I::I64Sub,
+        Operator::I64Mul => I::I64Mul,
+        other => {
+            return Err(anyhow!(
+                "RemoveUnreachableFunctions does not yet support the `{:?}` instruction",
+                other
+            ))
+        }
+    })
+}
+
+fn convert_block_type(ty: wasmparser::BlockType) -> wasm_encoder::BlockType {
+    match ty {
+        wasmparser::BlockType::Empty => wasm_encoder::BlockType::Empty,
+        wasmparser::BlockType::Type(t) => wasm_encoder::BlockType::Result(convert_val_type(t)),
+        wasmparser::BlockType::FuncType(idx) => wasm_encoder::BlockType::FunctionType(idx),
+    }
+}
+
+fn convert_memarg(memarg: wasmparser::MemArg) -> wasm_encoder::MemArg {
+    wasm_encoder::MemArg {
+        offset: memarg.offset,
+        align: memarg.align as u32,
+        memory_index: memarg.memory,
+    }
+}
+
+/// For a payload not already special-cased above, the raw `(section_id, byte_range)`
+/// to copy verbatim from the original binary.
+fn raw_section_of(payload: &Payload) -> Option<(u8, std::ops::Range<usize>)> {
+    match payload {
+        Payload::TypeSection(r) => Some((1, r.range())),
+        Payload::ImportSection(r) => Some((2, r.range())),
+        Payload::TableSection(r) => Some((4, r.range())),
+        Payload::MemorySection(r) => Some((5, r.range())),
+        Payload::GlobalSection(r) => Some((6, r.range())),
+        Payload::ElementSection(r) => Some((9, r.range())),
+        Payload::DataCountSection { range, .. } => Some((12, range.clone())),
+        Payload::DataSection(r) => Some((11, r.range())),
+        _ => None,
+    }
+}
+
+/// Run `RemoveUnreachableFunctions` using a module's own computed `CallGraph`. A
+/// thin convenience over `re_encode` for the common "strip this analysis's dead
+/// code" case.
+pub fn strip_unreachable_functions(original_bytes: &[u8], call_graph: &CallGraph) -> Result<Vec<u8>> {
+    re_encode(
+        original_bytes,
+        &[ModuleTransform::RemoveUnreachableFunctions(
+            call_graph.unreachable_functions.clone(),
+        )],
+    )
+}
+
+/// How `harden` neutralized one policy-denied import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HardenAction {
+    /// Dropped from the import section entirely — nothing in the module ever called it.
+    Removed,
+    /// Kept callable (everything still targeting it was renumbered to match), but
+    /// replaced by a locally-defined function of the same signature whose body is
+    /// just `unreachable`.
+    Stubbed,
+}
+
+/// One import `harden` changed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HardenedImport {
+    pub module: String,
+    pub name: String,
+    pub action: HardenAction,
+}
+
+/// What `harden` did to a module.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HardenReport {
+    pub changes: Vec<HardenedImport>,
+}
+
+/// Produce a version of `original_bytes` safe to instantiate under `policy`: every
+/// function import `policy` denies is either dropped (if nothing calls it) or
+/// replaced by a same-signature stub that immediately traps (`unreachable`), with
+/// every call site, export, and the start function renumbered to match. Unlike
+/// `RemoveUnreachableFunctions`, this pass shrinks the *import* section, so the
+/// whole function index space shifts, not just the tail of it.
+pub fn harden(original_bytes: &[u8], policy: &Policy) -> Result<(Vec<u8>, HardenReport)> {
+    if has_element_section(original_bytes)? {
+        // Table-driven (`call_indirect`) call sites go through element-segment
+        // contents we don't have a remap for yet (see `re_encode`'s identical
+        // guard), so a denied import referenced only from a table could silently
+        // keep working, or worse, the table could end up pointing at the wrong
+        // function after the index shift.
+        return Err(anyhow!(
+            "harden does not yet support modules with an element section"
+        ));
+    }
+
+    let module_info = crate::parser::WasmParser::new(original_bytes)?.parse()?.into_module()?;
+    let imported_function_count = count_imported_functions(original_bytes)?;
+
+    let mut denied: HashMap<u32, (String, String, u32)> = HashMap::new(); // global func idx -> (module, name, type_index)
+    let mut func_import_idx = 0u32;
+    for import in &module_info.imports {
+        if let ImportKind::Function { type_index, .. } = &import.kind {
+            if policy.decide(&import.module, &import.name) == PolicyDecision::Deny {
+                denied.insert(func_import_idx, (import.module.clone(), import.name.clone(), *type_index));
+            }
+            func_import_idx += 1;
+        }
+    }
+
+    if denied.is_empty() {
+        return Ok((original_bytes.to_vec(), HardenReport { changes: Vec::new() }));
+    }
+
+    // An import still needs a callable stand-in if anything targets it directly by
+    // function index: a `call` site, an export, or the start function.
+    let directly_referenced: HashSet<u32> = module_info
+        .function_call_instructions
+        .iter()
+        .map(|&(_, callee)| callee)
+        .chain(
+            module_info
+                .exports
+                .iter()
+                .filter(|e| e.kind == ExportKind::Function)
+                .map(|e| e.index),
+        )
+        .chain(module_info.start_function)
+        .collect();
+
+    let mut denied_indices: Vec<u32> = denied.keys().copied().collect();
+    denied_indices.sort_unstable();
+
+    let mut changes = Vec::new();
+    let mut neutralized: HashSet<u32> = HashSet::new();
+    let mut to_stub: Vec<(u32, u32)> = Vec::new(); // (old global idx, type_index), ascending
+    for old_idx in denied_indices {
+        let (module, name, type_index) = &denied[&old_idx];
+        neutralized.insert(old_idx);
+        if directly_referenced.contains(&old_idx) {
+            to_stub.push((old_idx, *type_index));
+            changes.push(HardenedImport {
+                module: module.clone(),
+                name: name.clone(),
+                action: HardenAction::Stubbed,
+            });
+        } else {
+            changes.push(HardenedImport {
+                module: module.clone(),
+                name: name.clone(),
+                action: HardenAction::Removed,
+            });
+        }
+    }
+
+    let func_remap = build_harden_remap(
+        imported_function_count,
+        module_info.functions.len() as u32,
+        &neutralized,
+        &to_stub,
+    );
+
+    let bytes = encode_hardened(original_bytes, &neutralized, &to_stub, &func_remap)?;
+    Ok((bytes, HardenReport { changes }))
+}
+
+/// Build the old-index -> new-index map for `harden`: surviving imports keep their
+/// relative order (just shifted down by however many neutralized imports precede
+/// them), defined functions shift down by the total neutralized count, and the new
+/// stub functions are appended after everything else, in ascending old-index order.
+fn build_harden_remap(
+    imported_function_count: u32,
+    defined_function_count: u32,
+    neutralized: &HashSet<u32>,
+    to_stub: &[(u32, u32)],
+) -> FunctionRemap {
+    let mut old_to_new = HashMap::new();
+    let mut next_new_idx = 0u32;
+
+    for old in 0..imported_function_count {
+        if neutralized.contains(&old) {
+            continue;
+        }
+        old_to_new.insert(old, next_new_idx);
+        next_new_idx += 1;
+    }
+
+    for old in imported_function_count..(imported_function_count + defined_function_count) {
+        old_to_new.insert(old, next_new_idx);
+        next_new_idx += 1;
+    }
+
+    for &(old, _) in to_stub {
+        old_to_new.insert(old, next_new_idx);
+        next_new_idx += 1;
+    }
+
+    FunctionRemap { old_to_new }
+}
+
+/// Re-emit `original_bytes` with `neutralized` imports dropped from the import
+/// section, a trapping stub appended for each `(old_idx, type_index)` in `to_stub`,
+/// and every function reference renumbered via `remap`.
+fn encode_hardened(
+    original_bytes: &[u8],
+    neutralized: &HashSet<u32>,
+    to_stub: &[(u32, u32)],
+    remap: &FunctionRemap,
+) -> Result<Vec<u8>> {
+    let mut module = EncodedModule::new();
+    let mut code_section = CodeSection::new();
+    let mut code_entries_remaining: u32 = 0;
+    let mut code_section_emitted = false;
+
+    for payload in Parser::new(0).parse_all(original_bytes) {
+        match payload? {
+            Payload::Version { .. } | Payload::End(_) => {}
+
+            Payload::ImportSection(reader) => {
+                let mut section = ImportSection::new();
+                let mut func_import_idx = 0u32;
+                for import in reader {
+                    let import = import?;
+                    match import.ty {
+                        wasmparser::TypeRef::Func(type_index) => {
+                            let keep = !neutralized.contains(&func_import_idx);
+                            func_import_idx += 1;
+                            if keep {
+                                section.import(
+                                    import.module,
+                                    import.name,
+                                    EntityType::Function(type_index),
+                                );
+                            }
+                        }
+                        wasmparser::TypeRef::Table(t) => {
+                            section.import(
+                                import.module,
+                                import.name,
+                                EntityType::Table(convert_table_type(t)),
+                            );
+                        }
+                        wasmparser::TypeRef::Memory(t) => {
+                            section.import(
+                                import.module,
+                                import.name,
+                                EntityType::Memory(convert_memory_type(t)),
+                            );
+                        }
+                        wasmparser::TypeRef::Global(t) => {
+                            section.import(
+                                import.module,
+                                import.name,
+                                EntityType::Global(convert_global_type(t)),
+                            );
+                        }
+                        wasmparser::TypeRef::Tag(_) => {
+                            return Err(anyhow!(
+                                "harden does not yet support modules with tag (exception) imports"
+                            ));
+                        }
+                    }
+                }
+                module.section(&section);
+            }
+
+            Payload::FunctionSection(reader) => {
+                let mut section = FunctionSection::new();
+                for type_index in reader {
+                    section.function(type_index?);
+                }
+                for &(_, type_index) in to_stub {
+                    section.function(type_index);
+                }
+                module.section(&section);
+            }
+
+            Payload::ExportSection(reader) => {
+                let mut section = ExportSection::new();
+                for export in reader {
+                    let export = export?;
+                    let (kind, index) = match export.kind {
+                        ExternalKind::Func => (
+                            EncExportKind::Func,
+                            remap.get(export.index).ok_or_else(|| {
+                                anyhow!(
+                                    "export \"{}\" references a removed import {}",
+                                    export.name,
+                                    export.index
+                                )
+                            })?,
+                        ),
+                        ExternalKind::Table => (EncExportKind::Table, export.index),
+                        ExternalKind::Memory => (EncExportKind::Memory, export.index),
+                        ExternalKind::Global => (EncExportKind::Global, export.index),
+                        ExternalKind::Tag => (EncExportKind::Tag, export.index),
+                    };
+                    section.export(export.name, kind, index);
+                }
+                module.section(&section);
+            }
+
+            Payload::StartSection { func, .. } => {
+                let remapped = remap
+                    .get(func)
+                    .ok_or_else(|| anyhow!("start function {} references a removed import", func))?;
+                module.section(&StartSection {
+                    function_index: remapped,
+                });
+            }
+
+            Payload::CodeSectionStart { count, .. } => {
+                code_entries_remaining = count;
+            }
+
+            Payload::CodeSectionEntry(body) => {
+                code_entries_remaining -= 1;
+
+                let locals: Vec<(u32, wasm_encoder::ValType)> = body
+                    .get_locals_reader()?
+                    .into_iter()
+                    .map(|local| local.map(|(count, ty)| (count, convert_val_type(ty))))
+                    .collect::<std::result::Result<_, _>>()?;
+                let mut function = Function::new(locals);
+
+                let mut ops = body.get_operators_reader()?;
+                while !ops.eof() {
+                    let op = ops.read()?;
+                    function.instruction(&remap_instruction(op, remap)?);
+                }
+                code_section.function(&function);
+
+                if code_entries_remaining == 0 {
+                    for _ in to_stub {
+                        let mut stub = Function::new(Vec::new());
+                        stub.instruction(&Instruction::Unreachable);
+                        stub.instruction(&Instruction::End);
+                        code_section.function(&stub);
+                    }
+                    module.section(&code_section);
+                    code_section_emitted = true;
+                }
+            }
+
+            Payload::CustomSection(reader) => {
+                module.section(&CustomSection {
+                    name: reader.name().into(),
+                    data: reader.data().into(),
+                });
+            }
+
+            payload => {
+                if let Some((id, range)) = raw_section_of(&payload) {
+                    module.section(&RawSection {
+                        id,
+                        data: &original_bytes[range],
+                    });
+                }
+            }
+        }
+    }
+
+    // A module with no defined functions at all never hits a `CodeSectionEntry`
+    // payload to piggyback the new stub bodies on, so flush them here instead.
+    if !code_section_emitted && !to_stub.is_empty() {
+        for _ in to_stub {
+            let mut stub = Function::new(Vec::new());
+            stub.instruction(&Instruction::Unreachable);
+            stub.instruction(&Instruction::End);
+            code_section.function(&stub);
+        }
+        module.section(&code_section);
+    }
+
+    let bytes = module.finish();
+    wasmparser::Validator::new().validate_all(&bytes)?;
+    Ok(bytes)
+}
+
+fn convert_table_type(t: wasmparser::TableType) -> wasm_encoder::TableType {
+    wasm_encoder::TableType {
+        element_type: convert_ref_type(t.element_type),
+        minimum: t.initial,
+        maximum: t.maximum,
+    }
+}
+
+fn convert_ref_type(t: wasmparser::RefType) -> wasm_encoder::RefType {
+    if t.is_func_ref() {
+        wasm_encoder::RefType::FUNCREF
+    } else {
+        wasm_encoder::RefType::EXTERNREF
+    }
+}
+
+fn convert_memory_type(t: wasmparser::MemoryType) -> wasm_encoder::MemoryType {
+    wasm_encoder::MemoryType {
+        minimum: t.initial,
+        maximum: t.maximum,
+        memory64: t.memory64,
+        shared: t.shared,
+    }
+}
+
+fn convert_global_type(t: wasmparser::GlobalType) -> wasm_encoder::GlobalType {
+    wasm_encoder::GlobalType {
+        val_type: convert_val_type(t.content_type),
+        mutable: t.mutable,
+    }
+}
@@ -1,6 +1,8 @@
 // types.rs
 use serde::{Deserialize, Serialize}; // Make sure this is present
+use std::collections::BTreeMap;
 use crate::memory::MemoryAnalysisResult;
+use crate::cfg::Cfg;
 
 #[derive(Debug, Clone, Serialize, Deserialize)] // CHECKED
 pub struct ModuleAnalysis {
@@ -10,6 +12,177 @@ pub struct ModuleAnalysis {
     pub performance_metrics: PerformanceMetrics, // CHECKED (PerformanceMetrics below)
     pub compatibility: CompatibilityMatrix,      // CHECKED (CompatibilityMatrix below)
     pub memory_analysis: MemoryAnalysisResult,   // CHECKED (MemoryAnalysisResult in memory.rs, all sub-fields checked)
+    pub control_flow_graphs: Vec<Cfg>,           // Per-function CFGs (cfg.rs)
+    pub capability_report: CapabilityReport,     // Import/entry-point summary (below)
+    pub conformance: Vec<ConformanceReport>,     // Named-interface conformance checks (below)
+    pub liveness: crate::liveness::LivenessReport, // Reachability-based tree-shaking analysis (liveness.rs)
+}
+
+/// Either shape an end-to-end analysis can take, mirroring [`ParsedArtifact`]:
+/// a bare core module gets the full [`ModuleAnalysis`] treatment, while a
+/// Component Model binary gets the lighter-weight [`ComponentAnalysis`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AnalysisResult {
+    Module(ModuleAnalysis),
+    Component(ComponentAnalysis),
+}
+
+/// Analysis of a Component Model binary: its structural summary plus
+/// imports/exports grouped by WIT interface name (the part of the import/
+/// export name before the last `/`, e.g. `wasi:io/poll` for
+/// `wasi:io/poll@0.2.0#...`), runtime compatibility, and a best-effort
+/// source-language guess from adapter export shapes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentAnalysis {
+    pub component_info: ComponentInfo,
+    pub interfaces_imported: BTreeMap<String, Vec<ComponentImport>>,
+    pub interfaces_exported: BTreeMap<String, Vec<ComponentExport>>,
+    pub compatibility: CompatibilityMatrix,
+    pub detected_source_language: Option<String>,
+}
+
+/// Static summary of what host capabilities a module requires and which
+/// well-known entry-point "profiles" it satisfies, derived purely from
+/// `imports`/`exports` without instantiating the module.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapabilityReport {
+    /// Number of imported functions required, grouped by import module namespace
+    /// (e.g. `env`, `wasi_snapshot_preview1`).
+    pub required_imports_by_namespace: BTreeMap<String, u32>,
+    /// Well-known entry-point export names (e.g. `_start`, `instantiate`) present
+    /// in this module's exports.
+    pub present_entry_points: Vec<String>,
+    /// Results of checking each registered `EntryPointProfile` against the
+    /// module's exports.
+    pub profiles: Vec<EntryPointProfileResult>,
+}
+
+/// A named set of export names that together define a runtime/protocol
+/// "profile" (e.g. the CosmWasm IBC entry-point set). Callers register these
+/// with `ModuleAnalyzer::analyze_capabilities_with_profiles`.
+#[derive(Debug, Clone)]
+pub struct EntryPointProfile {
+    pub name: String,
+    pub required_exports: Vec<String>,
+}
+
+impl EntryPointProfile {
+    pub fn new(name: impl Into<String>, required_exports: Vec<String>) -> Self {
+        Self {
+            name: name.into(),
+            required_exports,
+        }
+    }
+}
+
+/// Whether a module's exports satisfy a given `EntryPointProfile`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryPointProfileResult {
+    pub name: String,
+    pub satisfied: bool,
+    pub missing_exports: Vec<String>,
+}
+
+/// A value-type signature a conforming export's function type is expected to
+/// match. Either side left as `None` means "unconstrained" — useful for
+/// built-in profiles that only care an entry point exists, as opposed to a
+/// custom ABI spec that wants to pin the exact `params`/`results`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectedSignature {
+    pub params: Option<Vec<ValType>>,
+    pub results: Option<Vec<ValType>>,
+}
+
+impl ExpectedSignature {
+    /// No constraint on params or results — only the export's presence matters.
+    pub fn any() -> Self {
+        Self {
+            params: None,
+            results: None,
+        }
+    }
+
+    pub fn new(params: Vec<ValType>, results: Vec<ValType>) -> Self {
+        Self {
+            params: Some(params),
+            results: Some(results),
+        }
+    }
+
+    pub(crate) fn matches(&self, actual_params: &[ValType], actual_results: &[ValType]) -> bool {
+        self.params.as_deref().map_or(true, |p| p == actual_params)
+            && self.results.as_deref().map_or(true, |r| r == actual_results)
+    }
+}
+
+/// A named interface a module can be checked for conformance against — a WASI
+/// command/reactor, a custom contract ABI, etc. Import/export rules reuse the
+/// `"module.name"`/`"module.*"` wildcard syntax already used by `policy::Policy`.
+#[derive(Debug, Clone)]
+pub struct InterfaceSpec {
+    pub name: String,
+    pub required_exports: Vec<(String, ExpectedSignature)>,
+    pub required_imports: Vec<String>,
+    pub forbidden_imports: Vec<String>,
+    pub forbidden_exports: Vec<String>,
+}
+
+impl InterfaceSpec {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            required_exports: Vec::new(),
+            required_imports: Vec::new(),
+            forbidden_imports: Vec::new(),
+            forbidden_exports: Vec::new(),
+        }
+    }
+
+    pub fn require_export(mut self, name: impl Into<String>, signature: ExpectedSignature) -> Self {
+        self.required_exports.push((name.into(), signature));
+        self
+    }
+
+    pub fn require_import(mut self, rule: impl Into<String>) -> Self {
+        self.required_imports.push(rule.into());
+        self
+    }
+
+    pub fn forbid_import(mut self, rule: impl Into<String>) -> Self {
+        self.forbidden_imports.push(rule.into());
+        self
+    }
+
+    pub fn forbid_export(mut self, name: impl Into<String>) -> Self {
+        self.forbidden_exports.push(name.into());
+        self
+    }
+
+    /// Whether a `"module.name"`/`"module.*"` rule matches a given import, using
+    /// the same parsing as `policy::Policy::add_rule`. The special rule
+    /// `"wasi.*"` matches any WASI namespace (`wasi_snapshot_preview1`,
+    /// `wasi_unstable`, ...), reusing the same module-name detection as
+    /// `SecurityAnalyzer::analyze_wasi_usage`, since the concrete namespace a
+    /// module imports from varies by WASI version.
+    pub(crate) fn import_rule_matches(rule: &str, module: &str, name: &str) -> bool {
+        if rule == "wasi.*" {
+            return crate::security::is_wasi_module(module);
+        }
+        let (rule_module, rule_name) = rule.rsplit_once('.').unwrap_or((rule, "*"));
+        rule_module == module && (rule_name == "*" || rule_name == name)
+    }
+}
+
+/// The result of checking a module against one `InterfaceSpec`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConformanceReport {
+    pub profile_name: String,
+    pub conforms: bool,
+    pub missing_exports: Vec<String>,
+    pub signature_mismatches: Vec<String>,
+    pub missing_imports: Vec<String>,
+    pub forbidden_imports_present: Vec<String>,
+    pub forbidden_exports_present: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)] // CHECKED
@@ -25,11 +198,97 @@ pub struct ModuleInfo {
     pub element_segments: Vec<ElementSegment>,  // CHECKED (ElementSegment below)
     pub start_function: Option<u32>,
     pub custom_sections: Vec<CustomSection>,    // CHECKED (CustomSection below)
-    pub function_call_instructions: Vec<(u32, u32)>, // Tuples of u32 are fine
+    /// Name of the module itself, from the name section's module subsection.
+    pub module_name: Option<String>,
+    /// Debug names for locals, from the name section's local subsection:
+    /// function global index -> (local index -> name).
+    pub local_names: BTreeMap<u32, BTreeMap<u32, String>>,
+    /// Debug names for types, from the name section's type subsection.
+    pub type_names: BTreeMap<u32, String>,
+    // Tuples of u32 are fine. Includes `return_call` tail calls alongside regular `call`s.
+    pub function_call_instructions: Vec<(u32, u32)>,
+    /// (caller_global_idx, callee_type_index, const_table_slot). `const_table_slot`
+    /// is `Some` when the `call_indirect`/`return_call_indirect` site was immediately
+    /// preceded by `i32.const N` — the common vtable/closure-table codegen pattern —
+    /// and `None` when the table index isn't statically known at the call site.
+    pub indirect_call_instructions: Vec<(u32, u32, Option<u32>)>,
     pub type_signatures: Vec<String>,           // Vec<String> is fine
+    /// Per-defined-function byte offset maps, keyed by global function index, for
+    /// correlating a trap/stack-trace offset (or an external DWARF `.debug_line`
+    /// entry) back to a specific function and instruction. `BTreeMap` for stable
+    /// (index-ordered) serialization, matching `required_imports_by_namespace` above.
+    pub function_code_maps: BTreeMap<u32, FunctionCodeMap>,
+}
+
+/// Byte-offset map for one defined function's code, recorded while parsing its
+/// body so trap offsets and DWARF line info can be mapped back to a specific
+/// function and instruction without re-disassembling the module.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionCodeMap {
+    /// Absolute byte range of this function's code body in the original module.
+    pub body_range: std::ops::Range<u32>,
+    /// (operator index within the body, absolute byte offset of that operator)
+    /// for every instruction, in the order they appear.
+    pub instruction_offsets: Vec<(u32, u32)>,
+}
+
+/// Either of the two top-level shapes a `.wasm` binary can have, as
+/// distinguished by the encoding field on `Payload::Version` during parsing.
+/// Most of this crate's analyses (`ModuleAnalyzer`, the call graph, security
+/// passes, etc.) only understand core modules; a `Component` is returned
+/// as-is for callers to inspect without forcing every downstream pass to
+/// handle it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ParsedArtifact {
+    Module(ModuleInfo),
+    Component(ComponentInfo),
+}
+
+impl ParsedArtifact {
+    /// Unwraps a core-module parse result, for the common case of callers
+    /// that only handle `ModuleInfo` (e.g. `ModuleAnalyzer`). Fails loudly on
+    /// a component rather than silently discarding it.
+    pub fn into_module(self) -> anyhow::Result<ModuleInfo> {
+        match self {
+            ParsedArtifact::Module(module_info) => Ok(module_info),
+            ParsedArtifact::Component(_) => {
+                anyhow::bail!("input is a WebAssembly component, not a core module")
+            }
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)] // CHECKED
+/// Summary of a WebAssembly Component Model binary: nested core
+/// modules/components, component-level imports/exports (named, with their
+/// interface type stringified rather than fully modeled), and counts of the
+/// instance/alias/type machinery that wires them together. This is a
+/// structural summary, not a recursive parse of nested modules/components —
+/// those are counted but not descended into.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ComponentInfo {
+    pub nested_modules: u32,
+    pub nested_components: u32,
+    pub core_type_count: u32,
+    pub type_count: u32,
+    pub imports: Vec<ComponentImport>,
+    pub exports: Vec<ComponentExport>,
+    pub instance_count: u32,
+    pub alias_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentImport {
+    pub name: String,
+    pub interface_type: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentExport {
+    pub name: String,
+    pub interface_type: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)] // CHECKED
 pub struct Import {
     pub module: String,
     pub name: String,
@@ -37,19 +296,45 @@ pub struct Import {
     pub index: u32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)] // CHECKED
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)] // CHECKED
 pub enum ImportKind {
-    Function { type_index: u32 },
+    Function {
+        type_index: u32,
+        params: Vec<ValType>,
+        results: Vec<ValType>,
+    },
     Table { table_type: TableType },           // CHECKED (TableType below)
     Memory { memory_type: MemoryType },         // CHECKED (MemoryType below)
     Global { global_type: GlobalType },         // CHECKED (GlobalType below)
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)] // CHECKED
+/// WASM value types, resolved from the type section so `Function`/`ImportKind::Function`
+/// can carry real signatures instead of just a type index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ValType {
+    I32,
+    I64,
+    F32,
+    F64,
+    V128,
+    FuncRef,
+    ExternRef,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)] // CHECKED
 pub struct Export {
     pub name: String,
     pub kind: ExportKind,                       // CHECKED (ExportKind enum below)
     pub index: u32,
+    /// True when `kind` is `Function` and `index` falls in the imported half of the
+    /// function index space, i.e. this export is a thin re-export of a host import
+    /// rather than a function defined in this module. Always `false` for non-Function
+    /// exports.
+    pub points_to_import: bool,
+    /// `index` resolved into its own space: the function-import index when
+    /// `points_to_import` is true, or the defined-function slot (`index` minus the
+    /// imported function count) otherwise. Unused (0) for non-Function exports.
+    pub resolved_index: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)] // CHECKED
@@ -65,11 +350,25 @@ pub enum ExportKind {
 pub struct Function {
     pub index: u32,
     pub type_index: u32,
+    pub params: Vec<ValType>,
+    pub results: Vec<ValType>,
     pub locals: Vec<LocalType>,                 // CHECKED (LocalType below)
     pub body_size: u32,
     pub is_imported: bool,
     pub is_exported: bool,
     pub name: Option<String>,
+    /// Human-readable rendering of `name`, produced by demangling it as a Rust
+    /// (v0 or legacy) or Itanium C++ symbol. `None` when `name` is absent or
+    /// doesn't decode as either mangling scheme (e.g. it's already a plain
+    /// export name). `name` itself is left as-is so index lookups and export
+    /// matching keep working against the raw symbol.
+    pub demangled_name: Option<String>,
+    /// Deepest `block`/`loop`/`if` nesting reached in this function's body, used as a
+    /// coarse proxy for its operand-stack footprint when estimating stack depth.
+    pub max_block_depth: u32,
+    /// Total number of operators in this function's body, used as the base size
+    /// term when scoring it as an inlining candidate. `0` for imported functions.
+    pub instruction_count: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)] // CHECKED
@@ -79,6 +378,8 @@ pub struct Memory {
     pub shared: bool,
     pub is_imported: bool,
     pub is_exported: bool,
+    /// Debug name from the name section's memory subsection, if present.
+    pub name: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)] // CHECKED
@@ -87,16 +388,18 @@ pub struct Table {
     pub table_type: TableType,                  // CHECKED (TableType below)
     pub is_imported: bool,
     pub is_exported: bool,
+    /// Debug name from the name section's table subsection, if present.
+    pub name: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)] // CHECKED
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)] // CHECKED
 pub struct TableType {
     pub element_type: String,
     pub initial: u32,
     pub maximum: Option<u32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)] // CHECKED
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)] // CHECKED
 pub struct MemoryType {
     pub initial: u32,
     pub maximum: Option<u32>,
@@ -107,12 +410,14 @@ pub struct MemoryType {
 pub struct Global {
     pub index: u32,
     pub global_type: GlobalType,                // CHECKED (GlobalType below)
-    pub init_value: Option<String>,
+    pub init_value: ConstValue,                 // CHECKED (ConstValue below)
     pub is_imported: bool,
     pub is_exported: bool,
+    /// Debug name from the name section's global subsection, if present.
+    pub name: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)] // CHECKED
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)] // CHECKED
 pub struct GlobalType {
     pub value_type: String,
     pub mutable: bool,
@@ -128,7 +433,7 @@ pub struct LocalType {
 pub struct DataSegment {
     pub index: u32,
     pub memory_index: u32,
-    pub offset: u32,
+    pub offset: ConstValue,                     // CHECKED (ConstValue below)
     pub size: u32,
     pub is_passive: bool,
 }
@@ -137,9 +442,56 @@ pub struct DataSegment {
 pub struct ElementSegment {
     pub index: u32,
     pub table_index: Option<u32>,
-    pub offset: Option<u32>,
+    pub offset: Option<ConstValue>,             // CHECKED (ConstValue below)
     pub element_count: u32,
     pub is_passive: bool,
+    /// The segment's function indices, in table-slot order, when its items are a
+    /// concrete function-index list (`wasmparser::ElementItems::Functions`).
+    /// Empty for `ref.func`/`ref.null` expression items, which aren't resolved.
+    pub function_indices: Vec<u32>,
+}
+
+/// The evaluated result of a WASM init expression (global initializers, and
+/// active data/element segment offsets). Covers every form the evaluator in
+/// `parser::eval_const_expr` models; anything else becomes `Unknown` rather
+/// than silently discarding the expression.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)] // CHECKED
+pub enum ConstValue {
+    I32(i32),
+    I64(i64),
+    F32(f32),
+    F64(f64),
+    /// Raw little-endian bytes of a `v128.const`.
+    V128([u8; 16]),
+    /// A `global.get` reference to another global, by its global index.
+    GlobalRef(u32),
+    /// A `ref.func` literal, by the referenced function's global index.
+    FuncRef(u32),
+    /// A `ref.null` literal, carrying the null reference's heap type.
+    RefNull(String),
+    /// An init expression the evaluator doesn't model (e.g. an `extended-const`
+    /// arithmetic sequence), recorded rather than discarded.
+    Unknown,
+}
+
+impl ConstValue {
+    /// The value as an `i64`, if this is an integer constant (`I32` is
+    /// sign-extended, matching how WASM itself treats it as a 32-bit `i64`).
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            ConstValue::I32(v) => Some(*v as i64),
+            ConstValue::I64(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// The value as a `u32` offset/address, if this is a 32-bit integer constant.
+    pub fn as_u32(&self) -> Option<u32> {
+        match self {
+            ConstValue::I32(v) => Some(*v as u32),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)] // CHECKED
@@ -153,13 +505,56 @@ pub struct CallGraph {
     pub nodes: Vec<CallNode>,                   // CHECKED (CallNode below)
     pub edges: Vec<CallEdge>,                   // CHECKED (CallEdge below)
     pub entry_points: Vec<u32>,
+    /// Defined, non-imported functions with no path to them from `entry_points`
+    /// (which includes table/`ref.func`-global entries, same as
+    /// [`crate::liveness::LivenessReport::dead_functions`]). The two usually
+    /// agree, but can diverge: this set additionally treats a function as
+    /// reachable via an *unresolved* `call_indirect` if any live caller shares
+    /// its signature (a conservative overapproximation `liveness.rs` doesn't
+    /// make), while `LivenessReport` doesn't resolve per-slot `ref.func`
+    /// expressions inside `ElementItems::Expressions`-kind element segments
+    /// (see that type's doc comment). Prefer this field when deciding what
+    /// `transform::strip_unreachable_functions` is safe to delete.
     pub unreachable_functions: Vec<u32>,
+    /// Strongly connected components (via Tarjan's algorithm) of size > 1, or
+    /// size 1 with a self-loop — i.e. every cycle of mutual or self recursion
+    /// in the call graph. Useful for spotting unbounded stack growth risk in
+    /// untrusted WASM.
+    pub recursive_components: Vec<Vec<u32>>,
+    /// Defined functions ranked by how good an inlining candidate they are,
+    /// highest benefit score first. See [`InlineCandidate`].
+    pub inline_candidates: Vec<InlineCandidate>,
+    /// Dead-code and unused-surface classification. See [`UsageReport`].
+    pub usage_report: UsageReport,
+}
+
+/// Classifies functions by how (or whether) they're actually used, split into
+/// the categories that call for different fixes: a dead function can be
+/// deleted outright, an unused import can be dropped from the module's
+/// import surface, and a leaf-only export is live but only ever invoked from
+/// outside the module (so it's not dead, just worth double-checking).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)] // CHECKED
+pub struct UsageReport {
+    /// Defined, non-imported functions with no path to them from any entry
+    /// point or export — identical to `CallGraph::unreachable_functions`.
+    pub dead_functions: Vec<u32>,
+    /// Imported functions that are declared but never targeted by any direct
+    /// or indirect call edge from within the module.
+    pub unused_imports: Vec<u32>,
+    /// Exported functions that are never called by other functions in the
+    /// module — reachable only because they're an export, not because
+    /// anything internal depends on them.
+    pub leaf_only_exports: Vec<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)] // CHECKED
 pub struct CallNode {
     pub function_index: u32,
     pub name: Option<String>,
+    /// Human-readable rendering of `name`, demangled the same way as
+    /// `Function::demangled_name`. `None` for synthetic placeholder names
+    /// (e.g. `func_N (implicit_caller)`) that were never a real symbol.
+    pub demangled_name: Option<String>,
     pub is_imported: bool,
     pub is_exported: bool,
     pub call_count: u32,
@@ -170,6 +565,39 @@ pub struct CallEdge {
     pub from: u32,
     pub to: u32,
     pub call_sites: u32,
+    /// True when this edge was conservatively derived from a `call_indirect` site
+    /// (target resolved by matching type signature rather than a direct `call`).
+    pub is_indirect: bool,
+}
+
+/// Which way to walk edges for a [`CallGraph`] reachability query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Follow `CallEdge::from -> CallEdge::to`, i.e. "what does this function call".
+    Forward,
+    /// Follow `CallEdge::to -> CallEdge::from`, i.e. "what calls this function".
+    Reverse,
+}
+
+/// A MIR-style inlining suggestion for one defined function, ranked by `score`
+/// (higher is a better inlining candidate). `score` rewards small bodies,
+/// a single call site, and non-recursion, and penalizes large bodies, many
+/// callers, and membership in a recursive cycle.
+#[derive(Debug, Clone, Serialize, Deserialize)] // CHECKED
+pub struct InlineCandidate {
+    pub function_index: u32,
+    /// Estimated cost of inlining this function's body: its instruction count
+    /// plus a per-call-instruction penalty and an unknown-size penalty for any
+    /// indirect calls it makes (their callee's size can't be accounted for).
+    pub estimated_cost: u32,
+    /// Number of distinct places in the call graph that invoke this function
+    /// (i.e. the number of `CallEdge`s targeting it, as opposed to the total
+    /// dynamic invocation count on `CallNode::call_count`).
+    pub call_sites: u32,
+    /// Benefit score: roughly `call_sites / estimated_cost`, zeroed out for
+    /// functions that are part of a recursive cycle since inlining those
+    /// does not bound code growth.
+    pub score: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)] // CHECKED
@@ -180,7 +608,7 @@ pub struct SecurityAnalysis {
     pub wasi_usage: WasiUsage,                  // CHECKED (WasiUsage below)
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)] // CHECKED
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)] // CHECKED
 pub struct Capability {
     pub name: String,
     pub description: String,
@@ -196,7 +624,7 @@ pub enum RiskLevel {
     Critical,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)] // CHECKED
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)] // CHECKED
 pub struct Vulnerability {
     pub id: String,
     pub description: String,
@@ -219,6 +647,45 @@ pub struct WasiUsage {
     pub required_capabilities: Vec<String>,
 }
 
+/// How `SecurityAnalyzer::recommend_lockdown` restricts a single detected
+/// `Capability` when wrapping the module for a WASI-Virt-style
+/// virtualization/host layer.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)] // CHECKED
+pub enum CapabilityRestriction {
+    /// Deny the capability outright; the host should trap or error any call
+    /// into it rather than forwarding it.
+    Deny,
+    /// Allow the capability, but only for operations that cannot mutate host
+    /// state (e.g. a read-only preopened directory).
+    AllowReadOnly,
+    /// Replace the capability with a fixed, deterministic stand-in instead
+    /// of denying or forwarding it (e.g. a frozen clock value).
+    Stub,
+    /// Let the capability pass through to the real host implementation.
+    Allow,
+}
+
+/// One concrete restriction for a capability the module was observed to
+/// use, in the vocabulary a WASI-Virt-style virtualization/host layer
+/// understands.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)] // CHECKED
+pub struct LockdownDirective {
+    pub capability: String,
+    pub restriction: CapabilityRestriction,      // CHECKED (CapabilityRestriction above)
+    pub rationale: String,
+}
+
+/// A capability-lockdown manifest: for each capability the module actually
+/// uses, the minimal restriction a host can apply without breaking it.
+/// Meant to be handed to a WASI-Virt-style wrap-and-restrict tool, or
+/// rendered in a web UI as "here is the minimal sandbox this module needs."
+#[derive(Debug, Clone, Serialize, Deserialize)] // CHECKED
+pub struct LockdownPolicy {
+    pub directives: Vec<LockdownDirective>,      // CHECKED (LockdownDirective above)
+    pub clear_environment_variables: bool,
+    pub stubbed_clock_value: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)] // CHECKED
 pub struct PerformanceMetrics {
     pub module_size: u32,
@@ -227,6 +694,10 @@ pub struct PerformanceMetrics {
     pub complexity_score: f64,
     pub memory_usage_estimate: MemoryUsageEstimate, // CHECKED (MemoryUsageEstimate below)
     pub optimization_suggestions: Vec<OptimizationSuggestion>, // CHECKED (OptimizationSuggestion below)
+    /// Per-function cyclomatic complexity: a histogram plus the most complex
+    /// functions, distinguishing a large-but-simple module from one with a
+    /// few genuine hotspots. See [`crate::complexity`].
+    pub function_complexity: crate::complexity::ComplexityReport,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)] // CHECKED
@@ -243,18 +714,19 @@ pub struct OptimizationSuggestion {
     pub potential_savings: Option<String>,
 }
 
+/// Every declared deployment target's compatibility verdict, keyed by
+/// profile name. Populated from [`crate::targets::built_in_profiles`] (the
+/// six runtimes this crate has always reported on: `wasmtime`, `wasmer`,
+/// `browser`, `node_js`, `deno`, `cloudflare_workers`) plus any user-supplied
+/// `[target.<name>]` entries loaded at analysis time, so new targets don't
+/// require a crate change.
 #[derive(Debug, Clone, Serialize, Deserialize)] // CHECKED
 pub struct CompatibilityMatrix {
-    pub wasmtime: CompatibilityStatus,          // CHECKED (CompatibilityStatus below)
-    pub wasmer: CompatibilityStatus,            // CHECKED
-    pub browser: CompatibilityStatus,           // CHECKED
-    pub node_js: CompatibilityStatus,           // CHECKED
-    pub deno: CompatibilityStatus,              // CHECKED
-    pub cloudflare_workers: CompatibilityStatus, // CHECKED
+    pub targets: std::collections::BTreeMap<String, CompatibilityStatus>,
     pub detected_language: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)] // CHECKED
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)] // CHECKED
 pub struct CompatibilityStatus {
     pub compatible: bool,
     pub issues: Vec<String>,
@@ -1,6 +1,9 @@
 // tests/integration_test.rs
 
-use wasm_inspector::{analyze_wasm_module, quick_analyze, ExportKind, RiskLevel, ModuleInfo};
+use wasm_inspector::{analyze_wasm_module, analyze_wast_str, analyze_wat_str, quick_analyze, ExportKind, RiskLevel, ModuleInfo, ValType};
+use wasm_inspector::{EntryPointProfile, ModuleAnalyzer, WasmParser};
+use wasm_inspector::analyze_with_policy;
+use wasm_inspector::policy::Policy;
 
 // A minimal WASM module for testing (add function that returns 42)
 const MINIMAL_WASM: &[u8] = &[
@@ -121,6 +124,8 @@ fn test_minimal_wasm_analysis() {
     assert!(function.is_exported);
     // You might also want to check function.name if your parser populates it from exports/name section
     // assert_eq!(function.name.as_deref(), Some("main"));
+    assert!(function.params.is_empty(), "main should take no parameters");
+    assert_eq!(function.results, vec![ValType::I32], "main should resolve to a () -> i32 signature");
 
 
     // Check security analysis
@@ -129,9 +134,9 @@ fn test_minimal_wasm_analysis() {
     assert!(!analysis.security_analysis.wasi_usage.uses_wasi, "WASI usage should be false for minimal WASM");
 
     // Check compatibility
-    assert!(analysis.compatibility.browser.compatible, "Browser compatibility mismatch");
-    assert!(analysis.compatibility.node_js.compatible, "Node.js compatibility mismatch");
-    assert!(analysis.compatibility.wasmtime.compatible, "Wasmtime compatibility mismatch");
+    assert!(analysis.compatibility.targets["browser"].compatible, "Browser compatibility mismatch");
+    assert!(analysis.compatibility.targets["node_js"].compatible, "Node.js compatibility mismatch");
+    assert!(analysis.compatibility.targets["wasmtime"].compatible, "Wasmtime compatibility mismatch");
     assert!(analysis.security_analysis.sandbox_compatibility.browser_safe, "Sandbox browser safety mismatch");
 }
 
@@ -182,6 +187,11 @@ fn test_wasi_detection() {
     assert_eq!(import.module, "wasi_snapshot_preview1");
     assert_eq!(import.name, "fd_read");
 
+    // _start is the sole defined function and resolves to a () -> i32 signature
+    let start_function = &analysis.module_info.functions[0];
+    assert!(start_function.params.is_empty(), "_start should take no parameters");
+    assert_eq!(start_function.results, vec![ValType::I32], "_start should resolve to a () -> i32 signature");
+
     // Should detect WASI usage
     assert!(analysis.security_analysis.wasi_usage.uses_wasi, "WASI usage not detected");
     // Adjust expected version string based on your SecurityAnalyzer's output
@@ -205,14 +215,175 @@ fn test_wasi_detection() {
     assert!(has_fs_capability, "General File System Access capability not detected");
 
     // Should not be browser compatible due to WASI FS access
-    assert!(!analysis.compatibility.browser.compatible, "Browser compatibility should be false");
+    assert!(!analysis.compatibility.targets["browser"].compatible, "Browser compatibility should be false");
     assert!(!analysis.security_analysis.sandbox_compatibility.browser_safe, "Sandbox browser safety should be false");
 
     // Should be compatible with Node.js and Wasmtime (these typically support WASI)
-    assert!(analysis.compatibility.node_js.compatible, "Node.js compatibility should be true");
+    assert!(analysis.compatibility.targets["node_js"].compatible, "Node.js compatibility should be true");
     assert!(analysis.security_analysis.sandbox_compatibility.node_safe, "Sandbox Node.js safety should be true");
 
-    assert!(analysis.compatibility.wasmtime.compatible, "Wasmtime compatibility should be true");
+    assert!(analysis.compatibility.targets["wasmtime"].compatible, "Wasmtime compatibility should be true");
+
+    // Should conform to the built-in "WASI Command" interface: it exports
+    // `_start` and imports from a WASI namespace, with no `_initialize`.
+    let wasi_command = analysis
+        .conformance
+        .iter()
+        .find(|c| c.profile_name == "WASI Command")
+        .expect("WASI Command conformance report should be present");
+    assert!(
+        wasi_command.conforms,
+        "WASI_WASM should conform to the WASI Command interface: {:?}",
+        wasi_command
+    );
+}
+
+#[test]
+fn test_reexported_import_is_flagged() {
+    let wat = r#"
+        (module
+          (import "wasi_snapshot_preview1" "fd_read" (func $fd_read (param i32 i32 i32 i32) (result i32)))
+          (export "fd_read" (func $fd_read)))
+    "#;
+    let analysis = analyze_wat_str(wat).expect("valid WAT should analyze successfully");
+
+    let export = &analysis.module_info.exports[0];
+    assert_eq!(export.index, 0, "the only function in the module is the import itself");
+    assert!(export.points_to_import, "export re-exports an imported function");
+    assert_eq!(export.resolved_index, 0);
+
+    let has_reexport_capability = analysis
+        .security_analysis
+        .capabilities
+        .iter()
+        .any(|c| c.name == "Re-exported Host Import");
+    assert!(
+        has_reexport_capability,
+        "re-exporting a host import should be flagged as a capability"
+    );
+}
+
+#[test]
+fn test_analyze_wat_str() {
+    let wat = r#"
+        (module
+          (func $add (param i32 i32) (result i32)
+            local.get 0
+            local.get 1
+            i32.add)
+          (export "add" (func $add)))
+    "#;
+
+    let analysis = analyze_wat_str(wat).expect("valid WAT should analyze successfully");
+    assert_eq!(analysis.module_info.exports.len(), 1);
+    assert_eq!(analysis.module_info.exports[0].name, "add");
+}
+
+#[test]
+fn test_analyze_wast_str_skips_assertions() {
+    let wast = r#"
+        (module (func $f (export "f")))
+        (assert_return (invoke "f"))
+        (module (func $g (export "g")))
+    "#;
+
+    let analyses = analyze_wast_str(wast).expect("wast script should parse");
+    assert_eq!(analyses.len(), 2, "should analyze only the two module directives");
+    assert_eq!(analyses[0].module_info.exports[0].name, "f");
+    assert_eq!(analyses[1].module_info.exports[0].name, "g");
+}
+
+#[test]
+fn test_capability_report_detects_cosmwasm_entry_points() {
+    let wat = r#"
+        (module
+          (func $instantiate (result i32) i32.const 0)
+          (func $execute (result i32) i32.const 0)
+          (func $query (result i32) i32.const 0)
+          (export "instantiate" (func $instantiate))
+          (export "execute" (func $execute))
+          (export "query" (func $query)))
+    "#;
+    let analysis = analyze_wat_str(wat).expect("valid WAT should analyze successfully");
+    let report = &analysis.capability_report;
+
+    assert!(report.present_entry_points.contains(&"instantiate".to_string()));
+    assert!(report.present_entry_points.contains(&"execute".to_string()));
+    assert!(report.present_entry_points.contains(&"query".to_string()));
+
+    let cosmwasm_profile = report
+        .profiles
+        .iter()
+        .find(|p| p.name == "CosmWasm Contract")
+        .expect("CosmWasm Contract profile should be checked by default");
+    assert!(cosmwasm_profile.satisfied, "CosmWasm Contract profile should be satisfied");
+    assert!(cosmwasm_profile.missing_exports.is_empty());
+
+    let wasi_profile = report
+        .profiles
+        .iter()
+        .find(|p| p.name == "WASI Command")
+        .expect("WASI Command profile should be checked by default");
+    assert!(!wasi_profile.satisfied, "module has no _start export");
+    assert_eq!(wasi_profile.missing_exports, vec!["_start".to_string()]);
+}
+
+#[test]
+fn test_capability_report_groups_imports_by_namespace() {
+    let analysis = analyze_wasm_module(WASI_WASM).expect("WASI WASM should analyze");
+    let report = &analysis.capability_report;
+    assert_eq!(
+        report.required_imports_by_namespace.get("wasi_snapshot_preview1"),
+        Some(&1)
+    );
+}
+
+#[test]
+fn test_analyze_capabilities_with_custom_profile() {
+    let parser = WasmParser::new(MINIMAL_WASM).expect("parser should accept minimal WASM");
+    let module_info = parser.parse().expect("minimal WASM should parse");
+    let analyzer = ModuleAnalyzer::new(module_info, MINIMAL_WASM);
+
+    let custom_profile = EntryPointProfile::new("Has Main", vec!["main".to_string()]);
+    let report = analyzer.analyze_capabilities_with_profiles(&[custom_profile]);
+
+    let result = &report.profiles[0];
+    assert_eq!(result.name, "Has Main");
+    assert!(result.satisfied, "MINIMAL_WASM exports \"main\"");
+}
+
+#[test]
+fn test_analyze_with_policy_flags_unlisted_import() {
+    let policy = Policy::new(); // empty: nothing is allow-listed
+
+    let (_analysis, report) =
+        analyze_with_policy(WASI_WASM, &policy).expect("WASI WASM should analyze");
+
+    assert!(!report.passes_policy());
+    assert_eq!(report.violations.len(), 1);
+    assert_eq!(report.violations[0].module, "wasi_snapshot_preview1");
+    assert_eq!(report.violations[0].name, "fd_read");
 }
 
- 
\ No newline at end of file
+#[test]
+fn test_analyze_with_policy_wildcard_allow() {
+    let policy = Policy::new().allow("wasi_snapshot_preview1.*");
+
+    let (_analysis, report) =
+        analyze_with_policy(WASI_WASM, &policy).expect("WASI WASM should analyze");
+
+    assert!(report.passes_policy(), "wildcard rule should cover fd_read");
+}
+
+#[test]
+fn test_analyze_with_policy_deny_overrides_allow() {
+    let policy = Policy::new()
+        .allow("wasi_snapshot_preview1.*")
+        .deny("wasi_snapshot_preview1.fd_read");
+
+    let (_analysis, report) =
+        analyze_with_policy(WASI_WASM, &policy).expect("WASI WASM should analyze");
+
+    assert!(!report.passes_policy(), "explicit deny should win over the module wildcard");
+    assert_eq!(report.violations[0].reason, "\"wasi_snapshot_preview1.fd_read\" is explicitly denied by policy");
+}
@@ -0,0 +1,789 @@
+// tests/memory_test.rs
+
+use wasm_inspector::analyze_wat_str;
+use wasm_inspector::memory::{AllocationType, AllocatorKind, MemoryResetStrategy, OptimizationType};
+use wasm_inspector::RiskLevel;
+
+const DEAD_ACTIVE_SEGMENT_WAT: &str = r#"
+(module
+  (memory 1)
+  (data (i32.const 0) "unused bytes")
+  (func $touch_elsewhere (export "touch_elsewhere") (result i32)
+    i32.const 4096
+    i32.load)
+)
+"#;
+
+const REFERENCED_PASSIVE_SEGMENT_WAT: &str = r#"
+(module
+  (memory 1)
+  (data $seg "hello")
+  (func $init (export "init")
+    i32.const 0
+    i32.const 0
+    i32.const 5
+    memory.init $seg)
+)
+"#;
+
+const UNREFERENCED_PASSIVE_SEGMENT_WAT: &str = r#"
+(module
+  (memory 1)
+  (data $seg "hello")
+  (func $noop (export "noop")
+    nop)
+)
+"#;
+
+const SHARED_MEMORY_MIXED_ACCESS_WAT: &str = r#"
+(module
+  (memory 1 1 shared)
+  (func $racy (export "racy") (param $addr i32)
+    local.get $addr
+    i32.atomic.load
+    drop
+    local.get $addr
+    i32.const 1
+    i32.store)
+)
+"#;
+
+const SHARED_MEMORY_ATOMICS_ONLY_WAT: &str = r#"
+(module
+  (memory 1 1 shared)
+  (func $counter (export "counter") (param $addr i32)
+    local.get $addr
+    i32.const 1
+    i32.atomic.rmw.add
+    drop)
+)
+"#;
+
+const UNSHARED_MEMORY_WITH_ATOMICS_WAT: &str = r#"
+(module
+  (memory 1 1)
+  (func $counter (export "counter") (param $addr i32)
+    local.get $addr
+    i32.const 1
+    i32.atomic.rmw.add
+    drop)
+)
+"#;
+
+const VECTOR_LOAD_STORE_WAT: &str = r#"
+(module
+  (memory 1)
+  (func $copy_vec (export "copy_vec") (param $addr i32)
+    local.get $addr
+    local.get $addr
+    v128.load
+    v128.store)
+)
+"#;
+
+// One page of memory (64KiB) and no maximum, so the safety analyzer's fallback
+// limit is exactly 65536 bytes.
+const OVERFLOW_WAT: &str = r#"
+(module
+  (memory 1)
+  (global $base i32 (i32.const 65532))
+  (func $overflow_write
+    (i32.store (i32.add (global.get $base) (i32.const 8)) (i32.const 1)))
+  (export "overflow_write" (func $overflow_write))
+)
+"#;
+
+#[test]
+fn test_resolved_effective_address_flags_overflow() {
+    let analysis = analyze_wat_str(OVERFLOW_WAT).expect("valid WAT should analyze successfully");
+    let overflows = &analysis.memory_analysis.safety_analysis.potential_overflows;
+    assert!(
+        overflows.iter().any(|o| o.description.contains("resolved address")),
+        "an i32.store whose address folds to global(65532) + 8 = 65540 (size 4) exceeds the \
+         65536-byte memory limit and should be flagged via the resolved effective address, got: {:?}",
+        overflows
+    );
+}
+
+const NO_OVERFLOW_WAT: &str = r#"
+(module
+  (memory 1)
+  (func $safe_write (param $addr i32)
+    (i32.store (local.get $addr) (i32.const 1)))
+  (export "safe_write" (func $safe_write))
+)
+"#;
+
+#[test]
+fn test_unknown_base_does_not_false_positive() {
+    let analysis = analyze_wat_str(NO_OVERFLOW_WAT).expect("valid WAT should analyze successfully");
+    let overflows = &analysis.memory_analysis.safety_analysis.potential_overflows;
+    assert!(
+        overflows.is_empty(),
+        "a store to a parameter-supplied address is statically unknown and must not be \
+         flagged, got: {:?}",
+        overflows
+    );
+}
+
+const GROWTH_AND_BULK_WAT: &str = r#"
+(module
+  (memory 1)
+  (func $grow
+    (drop (memory.grow (i32.const 2))))
+  (func $bulk
+    (memory.copy (i32.const 0) (i32.const 0) (i32.const 100)))
+  (export "grow" (func $grow))
+  (export "bulk" (func $bulk))
+)
+"#;
+
+#[test]
+fn test_resolved_operand_feeds_allocation_pattern_average_size() {
+    let analysis =
+        analyze_wat_str(GROWTH_AND_BULK_WAT).expect("valid WAT should analyze successfully");
+    let patterns = &analysis.memory_analysis.allocation_patterns;
+
+    let growth = patterns
+        .iter()
+        .find(|p| matches!(p.pattern_type, AllocationType::DynamicGrowth))
+        .expect("memory.grow should produce a DynamicGrowth pattern");
+    assert_eq!(
+        growth.average_size,
+        2 * 64 * 1024,
+        "memory.grow's page-delta operand folds to a constant 2, so average_size should be in bytes"
+    );
+
+    let bulk = patterns
+        .iter()
+        .find(|p| matches!(p.pattern_type, AllocationType::BulkOperations))
+        .expect("memory.copy should produce a BulkOperations pattern");
+    assert_eq!(
+        bulk.average_size, 100,
+        "memory.copy's length operand folds to a constant 100 bytes"
+    );
+}
+
+// $leaf = 0, $middle = 1, $entry = 2 (global indices follow declaration order).
+const CALL_CHAIN_WAT: &str = r#"
+(module
+  (func $leaf (result i32)
+    (local i32 i32)
+    i32.const 1)
+  (func $middle (result i32)
+    (local i32)
+    call $leaf)
+  (func $entry (export "entry") (result i32)
+    call $middle)
+)
+"#;
+
+#[test]
+fn test_stack_usage_sums_frame_sizes_along_the_call_chain() {
+    let analysis = analyze_wat_str(CALL_CHAIN_WAT).expect("valid WAT should analyze successfully");
+    let stack = &analysis.memory_analysis.memory_layout.stack_estimation;
+
+    assert!(!stack.recursive_risk, "a linear call chain has no recursion");
+    // entry (0 locals) + middle (1 i32 local = 4 bytes) + leaf (2 i32 locals = 8 bytes)
+    assert_eq!(stack.estimated_max_depth, 12);
+    assert!(
+        stack.deep_call_chains.iter().any(|chain| chain == &vec![2, 1, 0]),
+        "expected the entry -> middle -> leaf chain among deep_call_chains, got: {:?}",
+        stack.deep_call_chains
+    );
+}
+
+const RECURSIVE_WAT: &str = r#"
+(module
+  (func $fact (export "fact") (param $n i32) (result i32)
+    local.get $n
+    i32.const 1
+    i32.le_s
+    if (result i32)
+      i32.const 1
+    else
+      local.get $n
+      local.get $n
+      i32.const 1
+      i32.sub
+      call $fact
+      i32.mul
+    end)
+)
+"#;
+
+#[test]
+fn test_self_recursive_call_sets_recursive_risk() {
+    let analysis = analyze_wat_str(RECURSIVE_WAT).expect("valid WAT should analyze successfully");
+    let stack = &analysis.memory_analysis.memory_layout.stack_estimation;
+    assert!(
+        stack.recursive_risk,
+        "$fact calls itself, so recursive_risk should be set"
+    );
+}
+
+#[test]
+fn test_mixed_atomic_and_plain_access_on_shared_memory_flags_data_race() {
+    let analysis = analyze_wat_str(SHARED_MEMORY_MIXED_ACCESS_WAT)
+        .expect("valid WAT should analyze successfully");
+    let race = &analysis.memory_analysis.safety_analysis.data_race_risk;
+    assert_eq!(
+        race.risk_level,
+        RiskLevel::High,
+        "$racy mixes an atomic load with a plain store on shared memory"
+    );
+    assert_eq!(race.functions_involved, vec![0]);
+}
+
+#[test]
+fn test_atomics_only_on_shared_memory_is_not_flagged() {
+    let analysis = analyze_wat_str(SHARED_MEMORY_ATOMICS_ONLY_WAT)
+        .expect("valid WAT should analyze successfully");
+    let race = &analysis.memory_analysis.safety_analysis.data_race_risk;
+    assert_eq!(
+        race.risk_level,
+        RiskLevel::Low,
+        "$counter only ever accesses shared memory atomically"
+    );
+    assert!(race.functions_involved.is_empty());
+}
+
+#[test]
+fn test_atomics_on_unshared_memory_is_not_flagged() {
+    let analysis = analyze_wat_str(UNSHARED_MEMORY_WITH_ATOMICS_WAT)
+        .expect("valid WAT should analyze successfully");
+    let race = &analysis.memory_analysis.safety_analysis.data_race_risk;
+    assert_eq!(
+        race.risk_level,
+        RiskLevel::Low,
+        "without a shared memory declaration there is no cross-agent data race to flag"
+    );
+}
+
+#[test]
+fn test_vector_load_and_store_are_counted_as_vector_operations() {
+    let analysis =
+        analyze_wat_str(VECTOR_LOAD_STORE_WAT).expect("valid WAT should analyze successfully");
+    assert_eq!(
+        analysis
+            .memory_analysis
+            .operation_analysis
+            .vector_operations,
+        2,
+        "the v128.load and v128.store should both be counted as vector operations"
+    );
+}
+
+#[test]
+fn test_atomic_rmw_is_counted_as_an_atomic_operation() {
+    let analysis = analyze_wat_str(SHARED_MEMORY_ATOMICS_ONLY_WAT)
+        .expect("valid WAT should analyze successfully");
+    assert_eq!(
+        analysis
+            .memory_analysis
+            .operation_analysis
+            .atomic_operations,
+        1,
+        "the i32.atomic.rmw.add should be counted as an atomic operation"
+    );
+}
+
+#[test]
+fn test_atomics_on_unshared_memory_are_flagged_by_thread_safety_analysis() {
+    let analysis = analyze_wat_str(UNSHARED_MEMORY_WITH_ATOMICS_WAT)
+        .expect("valid WAT should analyze successfully");
+    let thread_safety = &analysis.memory_analysis.thread_safety_analysis;
+    assert!(!thread_safety.is_shared_memory);
+    assert_eq!(
+        thread_safety.atomics_on_non_shared_memory,
+        vec![0],
+        "$counter issues an atomic RMW against memory that isn't declared shared"
+    );
+}
+
+#[test]
+fn test_shared_memory_with_no_atomics_is_flagged() {
+    let analysis = analyze_wat_str(VECTOR_LOAD_STORE_WAT).expect("valid WAT should analyze successfully");
+    let thread_safety = &analysis.memory_analysis.thread_safety_analysis;
+    assert!(
+        !thread_safety.is_shared_memory,
+        "VECTOR_LOAD_STORE_WAT's memory isn't declared shared, so this flag shouldn't apply"
+    );
+    assert!(!thread_safety.shared_memory_with_no_atomics);
+
+    let shared_analysis = analyze_wat_str(SHARED_MEMORY_MIXED_ACCESS_WAT)
+        .expect("valid WAT should analyze successfully");
+    let shared_thread_safety = &shared_analysis.memory_analysis.thread_safety_analysis;
+    assert!(shared_thread_safety.is_shared_memory);
+    assert!(
+        !shared_thread_safety.shared_memory_with_no_atomics,
+        "$racy does issue an atomic load, so this shouldn't be flagged"
+    );
+}
+
+const SHARED_MEMORY_NO_ATOMICS_WAT: &str = r#"
+(module
+  (memory 1 1 shared)
+  (func $plain_touch (export "plain_touch") (param $addr i32)
+    local.get $addr
+    i32.const 1
+    i32.store)
+)
+"#;
+
+#[test]
+fn test_shared_memory_declared_with_zero_atomics_is_flagged() {
+    let analysis = analyze_wat_str(SHARED_MEMORY_NO_ATOMICS_WAT)
+        .expect("valid WAT should analyze successfully");
+    let thread_safety = &analysis.memory_analysis.thread_safety_analysis;
+    assert!(thread_safety.is_shared_memory);
+    assert!(
+        thread_safety.shared_memory_with_no_atomics,
+        "memory is shared but $plain_touch never issues an atomic instruction"
+    );
+}
+
+const BOUNDED_MEMORY_AND_TABLE_WAT: &str = r#"
+(module
+  (memory 1 4)
+  (table 2 8 funcref)
+  (func $touch (export "touch")
+    i32.const 0
+    i32.load
+    drop)
+)
+"#;
+
+#[test]
+fn test_pooling_recommendation_uses_declared_maximum_and_table_bound() {
+    let analysis = analyze_wat_str(BOUNDED_MEMORY_AND_TABLE_WAT)
+        .expect("valid WAT should analyze successfully");
+    let pooling = &analysis.memory_analysis.pooling_recommendation;
+    assert_eq!(pooling.max_memory_pages, 4);
+    assert!(
+        pooling.requires_growth,
+        "declared maximum (4) exceeds initial (1), so growth is possible"
+    );
+    assert_eq!(pooling.recommended_slot_bytes, 4 * 64 * 1024);
+    assert!(matches!(pooling.reset_strategy, MemoryResetStrategy::CopyOnWrite));
+    assert_eq!(pooling.table_element_capacity, 8);
+}
+
+#[test]
+fn test_pooling_recommendation_estimates_from_growth_when_no_maximum_declared() {
+    let analysis =
+        analyze_wat_str(GROWTH_AND_BULK_WAT).expect("valid WAT should analyze successfully");
+    let pooling = &analysis.memory_analysis.pooling_recommendation;
+    assert!(
+        pooling.requires_growth,
+        "memory has no declared maximum but $grow grows it, so growth should be flagged"
+    );
+    assert!(
+        pooling.max_memory_pages > 1,
+        "the estimate should account for $grow's folded 2-page growth on top of the 1 initial page, got {}",
+        pooling.max_memory_pages
+    );
+}
+
+#[test]
+fn test_pooling_recommendation_uses_zero_fill_for_shared_memory() {
+    let analysis = analyze_wat_str(SHARED_MEMORY_ATOMICS_ONLY_WAT)
+        .expect("valid WAT should analyze successfully");
+    let pooling = &analysis.memory_analysis.pooling_recommendation;
+    assert!(matches!(pooling.reset_strategy, MemoryResetStrategy::ZeroFill));
+}
+
+const RUST_SYSTEM_ALLOC_WAT: &str = r#"
+(module
+  (memory 1)
+  (func $alloc (export "__rust_alloc") (param i32 i32) (result i32)
+    i32.const 0)
+  (func $dealloc (export "__rust_dealloc") (param i32 i32 i32))
+)
+"#;
+
+#[test]
+fn test_fingerprints_rust_system_allocator_exports() {
+    let analysis =
+        analyze_wat_str(RUST_SYSTEM_ALLOC_WAT).expect("valid WAT should analyze successfully");
+    assert_eq!(
+        analysis.memory_analysis.memory_layout.heap_estimation.allocator_kind,
+        AllocatorKind::RustSystemAlloc
+    );
+}
+
+const WEE_ALLOC_WAT: &str = r#"
+(module
+  (memory 1)
+  (func $a (export "wee_alloc") (param i32) (result i32)
+    i32.const 0)
+)
+"#;
+
+#[test]
+fn test_fingerprints_wee_alloc_export() {
+    let analysis = analyze_wat_str(WEE_ALLOC_WAT).expect("valid WAT should analyze successfully");
+    assert_eq!(
+        analysis.memory_analysis.memory_layout.heap_estimation.allocator_kind,
+        AllocatorKind::WeeAlloc
+    );
+}
+
+#[test]
+fn test_allocator_kind_defaults_to_unknown() {
+    let analysis =
+        analyze_wat_str(VECTOR_LOAD_STORE_WAT).expect("valid WAT should analyze successfully");
+    assert_eq!(
+        analysis.memory_analysis.memory_layout.heap_estimation.allocator_kind,
+        AllocatorKind::Unknown
+    );
+}
+
+const CUSTOM_POOL_MALLOC_WAT: &str = r#"
+(module
+  (memory 1)
+  (func $malloc (export "malloc") (param i32) (result i32)
+    i32.const 0)
+  (func $free (export "free") (param i32))
+  (func $hot (export "hot") (param $addr i32)
+    local.get $addr i32.load drop
+    local.get $addr i32.load drop
+    local.get $addr i32.load drop
+    local.get $addr i32.load drop
+    local.get $addr i32.load drop
+    local.get $addr i32.load drop
+    local.get $addr i32.load drop
+    local.get $addr i32.load drop
+    local.get $addr i32.load drop
+    local.get $addr i32.load drop
+    local.get $addr i32.load drop
+    local.get $addr i32.load drop)
+)
+"#;
+
+#[test]
+fn test_unrecognized_malloc_free_pair_under_small_allocation_pressure_is_custom_pool() {
+    let analysis = analyze_wat_str(CUSTOM_POOL_MALLOC_WAT)
+        .expect("valid WAT should analyze successfully");
+    assert_eq!(
+        analysis.memory_analysis.memory_layout.heap_estimation.allocator_kind,
+        AllocatorKind::CustomPool
+    );
+    let opt = analysis
+        .memory_analysis
+        .optimization_opportunities
+        .iter()
+        .find(|o| matches!(o.optimization_type, OptimizationType::MinimizeAllocations))
+        .expect("frequent small accesses should produce a MinimizeAllocations optimization");
+    assert!(
+        opt.description.contains("pool"),
+        "advice should be tailored to the CustomPool classification, got: {:?}",
+        opt.description
+    );
+}
+
+const UNCHECKED_GROW_WAT: &str = r#"
+(module
+  (memory 1)
+  (func $grow_then_write (export "grow_then_write")
+    i32.const 1
+    memory.grow
+    i32.const 0
+    i32.store)
+)
+"#;
+
+#[test]
+fn test_memory_grow_result_used_as_address_without_check_is_flagged() {
+    let analysis =
+        analyze_wat_str(UNCHECKED_GROW_WAT).expect("valid WAT should analyze successfully");
+    let overflows = &analysis.memory_analysis.safety_analysis.potential_overflows;
+    assert!(
+        overflows
+            .iter()
+            .any(|o| o.operation_type == "UncheckedAllocation(Grow)"),
+        "memory.grow's result feeds directly into i32.store's address with no check, got: {:?}",
+        overflows
+    );
+}
+
+const CHECKED_GROW_WAT: &str = r#"
+(module
+  (memory 1)
+  (func $grow_then_check (export "grow_then_check")
+    i32.const 1
+    memory.grow
+    i32.const -1
+    i32.eq
+    if
+      unreachable
+    end)
+)
+"#;
+
+#[test]
+fn test_memory_grow_result_checked_before_use_is_not_flagged() {
+    let analysis =
+        analyze_wat_str(CHECKED_GROW_WAT).expect("valid WAT should analyze successfully");
+    let overflows = &analysis.memory_analysis.safety_analysis.potential_overflows;
+    assert!(
+        !overflows
+            .iter()
+            .any(|o| o.operation_type == "UncheckedAllocation(Grow)"),
+        "memory.grow's result is compared against -1 before any address use, got: {:?}",
+        overflows
+    );
+}
+
+const UNCHECKED_ALLOC_CALL_WAT: &str = r#"
+(module
+  (memory 1)
+  (import "env" "malloc" (func $malloc (param i32) (result i32)))
+  (func $use_alloc (export "use_alloc")
+    i32.const 16
+    call $malloc
+    i32.const 0
+    i32.store)
+)
+"#;
+
+#[test]
+fn test_allocation_call_result_used_as_address_without_check_is_flagged() {
+    let analysis = analyze_wat_str(UNCHECKED_ALLOC_CALL_WAT)
+        .expect("valid WAT should analyze successfully");
+    let overflows = &analysis.memory_analysis.safety_analysis.potential_overflows;
+    assert!(
+        overflows
+            .iter()
+            .any(|o| o.operation_type == "UncheckedAllocation(HeapCall)"),
+        "$malloc's result feeds directly into i32.store's address with no check, got: {:?}",
+        overflows
+    );
+}
+
+#[test]
+fn test_active_segment_never_read_is_flagged_as_dead() {
+    let analysis =
+        analyze_wat_str(DEAD_ACTIVE_SEGMENT_WAT).expect("valid WAT should analyze successfully");
+    assert!(
+        analysis
+            .memory_analysis
+            .optimization_opportunities
+            .iter()
+            .any(|opt| matches!(opt.optimization_type, OptimizationType::ReduceMemoryFootprint)
+                && opt.estimated_savings.as_deref() == Some("12 bytes of dead data segments")),
+        "the data segment at offset 0 is never overlapped by the load at address 4096"
+    );
+}
+
+#[test]
+fn test_passive_segment_referenced_by_memory_init_is_not_dead() {
+    let analysis = analyze_wat_str(REFERENCED_PASSIVE_SEGMENT_WAT)
+        .expect("valid WAT should analyze successfully");
+    assert!(
+        !analysis
+            .memory_analysis
+            .optimization_opportunities
+            .iter()
+            .any(|opt| matches!(opt.optimization_type, OptimizationType::ReduceMemoryFootprint)
+                && opt
+                    .estimated_savings
+                    .as_deref()
+                    .map_or(false, |s| s.contains("dead data segments"))),
+        "$seg is targeted by memory.init, so it should not be reported as dead"
+    );
+}
+
+#[test]
+fn test_passive_segment_never_initialized_is_flagged_as_dead() {
+    let analysis = analyze_wat_str(UNREFERENCED_PASSIVE_SEGMENT_WAT)
+        .expect("valid WAT should analyze successfully");
+    assert!(
+        analysis
+            .memory_analysis
+            .optimization_opportunities
+            .iter()
+            .any(|opt| matches!(opt.optimization_type, OptimizationType::ReduceMemoryFootprint)
+                && opt.estimated_savings.as_deref() == Some("5 bytes of dead data segments")),
+        "$seg is never the target of a memory.init, so it should be reported as dead"
+    );
+}
+
+const OVERLAPPING_SEGMENTS_WAT: &str = r#"
+(module
+  (memory 1)
+  (data (i32.const 0) "\01\02\03\04\05\06\07\08")
+  (data (i32.const 4) "\09\0a\0b\0c")
+)
+"#;
+
+#[test]
+fn test_overlapping_active_segments_are_reported_and_flagged_high_risk() {
+    let analysis = analyze_wat_str(OVERLAPPING_SEGMENTS_WAT)
+        .expect("valid WAT should analyze successfully");
+    let report = &analysis.memory_analysis.memory_layout.data_segment_report;
+    assert_eq!(
+        report.overlapping_segments,
+        vec![(0, 1)],
+        "segment 0 covers [0, 8) and segment 1 covers [4, 8), so they overlap"
+    );
+
+    let overflows = &analysis.memory_analysis.safety_analysis.potential_overflows;
+    assert!(
+        overflows
+            .iter()
+            .any(|o| o.operation_type == "DataSegmentOverlap(segment 1)"
+                && matches!(o.risk_level, RiskLevel::High)),
+        "overlapping data segments fail at instantiation, so they should be High risk, got: {:?}",
+        overflows
+    );
+}
+
+const OUT_OF_BOUNDS_SEGMENT_WAT: &str = r#"
+(module
+  (memory 1)
+  (data (i32.const 65530) "\01\02\03\04\05\06\07\08")
+)
+"#;
+
+#[test]
+fn test_segment_extending_past_initial_memory_size_is_out_of_bounds() {
+    let analysis = analyze_wat_str(OUT_OF_BOUNDS_SEGMENT_WAT)
+        .expect("valid WAT should analyze successfully");
+    let report = &analysis.memory_analysis.memory_layout.data_segment_report;
+    assert_eq!(
+        report.out_of_bounds_segments,
+        vec![0],
+        "a 1-page memory is 65536 bytes, and this segment ends at 65538"
+    );
+
+    let overflows = &analysis.memory_analysis.safety_analysis.potential_overflows;
+    assert!(
+        overflows
+            .iter()
+            .any(|o| o.operation_type == "DataSegmentOutOfBounds"
+                && matches!(o.risk_level, RiskLevel::High)),
+        "a segment that traps at instantiation should be High risk, got: {:?}",
+        overflows
+    );
+}
+
+#[test]
+fn test_large_zero_fill_gap_is_reported() {
+    let mut data = String::from("\\01\\02\\03\\04");
+    data.push_str(&"\\00".repeat(300));
+    let wat = format!(
+        r#"(module (memory 1) (data (i32.const 0) "{}"))"#,
+        data
+    );
+    let analysis = analyze_wat_str(&wat).expect("valid WAT should analyze successfully");
+    let report = &analysis.memory_analysis.memory_layout.data_segment_report;
+    assert!(
+        report
+            .zero_fill_gaps
+            .iter()
+            .any(|g| g.segment_index == 0 && g.trailing_zero_bytes == 300),
+        "300 trailing zero bytes duplicate the memory's already-zeroed default, got: {:?}",
+        report.zero_fill_gaps
+    );
+}
+
+const LARGE_DATA_SEGMENT_WAT_PREFIX: &str = r#"(module (memory 3) (data (i32.const 0) ""#;
+
+#[test]
+fn test_large_individual_segment_is_flagged_by_index_not_aggregate() {
+    let payload = "\\ff".repeat(100 * 1024 + 1);
+    let wat = format!("{}{}\"))", LARGE_DATA_SEGMENT_WAT_PREFIX, payload);
+    let analysis = analyze_wat_str(&wat).expect("valid WAT should analyze successfully");
+    assert!(
+        analysis
+            .memory_analysis
+            .optimization_opportunities
+            .iter()
+            .any(|opt| matches!(opt.optimization_type, OptimizationType::ReduceMemoryFootprint)
+                && opt.description.contains("[0]")),
+        "the single oversized segment should be called out by index"
+    );
+}
+
+const MASKED_ADDRESS_OUT_OF_BOUNDS_WAT: &str = r#"
+(module
+  (memory 1)
+  (func $f (export "f") (param $x i32)
+    local.get $x
+    i32.const 131071
+    i32.and
+    i32.load
+    drop)
+)
+"#;
+
+#[test]
+fn test_masked_address_proven_to_exceed_memory_limit_is_flagged_high_risk() {
+    let analysis = analyze_wat_str(MASKED_ADDRESS_OUT_OF_BOUNDS_WAT)
+        .expect("valid WAT should analyze successfully");
+    let overflows = &analysis.memory_analysis.safety_analysis.potential_overflows;
+    let finding = overflows
+        .iter()
+        .find(|o| o.operation_type == "BoundsAnalysis")
+        .unwrap_or_else(|| {
+            panic!(
+                "$x & 0x1FFFF bounds the address to [0, 131071], which exceeds a 1-page (65536 byte) memory, got: {:?}",
+                overflows
+            )
+        });
+    assert!(matches!(finding.risk_level, RiskLevel::High));
+    assert_eq!(finding.inferred_address_range, Some((0, 131075)));
+}
+
+const MASKED_ADDRESS_IN_BOUNDS_WAT: &str = r#"
+(module
+  (memory 1)
+  (func $f (export "f") (param $x i32)
+    local.get $x
+    i32.const 1023
+    i32.and
+    i32.load
+    drop)
+)
+"#;
+
+#[test]
+fn test_masked_address_proven_within_memory_limit_is_not_flagged() {
+    let analysis = analyze_wat_str(MASKED_ADDRESS_IN_BOUNDS_WAT)
+        .expect("valid WAT should analyze successfully");
+    let overflows = &analysis.memory_analysis.safety_analysis.potential_overflows;
+    assert!(
+        !overflows.iter().any(|o| o.operation_type == "BoundsAnalysis"),
+        "$x & 0x3FF bounds the address to [0, 1023], well within a 1-page memory, got: {:?}",
+        overflows
+    );
+}
+
+const GUARD_REFINED_ADDRESS_STILL_OUT_OF_BOUNDS_WAT: &str = r#"
+(module
+  (memory 1)
+  (func $f (export "f") (param $x i32)
+    local.get $x
+    i32.const 100000
+    i32.lt_u
+    if
+      local.get $x
+      i32.load
+      drop
+    end)
+)
+"#;
+
+#[test]
+fn test_if_guard_refines_local_but_bound_still_exceeds_memory_limit() {
+    let analysis = analyze_wat_str(GUARD_REFINED_ADDRESS_STILL_OUT_OF_BOUNDS_WAT)
+        .expect("valid WAT should analyze successfully");
+    let overflows = &analysis.memory_analysis.safety_analysis.potential_overflows;
+    assert!(
+        overflows.iter().any(|o| o.operation_type == "BoundsAnalysis"),
+        "the `$x < 100000` guard refines $x to [0, 99999], which still exceeds the 1-page memory \
+         limit once the load's own size is added, got: {:?}",
+        overflows
+    );
+}
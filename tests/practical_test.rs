@@ -1,13 +1,11 @@
 // practical_tests.rs
 
 use std::fs;
-use std::process::Command;
-use wasm_inspector::{ModuleAnalyzer, ModuleInfo, WasmParser, analyze_wasm_module}; // Ensure all used types are imported
+use wasm_inspector::{ModuleAnalyzer, ModuleInfo, WasmParser, analyze_wasm_module, analyze_wat_str}; // Ensure all used types are imported
 
-// Helper to create a real WASM file using wat2wasm if available
-fn create_test_wasm_with_wat() -> Option<Vec<u8>> {
-    // Simple WAT (WebAssembly Text) that we can convert to binary
-    let wat_content = r#"
+// WAT source for a tiny `add` module, assembled in-process via `analyze_wat_str`
+// instead of shelling out to `wat2wasm`.
+const ADD_WAT: &str = r#"
 (module
   (func $add (param i32 i32) (result i32)
     local.get 0
@@ -16,36 +14,6 @@ fn create_test_wasm_with_wat() -> Option<Vec<u8>> {
   (export "add" (func $add))
 )
 "#;
-    let temp_wat_file = "temp_test.wat";
-    let temp_wasm_file = "temp_test.wasm";
-
-    if fs::write(temp_wat_file, wat_content).is_err() {
-        return None;
-    }
-
-    // Try to use wat2wasm if available (from WABT tools)
-    let status = Command::new("wat2wasm")
-        .arg(temp_wat_file)
-        .arg("-o")
-        .arg(temp_wasm_file)
-        .status();
-
-    let _ = fs::remove_file(temp_wat_file); // Clean up .wat file
-
-    match status {
-        Ok(exit_status) if exit_status.success() => {
-            let wasm_bytes = fs::read(temp_wasm_file);
-            let _ = fs::remove_file(temp_wasm_file); // Clean up .wasm file
-            wasm_bytes.ok()
-        }
-        _ => {
-            let _ = fs::remove_file(temp_wasm_file); // Clean up .wasm file if it exists
-            // Fallback: A very minimal valid WASM binary if wat2wasm fails or is not installed
-            // (module)
-            Some(vec![0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00])
-        }
-    }
-}
 
 #[test]
 fn test_basic_error_handling() {
@@ -94,6 +62,7 @@ fn test_library_integration() {
         start_function: None,
         custom_sections: Vec::new(),
         function_call_instructions: Vec::new(), // FIX: Added
+        indirect_call_instructions: Vec::new(),
         type_signatures: Vec::new(),            // FIX: Added
     };
 
@@ -178,27 +147,19 @@ fn test_analyzer_components() {
 // Integration test that checks if we can find real WASM files
 #[test]
 fn test_with_real_wasm_if_available() {
-    if let Some(wasm_bytes) = create_test_wasm_with_wat() {
-        println!("üîç Testing with generated WASM (add function)");
-        let result = analyze_wasm_module(&wasm_bytes);
-        match result {
-            Ok(analysis) => {
-                println!("‚úÖ Successfully analyzed generated WASM");
-                assert_eq!(analysis.module_info.exports.len(), 1);
-                if !analysis.module_info.exports.is_empty() {
-                    assert_eq!(analysis.module_info.exports[0].name, "add");
-                }
-                // Add more assertions based on the known structure of add.wat
-                return; // Exit after successful test with generated WASM
-            }
-            Err(e) => {
-                panic!("‚ö†Ô∏è Failed to analyze generated WASM: {}", e);
+    match analyze_wat_str(ADD_WAT) {
+        Ok(analysis) => {
+            println!("✅ Successfully analyzed generated WASM");
+            assert_eq!(analysis.module_info.exports.len(), 1);
+            if !analysis.module_info.exports.is_empty() {
+                assert_eq!(analysis.module_info.exports[0].name, "add");
             }
+            // Add more assertions based on the known structure of add.wat
+            return; // Exit after successful test with generated WASM
+        }
+        Err(e) => {
+            panic!("⚠️ Failed to analyze generated WASM: {}", e);
         }
-    } else {
-        println!(
-            "‚ÑπÔ∏è  wat2wasm not found or failed, or fallback minimal WASM used by create_test_wasm_with_wat. Proceeding to search for other .wasm files."
-        );
     }
 
     // Look for any .wasm files in common locations
@@ -277,56 +238,6 @@ fn test_with_real_wasm_if_available() {
     }
 }
 
-#[cfg(test)]
-mod performance_tests {
-    use super::*; // Imports analyze_wasm_module from parent scope
-    use std::time::Instant;
-
-    #[test]
-    fn test_analysis_performance_on_invalid_input() {
-        // Test that analysis doesn't take too long even with invalid input
-        let invalid_large_input = vec![0u8; 10_000]; // 10KB of zeros
-
-        let start = Instant::now();
-        let result = analyze_wasm_module(&invalid_large_input);
-        let duration = start.elapsed();
-
-        // Should fail quickly, not hang
-        assert!(
-            duration.as_millis() < 1000,
-            "Analysis of invalid input should complete within 1 second, took: {:?}",
-            duration
-        );
-        assert!(
-            result.is_err(),
-            "Should reject invalid input. Result: {:?}",
-            result
-        );
-    }
-
-    #[test]
-    fn test_analysis_performance_on_minimal_valid_input() {
-        // A very minimal valid WASM module: (module)
-        let minimal_valid_wasm = vec![0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
-
-        let start = Instant::now();
-        let result = analyze_wasm_module(&minimal_valid_wasm);
-        let duration = start.elapsed();
-
-        assert!(
-            duration.as_millis() < 1000,
-            "Analysis of minimal valid input should complete within 1 second, took: {:?}",
-            duration
-        );
-        // This should ideally pass, or fail gracefully if the analyzer expects more sections
-        match result {
-            Ok(_) => {
-                println!("‚úÖ Minimal valid WASM analyzed successfully within performance limits.")
-            }
-            Err(e) => println!(
-                "‚ÑπÔ∏è Minimal valid WASM analysis failed (might be ok if analyzer is strict): {}",
-                e
-            ),
-        }
-    }
-}
+// Wall-clock timing checks used to live here as a `performance_tests` module built
+// on `std::time::Instant`. They've been replaced by the reproducible, regression-
+// tracked throughput benchmarks in `benches/wasm_analysis.rs`.
@@ -0,0 +1,126 @@
+// tests/proptest_fuzz.rs
+//
+// Differential-style fuzzing: feed `wasm-smith`-generated, structurally-valid
+// (but otherwise arbitrary) modules through the full analysis pipeline and assert
+// it never panics, plus a handful of cross-field invariants that must hold for any
+// module the parser accepts.
+
+use arbitrary::Unstructured;
+use proptest::prelude::*;
+use wasm_inspector::analyze_wasm_module;
+
+// Broadened past `Config::default()` so generated modules exercise reference
+// types, SIMD, bulk memory, and tail calls too — not just the MVP feature set —
+// since those are exactly the encodings most likely to trip up section-walking
+// code that wasn't written with them in mind. Kept in sync with the identical
+// helper in `fuzz/fuzz_targets/analyze_wasm_module.rs` (separate crates, so it
+// can't be shared).
+fn fuzzing_config() -> wasm_smith::Config {
+    wasm_smith::Config {
+        reference_types_enabled: true,
+        multi_value_enabled: true,
+        bulk_memory_enabled: true,
+        simd_enabled: true,
+        tail_call_enabled: true,
+        threads_enabled: true,
+        saturating_float_to_int_enabled: true,
+        sign_extension_ops_enabled: true,
+        ..Default::default()
+    }
+}
+
+fn arbitrary_module(seed: &[u8]) -> Option<Vec<u8>> {
+    let mut u = Unstructured::new(seed);
+    let module = wasm_smith::Module::new(fuzzing_config(), &mut u).ok()?;
+    Some(module.to_bytes())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn analyze_wasm_module_never_panics(seed in prop::collection::vec(any::<u8>(), 0..4096)) {
+        let bytes = match arbitrary_module(&seed) {
+            Some(bytes) => bytes,
+            None => return Ok(()),
+        };
+
+        // The point of this test is solely "does not panic"; a well-formed,
+        // wasm-smith-generated module may still legitimately fail analysis (e.g.
+        // exotic proposals this crate doesn't model yet), so we don't assert `Ok`.
+        let analysis = match analyze_wasm_module(&bytes) {
+            Ok(analysis) => analysis,
+            Err(_) => return Ok(()),
+        };
+
+        let imported_function_count = analysis
+            .module_info
+            .imports
+            .iter()
+            .filter(|i| matches!(i.kind, wasm_inspector::ImportKind::Function { .. }))
+            .count() as u32;
+        let total_function_count = imported_function_count + analysis.module_info.functions.len() as u32;
+
+        for &(caller, callee) in &analysis.module_info.function_call_instructions {
+            prop_assert!(caller < total_function_count, "call site {} out of range ({} functions)", caller, total_function_count);
+            prop_assert!(callee < total_function_count, "call target {} out of range ({} functions)", callee, total_function_count);
+        }
+
+        for export in &analysis.module_info.exports {
+            if export.kind == wasm_inspector::ExportKind::Function {
+                prop_assert!(
+                    export.index < total_function_count,
+                    "export \"{}\" references nonexistent function {}",
+                    export.name,
+                    export.index
+                );
+            }
+        }
+
+        if let Some(start) = analysis.module_info.start_function {
+            prop_assert!(start < total_function_count, "start function {} out of range", start);
+        }
+
+        // `module_size` accounts for every function body byte (among other
+        // sections), so it can never be smaller than `code_size` alone.
+        prop_assert!(
+            analysis.performance_metrics.module_size >= analysis.performance_metrics.code_size,
+            "module_size {} smaller than code_size {}",
+            analysis.performance_metrics.module_size,
+            analysis.performance_metrics.code_size
+        );
+
+        // Dead-code detection can only ever point at functions that exist.
+        for &unreachable in &analysis.call_graph.unreachable_functions {
+            prop_assert!(
+                unreachable < total_function_count,
+                "unreachable function {} out of range ({} functions)",
+                unreachable,
+                total_function_count
+            );
+        }
+
+        // A declared initial memory can never exceed a declared maximum.
+        let memory_estimate = &analysis.performance_metrics.memory_usage_estimate;
+        if let Some(max_kb) = memory_estimate.max_memory_kb {
+            prop_assert!(
+                memory_estimate.initial_memory_kb <= max_kb,
+                "initial memory {}KB exceeds declared maximum {}KB",
+                memory_estimate.initial_memory_kb,
+                max_kb
+            );
+        }
+
+        // Cyclomatic complexity is decision_points + 1, so it's always >= 1,
+        // and the reported top-N list is never larger than what was asked for.
+        let function_complexity = &analysis.performance_metrics.function_complexity;
+        for f in &function_complexity.most_complex {
+            prop_assert!(
+                f.cyclomatic_complexity >= 1,
+                "function {} has impossible complexity 0",
+                f.function_index
+            );
+        }
+        prop_assert!(function_complexity.most_complex.len() <= 10);
+    }
+}
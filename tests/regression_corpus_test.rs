@@ -0,0 +1,33 @@
+// tests/regression_corpus_test.rs
+//
+// Every time a fuzz run (or the proptest harness) finds an input that panics
+// `analyze_wasm_module`, its raw bytes get checked in under `tests/regressions/`
+// and replayed here. This turns each crash into a permanent regression test
+// instead of a one-off bug report, and keeps the corpus of known-bad inputs
+// growing alongside the fixed `MINIMAL_WASM`/`WASI_WASM` fixtures.
+use std::panic;
+use std::path::Path;
+
+#[test]
+fn regression_corpus_never_panics() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/regressions");
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(_) => return, // No regressions checked in yet.
+    };
+
+    for entry in entries {
+        let path = entry.expect("failed to read regressions dir entry").path();
+        if path.extension().and_then(|e| e.to_str()) != Some("wasm") {
+            continue;
+        }
+
+        let bytes = std::fs::read(&path).expect("failed to read regression fixture");
+        let result = panic::catch_unwind(|| wasm_inspector::analyze_wasm_module(&bytes));
+        assert!(
+            result.is_ok(),
+            "analyze_wasm_module panicked on regression fixture {:?}",
+            path
+        );
+    }
+}
@@ -0,0 +1,154 @@
+// tests/transform_test.rs
+
+use wasm_inspector::policy::Policy;
+use wasm_inspector::transform::{harden, re_encode, HardenAction, ModuleTransform};
+use wasm_inspector::{analyze_wasm_module, analyze_wat_str};
+
+const ADD_WITH_NAME_SECTION_WAT: &str = r#"
+(module
+  (func $add (param i32 i32) (result i32)
+    local.get 0
+    local.get 1
+    i32.add)
+  (export "add" (func $add))
+)
+"#;
+
+#[test]
+fn test_strip_custom_sections_round_trips() {
+    let original = wat::parse_str(ADD_WITH_NAME_SECTION_WAT).expect("valid WAT should assemble");
+    let original_analysis = analyze_wasm_module(&original).expect("original module should analyze");
+    assert!(
+        !original_analysis.module_info.custom_sections.is_empty(),
+        "fixture should start with at least one custom section (e.g. \"name\")"
+    );
+
+    let stripped = re_encode(&original, &[ModuleTransform::StripCustomSections])
+        .expect("stripping custom sections should produce a valid module");
+
+    let stripped_analysis = analyze_wasm_module(&stripped).expect("stripped module should still parse");
+    assert_eq!(
+        stripped_analysis.module_info.custom_sections.len(),
+        0,
+        "custom section count should drop to zero after stripping"
+    );
+
+    // The actual function signature/behavior is untouched by stripping names.
+    assert_eq!(stripped_analysis.module_info.exports.len(), 1);
+    assert_eq!(stripped_analysis.module_info.exports[0].name, "add");
+}
+
+#[test]
+fn test_keep_only_custom_sections_preserves_named_section() {
+    let original = wat::parse_str(ADD_WITH_NAME_SECTION_WAT).expect("valid WAT should assemble");
+
+    let kept = re_encode(
+        &original,
+        &[ModuleTransform::KeepOnlyCustom(vec!["name".to_string()])],
+    )
+    .expect("keeping a known custom section should produce a valid module");
+
+    let kept_analysis = analyze_wasm_module(&kept).expect("module should still parse");
+    assert!(
+        kept_analysis
+            .module_info
+            .custom_sections
+            .iter()
+            .any(|c| c.name == "name"),
+        "the \"name\" custom section should have survived KeepOnlyCustom"
+    );
+}
+
+#[test]
+fn test_remove_unreachable_functions_drops_dead_code() {
+    let wat = r#"
+        (module
+          (func $dead (result i32) i32.const 1)
+          (func $entry (result i32) i32.const 2)
+          (export "entry" (func $entry)))
+    "#;
+    let analysis = analyze_wat_str(wat).expect("valid WAT should analyze successfully");
+    assert_eq!(
+        analysis.call_graph.unreachable_functions.len(),
+        1,
+        "the analyzer should flag $dead as unreachable"
+    );
+
+    let original = wat::parse_str(wat).expect("valid WAT should assemble");
+    let trimmed = re_encode(
+        &original,
+        &[ModuleTransform::RemoveUnreachableFunctions(
+            analysis.call_graph.unreachable_functions.clone(),
+        )],
+    )
+    .expect("removing unreachable functions should produce a valid module");
+
+    let trimmed_analysis = analyze_wasm_module(&trimmed).expect("trimmed module should still parse");
+    assert_eq!(
+        trimmed_analysis.module_info.functions.len(),
+        1,
+        "only the reachable $entry function should remain"
+    );
+    assert_eq!(trimmed_analysis.module_info.exports[0].name, "entry");
+}
+
+#[test]
+fn test_harden_stubs_a_called_denied_import() {
+    let wat = r#"
+        (module
+          (import "env" "evil" (func $evil (result i32)))
+          (func $entry (export "entry") (result i32)
+            call $evil))
+    "#;
+    let original = wat::parse_str(wat).expect("valid WAT should assemble");
+    let policy = Policy::new().deny("env.evil");
+
+    let (hardened, report) = harden(&original, &policy).expect("harden should produce a valid module");
+    assert_eq!(report.changes.len(), 1);
+    assert_eq!(report.changes[0].module, "env");
+    assert_eq!(report.changes[0].name, "evil");
+    assert_eq!(report.changes[0].action, HardenAction::Stubbed);
+
+    let hardened_analysis = analyze_wasm_module(&hardened).expect("hardened module should still parse");
+    assert!(
+        hardened_analysis.module_info.imports.is_empty(),
+        "the denied import should no longer appear in the import section"
+    );
+    assert_eq!(hardened_analysis.module_info.exports[0].name, "entry");
+}
+
+#[test]
+fn test_harden_removes_an_unreferenced_denied_import() {
+    let wat = r#"
+        (module
+          (import "env" "evil" (func $evil (result i32)))
+          (func $entry (export "entry") (result i32)
+            i32.const 1))
+    "#;
+    let original = wat::parse_str(wat).expect("valid WAT should assemble");
+    let policy = Policy::new().deny("env.evil");
+
+    let (hardened, report) = harden(&original, &policy).expect("harden should produce a valid module");
+    assert_eq!(report.changes.len(), 1);
+    assert_eq!(report.changes[0].action, HardenAction::Removed);
+
+    let hardened_analysis = analyze_wasm_module(&hardened).expect("hardened module should still parse");
+    assert!(hardened_analysis.module_info.imports.is_empty());
+    assert_eq!(hardened_analysis.module_info.exports[0].name, "entry");
+}
+
+#[test]
+fn test_harden_leaves_allowed_imports_untouched() {
+    let wat = r#"
+        (module
+          (import "env" "ok" (func $ok (result i32)))
+          (func $entry (export "entry") (result i32)
+            call $ok))
+    "#;
+    let original = wat::parse_str(wat).expect("valid WAT should assemble");
+    let policy = Policy::new().allow("env.ok");
+
+    let (hardened, report) = harden(&original, &policy).expect("harden should produce a valid module");
+    assert!(report.changes.is_empty(), "nothing should change when no import is denied");
+    assert_eq!(hardened, original);
+}